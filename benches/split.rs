@@ -0,0 +1,99 @@
+//! Timings for splitting at high thresholds, where polynomial evaluation
+//! dominates. Run with `cargo bench --bench split`.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use num_bigint::{BigUint, RandBigInt};
+use prime_pmpt::{shamir_split_shares, StandardPrime};
+use rand::SeedableRng;
+use rand_chacha::ChaCha20Rng;
+
+/// Runs `f` until a second has passed and returns the mean time per run.
+fn time<T>(mut f: impl FnMut() -> T) -> Duration {
+    let start = Instant::now();
+    let mut runs = 0u32;
+    while start.elapsed() < Duration::from_secs(1) {
+        black_box(f());
+        runs += 1;
+    }
+    start.elapsed() / runs
+}
+
+/// The evaluation splitting used before Horner's rule: each coefficient
+/// times a fresh `modpow` of `x`.
+fn evaluate_per_term(coefficients: &[BigUint], x: &BigUint, modulus: &BigUint) -> BigUint {
+    let mut y = BigUint::ZERO;
+    for (i, coeff) in coefficients.iter().enumerate() {
+        y += coeff * x.modpow(&BigUint::from(i as u64), modulus);
+        y %= modulus;
+    }
+    y
+}
+
+fn evaluate_horner(coefficients: &[BigUint], x: &BigUint, modulus: &BigUint) -> BigUint {
+    let mut y = BigUint::ZERO;
+    for coeff in coefficients.iter().rev() {
+        y = (y * x + coeff) % modulus;
+    }
+    y
+}
+
+fn main() {
+    let mut rng = ChaCha20Rng::seed_from_u64(0);
+    let fields = [
+        ("curve25519", StandardPrime::Curve25519.modulus()),
+        ("modp-2048", StandardPrime::Modp2048.modulus()),
+    ];
+    // The per-term baseline against Horner's rule, on plain `BigUint`
+    // arithmetic, for one polynomial of degree t - 1 at t points.
+    for (name, modulus) in &fields {
+        for threshold in [16, 64, 256] {
+            let coefficients: Vec<BigUint> = (0..threshold)
+                .map(|_| rng.gen_biguint_below(modulus))
+                .collect();
+            let xs: Vec<BigUint> = (1..=threshold as u64).map(BigUint::from).collect();
+            let per_term = time(|| {
+                xs.iter()
+                    .map(|x| evaluate_per_term(&coefficients, x, modulus))
+                    .collect::<Vec<_>>()
+            });
+            let horner = time(|| {
+                xs.iter()
+                    .map(|x| evaluate_horner(&coefficients, x, modulus))
+                    .collect::<Vec<_>>()
+            });
+            println!(
+                "evaluate {name:>10}, t = n = {threshold:>3}: per-term {per_term:>12.2?}, Horner {horner:>12.2?}"
+            );
+        }
+    }
+    for (name, modulus) in &fields {
+        let secret = BigUint::from(42u32);
+        for threshold in [16, 64, 256] {
+            let elapsed = time(|| {
+                shamir_split_shares(&secret, threshold, threshold, modulus, &mut rng)
+                    .expect("valid parameters")
+            });
+            println!("split {name:>10}, t = n = {threshold:>3}: {elapsed:>12.2?}");
+        }
+    }
+    // Many shares at a low threshold, as for erasure coding, which the
+    // `parallel` feature spreads over threads.
+    let modulus = StandardPrime::Curve25519.modulus();
+    let secret = BigUint::from(42u32);
+    for shares in [32, 1024, 16384] {
+        let elapsed = time(|| {
+            shamir_split_shares(&secret, 16, shares, &modulus, &mut rng).expect("valid parameters")
+        });
+        println!("split curve25519, t = 16, n = {shares:>5}: {elapsed:>12.2?}");
+    }
+    // Thresholds of 512 and up evaluate through subproduct trees.
+    for threshold in [1024, 4096] {
+        let elapsed = time(|| {
+            shamir_split_shares(&secret, threshold, 4096, &modulus, &mut rng)
+                .expect("valid parameters")
+        });
+        println!("split curve25519, t = {threshold:>4}, n = 4096: {elapsed:>12.2?}");
+    }
+}
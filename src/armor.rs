@@ -0,0 +1,144 @@
+//! ASCII armor for copying shares into tickets and emails.
+//!
+//! The armored form is the share's DER encoding in base64 followed by an
+//! OpenPGP-style CRC-24 checksum (RFC 4880 §6.1), between a header and a
+//! footer line:
+//!
+//! ```text
+//! -----BEGIN SHAMIR ARMORED SHARE-----
+//! MIIBWwIBAQIBAQIBAgQIpLhCaRiISdoCggEAcSKi3qAJtS4PgnfPRTHhqntIgEdh
+//! ...
+//! =3kKd
+//! -----END SHAMIR ARMORED SHARE-----
+//! ```
+//!
+//! The single-line style puts everything on one line separated by spaces.
+//! Parsing ignores all whitespace, so either style survives re-wrapping by
+//! mail clients and ticket systems.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use zeroize::Zeroize;
+
+use crate::{ShamirError, Share};
+
+const BEGIN: &str = "-----BEGIN SHAMIR ARMORED SHARE-----";
+const END: &str = "-----END SHAMIR ARMORED SHARE-----";
+const LINE_WIDTH: usize = 64;
+
+/// Layout of armored output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ArmorStyle {
+    /// Header, base64, checksum and footer on one line.
+    SingleLine,
+    /// Base64 wrapped at 64 columns, one element per line.
+    #[default]
+    Wrapped,
+}
+
+/// CRC-24 as used by OpenPGP armor.
+fn crc24(data: &[u8]) -> u32 {
+    let mut crc: u32 = 0x00b7_04ce;
+    for &byte in data {
+        crc ^= (byte as u32) << 16;
+        for _ in 0..8 {
+            crc <<= 1;
+            if crc & 0x0100_0000 != 0 {
+                crc ^= 0x0186_4cfb;
+            }
+        }
+    }
+    crc & 0x00ff_ffff
+}
+
+/// Armors arbitrary bytes.
+pub fn armor(data: &[u8], style: ArmorStyle) -> String {
+    let mut body = STANDARD.encode(data);
+    let checksum = STANDARD.encode(&crc24(data).to_be_bytes()[1..]);
+    let mut out = String::with_capacity(body.len() + 2 * BEGIN.len() + 16);
+    out.push_str(BEGIN);
+    match style {
+        ArmorStyle::SingleLine => {
+            out.push(' ');
+            out.push_str(&body);
+            out.push_str(" =");
+            out.push_str(&checksum);
+            out.push(' ');
+        }
+        ArmorStyle::Wrapped => {
+            out.push('\n');
+            for line in body.as_bytes().chunks(LINE_WIDTH) {
+                out.push_str(core::str::from_utf8(line).expect("base64 is ASCII"));
+                out.push('\n');
+            }
+            out.push('=');
+            out.push_str(&checksum);
+            out.push('\n');
+        }
+    }
+    out.push_str(END);
+    out.push('\n');
+    body.zeroize();
+    out
+}
+
+/// Recovers the bytes from armored text, ignoring whitespace anywhere and
+/// verifying the checksum.
+pub fn dearmor(text: &str) -> Result<Vec<u8>, ShamirError> {
+    let mut compact: String = text.chars().filter(|c| !c.is_whitespace()).collect();
+    let result = dearmor_compact(&compact);
+    compact.zeroize();
+    result
+}
+
+fn dearmor_compact(compact: &str) -> Result<Vec<u8>, ShamirError> {
+    let begin: String = BEGIN.chars().filter(|c| !c.is_whitespace()).collect();
+    let end: String = END.chars().filter(|c| !c.is_whitespace()).collect();
+    let body = compact
+        .split_once(begin.as_str())
+        .and_then(|(_, rest)| rest.split_once(end.as_str()))
+        .map(|(body, _)| body)
+        .ok_or(ShamirError::MalformedShare(
+            "missing armor header or footer",
+        ))?;
+    let split = body
+        .len()
+        .checked_sub(5)
+        .filter(|&at| body.as_bytes()[at] == b'=')
+        .ok_or(ShamirError::MalformedShare("missing armor checksum"))?;
+    let (data, checksum) = (&body[..split], &body[split + 1..]);
+    let data = STANDARD
+        .decode(data)
+        .map_err(|_| ShamirError::MalformedShare("invalid base64 in armor"))?;
+    let checksum = STANDARD
+        .decode(checksum)
+        .map_err(|_| ShamirError::MalformedShare("invalid armor checksum"))?;
+    let expected = crc24(&data).to_be_bytes();
+    if checksum[..] != expected[1..] {
+        let mut data = data;
+        data.zeroize();
+        return Err(ShamirError::MalformedShare("armor checksum mismatch"));
+    }
+    Ok(data)
+}
+
+impl Share {
+    /// ASCII-armored DER encoding of the share.
+    pub fn to_armor(&self, style: ArmorStyle) -> String {
+        let mut der = self.to_der();
+        let armored = armor(&der, style);
+        der.zeroize();
+        armored
+    }
+
+    /// Parses a share armored by [`Share::to_armor`].
+    pub fn from_armor(text: &str) -> Result<Self, ShamirError> {
+        let mut der = dearmor(text)?;
+        let share = Share::from_der(&der);
+        der.zeroize();
+        share
+    }
+}
@@ -0,0 +1,178 @@
+//! DER and PEM encodings of shares.
+//!
+//! ```text
+//! ShamirShare ::= SEQUENCE {
+//!     version      INTEGER (1),
+//!     x            INTEGER,
+//!     threshold    INTEGER,
+//!     fingerprint  OCTET STRING (SIZE (8)),
+//!     y            INTEGER,
+//!     blinding     [0] IMPLICIT INTEGER OPTIONAL,
+//!     digest       [1] IMPLICIT OCTET STRING (SIZE (48)) OPTIONAL,
+//!     setId        [2] IMPLICIT OCTET STRING (SIZE (16)) OPTIONAL,
+//!     mac          [3] IMPLICIT OCTET STRING (SIZE (32)) OPTIONAL,
+//!     signature    [4] IMPLICIT OCTET STRING (SIZE (72)) OPTIONAL
+//! }
+//! ```
+//!
+//! The PEM form wraps the DER bytes in `-----BEGIN SHAMIR SHARE-----`.
+
+#[cfg(feature = "std")]
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use num_bigint::BigUint;
+use num_traits::{One, Zero};
+use zeroize::Zeroize;
+
+use crate::der::{self, Reader};
+use crate::{DealerSignature, Fingerprint, SecretDigest, ShamirError, Share, ShareMac, ShareSetId};
+
+/// PEM label for shares.
+#[cfg(feature = "std")]
+pub const SHARE_PEM_TAG: &str = "SHAMIR SHARE";
+
+const BLINDING: u8 = 0x80;
+const DIGEST: u8 = 0x81;
+const SET_ID: u8 = 0x82;
+const MAC: u8 = 0x83;
+const SIGNATURE: u8 = 0x84;
+
+impl Share {
+    /// Encodes the share as a DER `ShamirShare` structure.
+    pub fn to_der(&self) -> Vec<u8> {
+        let mut fields = Vec::new();
+        der::write_uint(&mut fields, &BigUint::one());
+        der::write_uint(&mut fields, self.x());
+        der::write_uint(&mut fields, &BigUint::from(self.threshold() as u64));
+        der::write(
+            &mut fields,
+            der::OCTET_STRING,
+            self.fingerprint().as_bytes(),
+        );
+        der::write_uint(&mut fields, self.y());
+        if let Some(blinding) = self.blinding() {
+            der::write_uint_tagged(&mut fields, BLINDING, blinding);
+        }
+        if let Some(digest) = self.digest() {
+            let mut bytes = [0u8; 48];
+            bytes[..16].copy_from_slice(digest.salt());
+            bytes[16..].copy_from_slice(digest.hash());
+            der::write(&mut fields, DIGEST, &bytes);
+        }
+        if let Some(set_id) = self.set_id() {
+            der::write(&mut fields, SET_ID, set_id.as_bytes());
+        }
+        if let Some(mac) = self.mac() {
+            der::write(&mut fields, MAC, mac.as_bytes());
+        }
+        if let Some(signature) = self.signature() {
+            der::write(&mut fields, SIGNATURE, &signature.to_bytes());
+        }
+        let mut out = Vec::with_capacity(fields.len() + 4);
+        der::write(&mut out, der::SEQUENCE, &fields);
+        fields.zeroize();
+        out
+    }
+
+    /// Decodes a DER `ShamirShare` structure.
+    pub fn from_der(input: &[u8]) -> Result<Self, ShamirError> {
+        let mut outer = Reader::new(input);
+        let mut fields = outer.sequence()?;
+        outer.finish()?;
+        if !fields.uint()?.is_one() {
+            return Err(ShamirError::MalformedShare("unsupported share version"));
+        }
+        let small = |value: BigUint| {
+            u64::try_from(&value)
+                .ok()
+                .and_then(|value| usize::try_from(value).ok())
+                .ok_or(ShamirError::MalformedShare("integer out of range"))
+        };
+        let x = fields.uint()?;
+        if x.is_zero() {
+            return Err(ShamirError::MalformedShare("x must be nonzero"));
+        }
+        let threshold = small(fields.uint()?)?;
+        if threshold < 2 {
+            return Err(ShamirError::MalformedShare("threshold must be at least 2"));
+        }
+        let fingerprint = fields
+            .read(der::OCTET_STRING)?
+            .try_into()
+            .map_err(|_| ShamirError::MalformedShare("invalid fingerprint"))?;
+        let mut share = Share::new(
+            x,
+            fields.uint()?,
+            threshold,
+            Fingerprint::from_bytes(fingerprint),
+        );
+        if fields.peek_tag() == Some(BLINDING) {
+            share = share.with_blinding(fields.uint_tagged(BLINDING)?);
+        }
+        if fields.peek_tag() == Some(DIGEST) {
+            let bytes: &[u8; 48] = fields
+                .read(DIGEST)?
+                .try_into()
+                .map_err(|_| ShamirError::MalformedShare("invalid secret digest"))?;
+            let salt = bytes[..16].try_into().expect("16 bytes");
+            let hash = bytes[16..].try_into().expect("32 bytes");
+            share = share.with_digest(SecretDigest::from_parts(salt, hash));
+        }
+        if fields.peek_tag() == Some(SET_ID) {
+            let set_id = fields
+                .read(SET_ID)?
+                .try_into()
+                .map_err(|_| ShamirError::MalformedShare("invalid share set id"))?;
+            share = share.with_set_id(ShareSetId::from_bytes(set_id));
+        }
+        if fields.peek_tag() == Some(MAC) {
+            let mac = fields
+                .read(MAC)?
+                .try_into()
+                .map_err(|_| ShamirError::MalformedShare("invalid share MAC"))?;
+            share = share.with_mac(ShareMac::from_bytes(mac));
+        }
+        if fields.peek_tag() == Some(SIGNATURE) {
+            let signature = fields
+                .read(SIGNATURE)?
+                .try_into()
+                .map_err(|_| ShamirError::MalformedShare("invalid dealer signature"))?;
+            share = share.with_signature(DealerSignature::from_bytes(signature));
+        }
+        fields.finish()?;
+        Ok(share)
+    }
+
+    /// Encodes the share as PEM with the `SHAMIR SHARE` label.
+    #[cfg(feature = "std")]
+    pub fn to_pem(&self) -> String {
+        let pem = pem::Pem {
+            tag: SHARE_PEM_TAG.into(),
+            contents: self.to_der(),
+        };
+        let encoded = pem::encode_config(
+            &pem,
+            pem::EncodeConfig {
+                line_ending: pem::LineEnding::LF,
+            },
+        );
+        let mut contents = pem.contents;
+        contents.zeroize();
+        encoded
+    }
+
+    /// Decodes a PEM-armored share.
+    #[cfg(feature = "std")]
+    pub fn from_pem(input: &str) -> Result<Self, ShamirError> {
+        let mut pem =
+            pem::parse(input).map_err(|_| ShamirError::MalformedShare("invalid PEM armor"))?;
+        if pem.tag != SHARE_PEM_TAG {
+            pem.contents.zeroize();
+            return Err(ShamirError::MalformedShare("not a SHAMIR SHARE PEM block"));
+        }
+        let share = Share::from_der(&pem.contents);
+        pem.contents.zeroize();
+        share
+    }
+}
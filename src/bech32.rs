@@ -0,0 +1,158 @@
+//! Bech32 and Bech32m share strings (BIP-173, BIP-350).
+//!
+//! The share's DER encoding is written in the bech32 alphabet after the
+//! human-readable part `shamir`, so a share reads as `shamir1...` and a
+//! mistyped character is caught by the checksum. BIP-173's 90-character
+//! limit is not applied, since shares over large fields are longer; the
+//! checksum still detects any single error and most bursts.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use zeroize::Zeroize;
+
+use crate::{ShamirError, Share};
+
+/// Human-readable part of share strings.
+pub const SHARE_HRP: &str = "shamir";
+
+const CHARSET: &[u8; 32] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+
+/// Checksum flavour; Bech32m is the default for new encodings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Bech32Variant {
+    Bech32,
+    #[default]
+    Bech32m,
+}
+
+impl Bech32Variant {
+    fn constant(self) -> u32 {
+        match self {
+            Bech32Variant::Bech32 => 1,
+            Bech32Variant::Bech32m => 0x2bc8_30a3,
+        }
+    }
+}
+
+fn polymod(values: impl Iterator<Item = u8>) -> u32 {
+    const GENERATOR: [u32; 5] = [
+        0x3b6a_57b2,
+        0x2650_8e6d,
+        0x1ea1_19fa,
+        0x3d42_33dd,
+        0x2a14_62b3,
+    ];
+    values.fold(1u32, |checksum, value| {
+        let top = checksum >> 25;
+        let checksum = ((checksum & 0x01ff_ffff) << 5) ^ value as u32;
+        GENERATOR
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| (top >> i) & 1 == 1)
+            .fold(checksum, |checksum, (_, g)| checksum ^ g)
+    })
+}
+
+fn hrp_expand(hrp: &str) -> impl Iterator<Item = u8> + '_ {
+    hrp.bytes()
+        .map(|b| b >> 5)
+        .chain(core::iter::once(0))
+        .chain(hrp.bytes().map(|b| b & 0x1f))
+}
+
+/// Regroups bits from `from`-bit to `to`-bit values.
+fn convert_bits(data: &[u8], from: u32, to: u32, pad: bool) -> Option<Vec<u8>> {
+    let mut acc = 0u32;
+    let mut bits = 0u32;
+    let mut out = Vec::with_capacity(data.len() * from as usize / to as usize + 1);
+    let max = (1u32 << to) - 1;
+    for &value in data {
+        acc = (acc << from) | value as u32;
+        bits += from;
+        while bits >= to {
+            bits -= to;
+            out.push(((acc >> bits) & max) as u8);
+        }
+    }
+    if pad && bits > 0 {
+        out.push(((acc << (to - bits)) & max) as u8);
+    } else if !pad && (bits >= from || (acc << (to - bits)) & max != 0) {
+        out.zeroize();
+        return None;
+    }
+    acc.zeroize();
+    Some(out)
+}
+
+/// Encodes `data` under `hrp`.
+pub(crate) fn encode(hrp: &str, data: &[u8], variant: Bech32Variant) -> String {
+    let mut values = convert_bits(data, 8, 5, true).expect("padding always succeeds");
+    let checksum = polymod(
+        hrp_expand(hrp)
+            .chain(values.iter().copied())
+            .chain([0u8; 6]),
+    ) ^ variant.constant();
+    values.extend((0..6).map(|i| ((checksum >> (5 * (5 - i))) & 0x1f) as u8));
+    let mut out = String::with_capacity(hrp.len() + 1 + values.len());
+    out.push_str(hrp);
+    out.push('1');
+    out.extend(values.iter().map(|&v| CHARSET[v as usize] as char));
+    values.zeroize();
+    out
+}
+
+/// Decodes a bech32 or bech32m string, returning its HRP, payload and
+/// checksum variant.
+pub(crate) fn decode(text: &str) -> Result<(String, Vec<u8>, Bech32Variant), ShamirError> {
+    let text = text.trim();
+    let malformed = ShamirError::MalformedShare;
+    if text.bytes().any(|b| b.is_ascii_lowercase()) && text.bytes().any(|b| b.is_ascii_uppercase())
+    {
+        return Err(malformed("mixed-case bech32 string"));
+    }
+    let text = text.to_ascii_lowercase();
+    let (hrp, data) = text
+        .rsplit_once('1')
+        .ok_or(malformed("missing bech32 separator"))?;
+    if hrp.is_empty() || data.len() < 6 || hrp.bytes().any(|b| !(33..=126).contains(&b)) {
+        return Err(malformed("malformed bech32 string"));
+    }
+    let mut values = data
+        .bytes()
+        .map(|c| CHARSET.iter().position(|&x| x == c).map(|v| v as u8))
+        .collect::<Option<Vec<u8>>>()
+        .ok_or(malformed("invalid bech32 character"))?;
+    let residue = polymod(hrp_expand(hrp).chain(values.iter().copied()));
+    let variant = [Bech32Variant::Bech32, Bech32Variant::Bech32m]
+        .into_iter()
+        .find(|variant| variant.constant() == residue);
+    let payload = variant.and_then(|_| convert_bits(&values[..values.len() - 6], 5, 8, false));
+    values.zeroize();
+    let variant = variant.ok_or(malformed("bech32 checksum mismatch"))?;
+    let payload = payload.ok_or(malformed("invalid bech32 padding"))?;
+    Ok((hrp.into(), payload, variant))
+}
+
+impl Share {
+    /// Bech32 string of the share's DER encoding under [`SHARE_HRP`].
+    pub fn to_bech32(&self, variant: Bech32Variant) -> String {
+        let mut der = self.to_der();
+        let encoded = encode(SHARE_HRP, &der, variant);
+        der.zeroize();
+        encoded
+    }
+
+    /// Parses a share written by [`Share::to_bech32`] in either variant,
+    /// accepting upper or lower case.
+    pub fn from_bech32(text: &str) -> Result<Self, ShamirError> {
+        let (hrp, mut der, _) = decode(text)?;
+        if hrp != SHARE_HRP {
+            der.zeroize();
+            return Err(ShamirError::MalformedShare("not a shamir bech32 string"));
+        }
+        let share = Share::from_der(&der);
+        der.zeroize();
+        share
+    }
+}
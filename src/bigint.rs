@@ -0,0 +1,39 @@
+//! Big integer operations that can run on a faster backend than
+//! `num-bigint`.
+//!
+//! Everything else in the crate uses `BigUint` arithmetic directly. By
+//! default, [`modpow`] with an odd modulus runs on the crate's own
+//! fixed-width Montgomery limbs, as fast as `BigUint::modpow` or a little
+//! faster in testing, and falls back to `BigUint::modpow` for even
+//! moduli.
+//!
+//! The `gmp` feature swaps in GMP for [`modpow`] only: it converts the
+//! operands to GMP integers, calls `mpz_powm` and converts back. The copies
+//! take linear time against the exponentiation's cubic time, and GMP comes
+//! out ahead from 64-bit moduli up. It is not a GMP backend for the crate:
+//! public types stay `BigUint`, and multiplication, division and modular
+//! inverses, such as those in Lagrange interpolation, still run on
+//! `num-bigint`.
+
+#[cfg(feature = "gmp")]
+mod gmp;
+
+use num_bigint::BigUint;
+
+#[cfg(not(feature = "gmp"))]
+use crate::montgomery::MontgomeryModulus;
+
+/// `base^exponent mod modulus`, panicking on a zero modulus like
+/// [`BigUint::modpow`].
+pub(crate) fn modpow(base: &BigUint, exponent: &BigUint, modulus: &BigUint) -> BigUint {
+    #[cfg(feature = "gmp")]
+    return gmp::modpow(base, exponent, modulus);
+    #[cfg(not(feature = "gmp"))]
+    match MontgomeryModulus::new(modulus) {
+        Some(field) => {
+            let base = field.to_montgomery(&(base % modulus));
+            field.to_plain(&field.pow_vartime(&base, exponent))
+        }
+        None => base.modpow(exponent, modulus),
+    }
+}
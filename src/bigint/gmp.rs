@@ -0,0 +1,93 @@
+//! [`modpow`] on GMP's `mpz_powm`, through the `gmp-mpfr-sys` bindings to
+//! the system `libgmp`.
+//!
+//! Values cross over as 32-bit words with `mpz_import` and `mpz_export`,
+//! the same digits `BigUint` stores, so the conversion does not depend on
+//! GMP's limb size.
+
+use alloc::vec;
+use core::mem::MaybeUninit;
+
+use gmp_mpfr_sys::gmp;
+use num_bigint::BigUint;
+use zeroize::Zeroize;
+
+/// An initialised `mpz_t`, wiped and cleared on drop.
+struct Mpz(gmp::mpz_t);
+
+impl Mpz {
+    fn new() -> Self {
+        let mut x = MaybeUninit::uninit();
+        // SAFETY: mpz_init fully initialises the struct it is given.
+        unsafe {
+            gmp::mpz_init(x.as_mut_ptr());
+            Mpz(x.assume_init())
+        }
+    }
+
+    fn from_biguint(value: &BigUint) -> Self {
+        let mut x = Mpz::new();
+        let mut digits = value.to_u32_digits();
+        // SAFETY: `digits` holds `digits.len()` least-significant-first
+        // native-endian u32 words, as the arguments describe.
+        unsafe {
+            gmp::mpz_import(&mut x.0, digits.len(), -1, 4, 0, 0, digits.as_ptr().cast());
+        }
+        digits.zeroize();
+        x
+    }
+
+    fn to_biguint(&self) -> BigUint {
+        // SAFETY: the struct is initialised.
+        let words = unsafe { gmp::mpz_sizeinbase(&self.0, 2) }.div_ceil(32);
+        let mut digits = vec![0u32; words];
+        let mut written = 0;
+        // SAFETY: `digits` has room for every 32-bit word of the value, and
+        // mpz_export writes them least significant first as asked.
+        unsafe {
+            gmp::mpz_export(
+                digits.as_mut_ptr().cast(),
+                &mut written,
+                -1,
+                4,
+                0,
+                0,
+                &self.0,
+            );
+        }
+        digits.truncate(written);
+        let value = BigUint::from_slice(&digits);
+        digits.zeroize();
+        value
+    }
+}
+
+impl Drop for Mpz {
+    fn drop(&mut self) {
+        // SAFETY: `d` points to `alloc` limbs, as documented for mpz_t, and
+        // the struct was initialised by mpz_init and is cleared exactly once.
+        unsafe {
+            let limbs = core::slice::from_raw_parts_mut(self.0.d.as_ptr(), self.0.alloc as usize);
+            limbs.zeroize();
+            gmp::mpz_clear(&mut self.0);
+        }
+    }
+}
+
+/// [`super::modpow`] on GMP.
+pub(super) fn modpow(base: &BigUint, exponent: &BigUint, modulus: &BigUint) -> BigUint {
+    // GMP raises SIGFPE on a zero modulus instead of unwinding.
+    assert!(
+        modulus.bits() > 0,
+        "attempt to calculate with zero modulus!"
+    );
+    let base = Mpz::from_biguint(base);
+    let exponent = Mpz::from_biguint(exponent);
+    let modulus = Mpz::from_biguint(modulus);
+    let mut result = Mpz::new();
+    // SAFETY: all four are initialised, the modulus is nonzero and the
+    // exponent nonnegative, and mpz_powm allows `result` to be written
+    // while the others are only read.
+    unsafe { gmp::mpz_powm(&mut result.0, &base.0, &exponent.0, &modulus.0) };
+    result.to_biguint()
+}
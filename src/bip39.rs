@@ -0,0 +1,224 @@
+//! BIP-39 word mnemonics for shares.
+//!
+//! The share's DER encoding, zero-padded to a multiple of four bytes, is
+//! treated as BIP-39 entropy: a SHA-256 checksum of one bit per 32 bits is
+//! appended and the result is written as 11-bit words from the English
+//! wordlist. Shares over large fields give long mnemonics, but every word is
+//! a common English word whose first four letters are unique, and the
+//! checksum catches most mistyped or swapped words.
+//!
+//! BIP-39 stops at 1024 bytes of entropy, where the checksum takes the whole
+//! 256-bit digest. Longer shares, such as those over the 8192-bit MODP
+//! prime, keep the full digest as their checksum and zero-pad the last word,
+//! so up to 768 words the mnemonic is plain BIP-39.
+
+mod wordlist;
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use sha2::{Digest, Sha256};
+use zeroize::Zeroize;
+
+use crate::der::{Reader, SEQUENCE};
+use crate::{ShamirError, Share};
+use wordlist::WORDS;
+
+/// The checksum is one bit per 32 bits of entropy up to the size of a
+/// SHA-256 digest.
+const MAX_CHECKSUM_BITS: usize = 256;
+
+fn checksum_bits(entropy_len: usize) -> usize {
+    (entropy_len / 4).min(MAX_CHECKSUM_BITS)
+}
+
+/// Number of words for `entropy_len` bytes of entropy.
+fn word_count(entropy_len: usize) -> usize {
+    (entropy_len * 8 + checksum_bits(entropy_len)).div_ceil(11)
+}
+
+/// Inverse of [`word_count`], for entropy lengths that are a multiple of
+/// four bytes.
+fn entropy_len(words: usize) -> Option<usize> {
+    if words == 0 {
+        return None;
+    }
+    let len = if words <= word_count(4 * MAX_CHECKSUM_BITS) {
+        words / 3 * 4
+    } else {
+        (words * 11 - MAX_CHECKSUM_BITS) / 32 * 4
+    };
+    (word_count(len) == words).then_some(len)
+}
+
+/// Writes `entropy`, whose length must be a multiple of four bytes, as a
+/// BIP-39 mnemonic.
+fn encode(entropy: &[u8]) -> String {
+    let checksum = Sha256::digest(entropy);
+    let checksum_bits = checksum_bits(entropy.len());
+    let padding_bits = word_count(entropy.len()) * 11 - entropy.len() * 8 - checksum_bits;
+    let mut words = Vec::with_capacity(word_count(entropy.len()));
+    let mut acc = 0u32;
+    let mut pending = 0usize;
+    let mut push = |bits: u32, count: usize| {
+        acc = (acc << count) | bits;
+        pending += count;
+        while pending >= 11 {
+            pending -= 11;
+            words.push(WORDS[((acc >> pending) & 0x7ff) as usize]);
+        }
+        acc &= (1 << pending) - 1;
+    };
+    for &byte in entropy {
+        push(byte as u32, 8);
+    }
+    for i in 0..checksum_bits {
+        push(((checksum[i / 8] >> (7 - i % 8)) & 1) as u32, 1);
+    }
+    if padding_bits > 0 {
+        push(0, padding_bits);
+    }
+    let mnemonic = words.join(" ");
+    acc.zeroize();
+    mnemonic
+}
+
+/// Parses a mnemonic written by [`encode`], verifying its checksum, and
+/// returns the entropy. Up to 768 words that is any BIP-39 mnemonic whose
+/// length is a multiple of three words.
+fn decode(mnemonic: &str) -> Result<Vec<u8>, ShamirError> {
+    let malformed = ShamirError::MalformedShare;
+    let mut indices = mnemonic
+        .split_whitespace()
+        .map(|word| {
+            WORDS
+                .binary_search(&word.to_ascii_lowercase().as_str())
+                .map(|index| index as u32)
+                .map_err(|_| malformed("word is not in the BIP-39 wordlist"))
+        })
+        .collect::<Result<Vec<u32>, _>>()?;
+    let Some(entropy_len) = entropy_len(indices.len()) else {
+        indices.zeroize();
+        return Err(malformed("mnemonic has an invalid number of words"));
+    };
+    let mut bits = Vec::with_capacity(indices.len() * 11);
+    for &index in &indices {
+        bits.extend((0..11).rev().map(|i| ((index >> i) & 1) as u8));
+    }
+    indices.zeroize();
+    let (entropy_bits, rest) = bits.split_at(entropy_len * 8);
+    let (checksum_bits, padding_bits) = rest.split_at(checksum_bits(entropy_len));
+    let entropy: Vec<u8> = entropy_bits
+        .chunks(8)
+        .map(|byte| byte.iter().fold(0u8, |acc, &bit| (acc << 1) | bit))
+        .collect();
+    let checksum = Sha256::digest(&entropy);
+    let valid = checksum_bits
+        .iter()
+        .enumerate()
+        .all(|(i, &bit)| (checksum[i / 8] >> (7 - i % 8)) & 1 == bit)
+        && padding_bits.iter().all(|&bit| bit == 0);
+    bits.zeroize();
+    if !valid {
+        let mut entropy = entropy;
+        entropy.zeroize();
+        return Err(malformed("mnemonic checksum mismatch"));
+    }
+    Ok(entropy)
+}
+
+impl Share {
+    /// BIP-39 mnemonic of the share's DER encoding.
+    pub fn to_mnemonic(&self) -> String {
+        let mut der = self.to_der();
+        der.resize(der.len().next_multiple_of(4), 0);
+        let mnemonic = encode(&der);
+        der.zeroize();
+        mnemonic
+    }
+
+    /// Parses a share written by [`Share::to_mnemonic`]. Words may be
+    /// separated by any whitespace and are matched case-insensitively.
+    pub fn from_mnemonic(mnemonic: &str) -> Result<Self, ShamirError> {
+        let mut entropy = decode(mnemonic)?;
+        let mut reader = Reader::new(&entropy);
+        let padding = reader.read(SEQUENCE).map(|_| reader.remaining());
+        let share = match padding {
+            Ok(padding) if padding.len() < 4 && padding.iter().all(|&byte| byte == 0) => {
+                Share::from_der(&entropy[..entropy.len() - padding.len()])
+            }
+            Ok(_) => Err(ShamirError::MalformedShare("invalid mnemonic padding")),
+            Err(err) => Err(err),
+        };
+        entropy.zeroize();
+        share
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+
+    use num_bigint::BigUint;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha20Rng;
+
+    use super::*;
+    use crate::{shamir_split_shares, StandardPrime};
+
+    #[test]
+    fn reference_vectors() {
+        let vectors = [
+            (
+                vec![0x00; 16],
+                "abandon abandon abandon abandon abandon abandon abandon abandon abandon \
+                 abandon abandon about",
+            ),
+            (
+                vec![0x7f; 16],
+                "legal winner thank year wave sausage worth useful legal winner thank yellow",
+            ),
+            (
+                vec![0xff; 32],
+                "zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo \
+                 zoo zoo zoo zoo zoo vote",
+            ),
+        ];
+        for (entropy, mnemonic) in vectors {
+            assert_eq!(encode(&entropy), mnemonic);
+            assert_eq!(decode(mnemonic).unwrap(), entropy);
+        }
+    }
+
+    #[test]
+    fn round_trips_past_the_bip39_limit() {
+        for len in [1020, 1024, 1028, 1032, 2048] {
+            let entropy: Vec<u8> = (0..len).map(|i| (i * 7 + 3) as u8).collect();
+            let mnemonic = encode(&entropy);
+            assert_eq!(mnemonic.split(' ').count(), word_count(len));
+            assert_eq!(decode(&mnemonic).unwrap(), entropy);
+        }
+        assert_eq!(word_count(1024), 768);
+        assert_eq!(entropy_len(769), None);
+    }
+
+    #[test]
+    fn rejects_a_changed_word() {
+        let entropy: Vec<u8> = (0..1028).map(|i| i as u8).collect();
+        let mnemonic = encode(&entropy);
+        let (first, rest) = mnemonic.split_once(' ').unwrap();
+        let other = if first == "zoo" { "abandon" } else { "zoo" };
+        assert!(decode(&alloc::format!("{other} {rest}")).is_err());
+    }
+
+    #[test]
+    fn round_trips_a_modp_8192_share() {
+        let mut rng = ChaCha20Rng::seed_from_u64(0);
+        let modulus = StandardPrime::Modp8192.modulus();
+        let shares = shamir_split_shares(&BigUint::from(42u32), 2, 2, &modulus, &mut rng).unwrap();
+        for share in shares {
+            assert!(share.to_der().len() > 1024);
+            assert_eq!(Share::from_mnemonic(&share.to_mnemonic()).unwrap(), share);
+        }
+    }
+}
@@ -0,0 +1,222 @@
+//! Threshold BLS signatures over BLS12-381.
+//!
+//! Secret key shares are ordinary [`Share`]s modulo the BLS12-381 scalar
+//! order `r`. Each holder signs with its share to produce a partial
+//! signature, and any `t` partials combine by Lagrange interpolation in the
+//! exponent into the signature of the shared key, which is never
+//! reconstructed. This is the basic scheme of the IETF BLS draft in its
+//! minimal-public-key variant: public keys live in G1 (48 bytes),
+//! signatures in G2 (96 bytes), and messages are hashed with
+//! `BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_NUL_`.
+
+use alloc::vec::Vec;
+
+use bls12_381::hash_to_curve::{ExpandMsgXmd, HashToCurve};
+use bls12_381::{pairing, G1Affine, G1Projective, G2Affine, G2Projective, Scalar};
+use num_bigint::BigUint;
+use num_traits::Zero;
+use rand::{CryptoRng, RngCore};
+use zeroize::Zeroize;
+
+use crate::shamir::{check_compatible, lagrange_coefficient};
+use crate::{shamir_split_shares, zeroize_biguint, ShamirError, Share};
+
+const DST: &[u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_NUL_";
+
+const ORDER: &str = "73eda753299d7d483339d80809a1d80553bda402fffe5bfeffffffff00000001";
+
+/// A holder's signature on a message under its key share.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlsPartialSignature {
+    x: BigUint,
+    signature: [u8; 96],
+}
+
+impl BlsPartialSignature {
+    pub fn new(x: BigUint, signature: [u8; 96]) -> Self {
+        BlsPartialSignature { x, signature }
+    }
+
+    pub fn x(&self) -> &BigUint {
+        &self.x
+    }
+
+    /// The compressed G2 signature under the holder's public key share.
+    pub fn signature(&self) -> &[u8; 96] {
+        &self.signature
+    }
+}
+
+/// A holder's public key share, in compressed G1 form.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlsPublicShare {
+    x: BigUint,
+    public_key: [u8; 48],
+}
+
+impl BlsPublicShare {
+    pub fn new(x: BigUint, public_key: [u8; 48]) -> Self {
+        BlsPublicShare { x, public_key }
+    }
+
+    pub fn x(&self) -> &BigUint {
+        &self.x
+    }
+
+    pub fn public_key(&self) -> &[u8; 48] {
+        &self.public_key
+    }
+}
+
+/// The BLS12-381 scalar order `r`, the modulus of every key share.
+pub fn bls_modulus() -> BigUint {
+    BigUint::parse_bytes(ORDER.as_bytes(), 16).expect("valid hex constant")
+}
+
+/// Splits a big-endian BLS secret key, which must lie in `1..r`.
+pub fn bls_split_secret_key<R: RngCore + CryptoRng + ?Sized>(
+    secret_key: &[u8; 32],
+    threshold: usize,
+    shares: usize,
+    rng: &mut R,
+) -> Result<Vec<Share>, ShamirError> {
+    let modulus = bls_modulus();
+    let mut value = BigUint::from_bytes_be(secret_key);
+    if value.is_zero() || value >= modulus {
+        zeroize_biguint(&mut value);
+        return Err(ShamirError::InvalidKey("BLS secret keys must lie in 1..r"));
+    }
+    let result = shamir_split_shares(&value, threshold, shares, &modulus, rng);
+    zeroize_biguint(&mut value);
+    result
+}
+
+/// The compressed public key of a big-endian secret key.
+pub fn bls_public_key(secret_key: &[u8; 32]) -> Result<[u8; 48], ShamirError> {
+    let mut value = BigUint::from_bytes_be(secret_key);
+    let scalar = to_scalar(&value);
+    zeroize_biguint(&mut value);
+    let mut scalar = scalar.ok_or(ShamirError::InvalidKey("BLS secret keys must lie in 1..r"))?;
+    let public_key = G1Affine::from(G1Projective::generator() * scalar).to_compressed();
+    scalar.zeroize();
+    Ok(public_key)
+}
+
+/// The public key share matching a secret key share.
+pub fn bls_public_share(share: &Share) -> Result<BlsPublicShare, ShamirError> {
+    let mut scalar = share_scalar(share)?;
+    let public_key = G1Affine::from(G1Projective::generator() * scalar).to_compressed();
+    scalar.zeroize();
+    Ok(BlsPublicShare::new(share.x().clone(), public_key))
+}
+
+/// Signs `message` with a key share.
+pub fn bls_partial_sign(share: &Share, message: &[u8]) -> Result<BlsPartialSignature, ShamirError> {
+    let mut scalar = share_scalar(share)?;
+    let signature = G2Affine::from(hash_message(message) * scalar).to_compressed();
+    scalar.zeroize();
+    Ok(BlsPartialSignature::new(share.x().clone(), signature))
+}
+
+/// Combines at least `threshold` partial signatures, all on the same
+/// message, into the signature of the shared key.
+pub fn bls_combine_signatures(
+    partials: &[BlsPartialSignature],
+    threshold: usize,
+) -> Result<[u8; 96], ShamirError> {
+    let points = partials
+        .iter()
+        .map(|partial| {
+            Option::from(G2Affine::from_compressed(&partial.signature))
+                .map(|point: G2Affine| (&partial.x, G2Projective::from(point)))
+                .ok_or(ShamirError::MalformedShare("invalid BLS signature"))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let combined = interpolate(&points, threshold, G2Projective::identity())?;
+    Ok(G2Affine::from(combined).to_compressed())
+}
+
+/// Combines at least `threshold` public key shares into the shared public
+/// key.
+pub fn bls_combine_public_keys(
+    shares: &[BlsPublicShare],
+    threshold: usize,
+) -> Result<[u8; 48], ShamirError> {
+    let points = shares
+        .iter()
+        .map(|share| {
+            Option::from(G1Affine::from_compressed(&share.public_key))
+                .map(|point: G1Affine| (&share.x, G1Projective::from(point)))
+                .ok_or(ShamirError::MalformedShare("invalid BLS public key"))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let combined = interpolate(&points, threshold, G1Projective::identity())?;
+    Ok(G1Affine::from(combined).to_compressed())
+}
+
+/// Checks a signature, or a partial signature against its public key share.
+pub fn bls_verify(public_key: &[u8; 48], message: &[u8], signature: &[u8; 96]) -> bool {
+    let public_key: Option<G1Affine> = G1Affine::from_compressed(public_key).into();
+    let signature: Option<G2Affine> = G2Affine::from_compressed(signature).into();
+    match (public_key, signature) {
+        (Some(public_key), Some(signature)) if !bool::from(public_key.is_identity()) => {
+            let hashed = G2Affine::from(hash_message(message));
+            pairing(&G1Affine::generator(), &signature) == pairing(&public_key, &hashed)
+        }
+        _ => false,
+    }
+}
+
+fn hash_message(message: &[u8]) -> G2Projective {
+    <G2Projective as HashToCurve<ExpandMsgXmd<sha2::Sha256>>>::hash_to_curve(message, DST)
+}
+
+fn share_scalar(share: &Share) -> Result<Scalar, ShamirError> {
+    check_compatible(core::slice::from_ref(share), &bls_modulus())?;
+    to_scalar(share.y()).ok_or(ShamirError::MalformedShare("share value exceeds r"))
+}
+
+/// Converts a value below `r` to a scalar; zero and larger values fail.
+fn to_scalar(value: &BigUint) -> Option<Scalar> {
+    if value.is_zero() {
+        return None;
+    }
+    let mut bytes = value.to_bytes_le();
+    if bytes.len() > 32 {
+        bytes.zeroize();
+        return None;
+    }
+    let mut le = [0u8; 32];
+    le[..bytes.len()].copy_from_slice(&bytes);
+    bytes.zeroize();
+    let scalar = Scalar::from_bytes(&le);
+    le.zeroize();
+    scalar.into()
+}
+
+fn interpolate<P>(points: &[(&BigUint, P)], threshold: usize, identity: P) -> Result<P, ShamirError>
+where
+    P: Copy + core::ops::Add<Output = P> + core::ops::Mul<Scalar, Output = P>,
+{
+    if threshold < 1 || points.len() < threshold {
+        return Err(ShamirError::NotEnoughShares {
+            required: threshold,
+            provided: points.len(),
+        });
+    }
+    let used = &points[..threshold];
+    let xs: Vec<BigUint> = used.iter().map(|(x, _)| (*x).clone()).collect();
+    if xs.iter().any(Zero::is_zero) {
+        return Err(ShamirError::MalformedShare("x must be nonzero"));
+    }
+    let modulus = bls_modulus();
+    let mut acc = identity;
+    for (i, (_, point)) in used.iter().enumerate() {
+        let coefficient = lagrange_coefficient(&xs, i, &modulus)?;
+        let coefficient = to_scalar(&coefficient).ok_or(ShamirError::MalformedShare(
+            "x is a multiple of the group order",
+        ))?;
+        acc = acc + *point * coefficient;
+    }
+    Ok(acc)
+}
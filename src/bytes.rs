@@ -0,0 +1,79 @@
+//! Sharing arbitrary byte strings over the standard prime fields.
+//!
+//! The secret is encoded as the integer `0x01 || secret`, so leading zero
+//! bytes survive the round trip, and shared in the smallest
+//! [`StandardPrime`] field that holds it. The field is recognised again
+//! from the share fingerprints, so reconstruction needs nothing but the
+//! shares.
+
+use alloc::vec::Vec;
+
+use num_bigint::BigUint;
+use rand::{CryptoRng, RngCore};
+use zeroize::Zeroize;
+
+use crate::primes::standard::StandardPrime;
+use crate::{
+    shamir_reconstruct, shamir_split_shares, zeroize_biguint, Fingerprint, ShamirError, Share,
+};
+
+/// Candidate fields, smallest first.
+const BYTE_FIELDS: [StandardPrime; 8] = [
+    StandardPrime::Curve25519,
+    StandardPrime::Mersenne521,
+    StandardPrime::Modp1536,
+    StandardPrime::Modp2048,
+    StandardPrime::Modp3072,
+    StandardPrime::Modp4096,
+    StandardPrime::Modp6144,
+    StandardPrime::Modp8192,
+];
+
+/// The field [`split_bytes`] uses for a secret of `len` bytes.
+pub fn bytes_modulus(len: usize) -> Result<BigUint, ShamirError> {
+    BYTE_FIELDS
+        .iter()
+        .find(|field| 8 * len + 2 <= field.bits())
+        .map(|field| field.modulus())
+        .ok_or(ShamirError::SecretTooLarge)
+}
+
+/// Splits a byte string of up to 1023 bytes.
+pub fn split_bytes<R: RngCore + CryptoRng + ?Sized>(
+    secret: &[u8],
+    threshold: usize,
+    shares: usize,
+    rng: &mut R,
+) -> Result<Vec<Share>, ShamirError> {
+    let modulus = bytes_modulus(secret.len())?;
+    let mut encoded = Vec::with_capacity(secret.len() + 1);
+    encoded.push(1u8);
+    encoded.extend_from_slice(secret);
+    let mut value = BigUint::from_bytes_be(&encoded);
+    encoded.zeroize();
+    let result = shamir_split_shares(&value, threshold, shares, &modulus, rng);
+    zeroize_biguint(&mut value);
+    result
+}
+
+/// Recovers the exact byte string from shares made by [`split_bytes`].
+pub fn reconstruct_bytes(shares: &[Share]) -> Result<Vec<u8>, ShamirError> {
+    let first = shares.first().ok_or(ShamirError::NotEnoughShares {
+        required: 1,
+        provided: 0,
+    })?;
+    let modulus = BYTE_FIELDS
+        .iter()
+        .map(|field| field.modulus())
+        .find(|modulus| Fingerprint::of(modulus) == first.fingerprint())
+        .ok_or(ShamirError::ModulusMismatch)?;
+    let mut value = shamir_reconstruct(shares, &modulus)?;
+    let mut encoded = value.to_bytes_be();
+    zeroize_biguint(&mut value);
+    if encoded.first() != Some(&1) {
+        encoded.zeroize();
+        return Err(ShamirError::MalformedShare("missing byte-string marker"));
+    }
+    encoded.remove(0);
+    Ok(encoded)
+}
@@ -0,0 +1,429 @@
+//! Deterministic CBOR (RFC 8949 §4.2.1) for shares, commitments and scheme
+//! parameters.
+//!
+//! Every value has exactly one encoding: integers and lengths use their
+//! shortest head, big integers that fit in 64 bits are plain unsigned
+//! integers and larger ones are tag-2 bignums without leading zeros, and
+//! maps use small integer keys in ascending order. The decoder rejects
+//! anything else, so `from_cbor(bytes)?.to_cbor() == bytes` always holds.
+//!
+//! - [`Share`]: `{1: x, 2: threshold, 3: fingerprint, 4: y, 5: blinding,
+//!   6: digest, 7: set id, 8: mac, 9: signature}`, the last five only when
+//!   present.
+//! - [`ShamirScheme`]: `{1: threshold, 2: shares, 3: modulus}`.
+//! - [`PedersenParams`]: `{1: p, 2: q, 3: g, 4: h}`.
+//! - [`PedersenCommitments`]: an array of the commitments.
+
+use alloc::vec::Vec;
+
+use num_bigint::BigUint;
+use zeroize::Zeroize;
+
+use crate::{
+    DealerSignature, Fingerprint, PedersenCommitments, PedersenParams, SecretDigest, ShamirError,
+    ShamirScheme, Share, ShareMac, ShareSetId,
+};
+
+const UNSIGNED: u8 = 0;
+const BYTES: u8 = 2;
+const ARRAY: u8 = 4;
+const MAP: u8 = 5;
+const TAG: u8 = 6;
+const POSITIVE_BIGNUM: u64 = 2;
+
+fn malformed(what: &'static str) -> ShamirError {
+    ShamirError::MalformedShare(what)
+}
+
+fn write_head(out: &mut Vec<u8>, major: u8, value: u64) {
+    let major = major << 5;
+    match value {
+        0..=23 => out.push(major | value as u8),
+        24..=0xff => out.extend_from_slice(&[major | 24, value as u8]),
+        0x100..=0xffff => {
+            out.push(major | 25);
+            out.extend_from_slice(&(value as u16).to_be_bytes());
+        }
+        0x1_0000..=0xffff_ffff => {
+            out.push(major | 26);
+            out.extend_from_slice(&(value as u32).to_be_bytes());
+        }
+        _ => {
+            out.push(major | 27);
+            out.extend_from_slice(&value.to_be_bytes());
+        }
+    }
+}
+
+fn write_bytes(out: &mut Vec<u8>, bytes: &[u8]) {
+    write_head(out, BYTES, bytes.len() as u64);
+    out.extend_from_slice(bytes);
+}
+
+fn write_biguint(out: &mut Vec<u8>, value: &BigUint) {
+    match u64::try_from(value) {
+        Ok(small) => write_head(out, UNSIGNED, small),
+        Err(_) => {
+            let mut bytes = value.to_bytes_be();
+            write_head(out, TAG, POSITIVE_BIGNUM);
+            write_bytes(out, &bytes);
+            bytes.zeroize();
+        }
+    }
+}
+
+/// Cursor over deterministic CBOR input.
+struct Reader<'a> {
+    input: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], ShamirError> {
+        if self.input.len() < len {
+            return Err(malformed("truncated CBOR"));
+        }
+        let (head, rest) = self.input.split_at(len);
+        self.input = rest;
+        Ok(head)
+    }
+
+    fn peek_major(&self) -> Option<u8> {
+        self.input.first().map(|byte| byte >> 5)
+    }
+
+    /// Reads a head of the given major type, rejecting non-shortest forms
+    /// and indefinite lengths.
+    fn head(&mut self, major: u8) -> Result<u64, ShamirError> {
+        let initial = self.take(1)?[0];
+        if initial >> 5 != major {
+            return Err(malformed("unexpected CBOR type"));
+        }
+        let (value, minimum) = match initial & 0x1f {
+            info @ 0..=23 => (info as u64, 0),
+            24 => (self.take(1)?[0] as u64, 24),
+            25 => (
+                u16::from_be_bytes(self.take(2)?.try_into().expect("2 bytes")) as u64,
+                0x100,
+            ),
+            26 => (
+                u32::from_be_bytes(self.take(4)?.try_into().expect("4 bytes")) as u64,
+                0x1_0000,
+            ),
+            27 => (
+                u64::from_be_bytes(self.take(8)?.try_into().expect("8 bytes")),
+                0x1_0000_0000,
+            ),
+            _ => return Err(malformed("unsupported CBOR length")),
+        };
+        if value < minimum {
+            return Err(malformed("non-canonical CBOR integer"));
+        }
+        Ok(value)
+    }
+
+    fn uint(&mut self) -> Result<u64, ShamirError> {
+        self.head(UNSIGNED)
+    }
+
+    fn usize(&mut self) -> Result<usize, ShamirError> {
+        usize::try_from(self.uint()?).map_err(|_| malformed("CBOR integer out of range"))
+    }
+
+    fn bytes(&mut self) -> Result<&'a [u8], ShamirError> {
+        let len = self.head(BYTES)?;
+        let len = usize::try_from(len).map_err(|_| malformed("truncated CBOR"))?;
+        self.take(len)
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], ShamirError> {
+        self.bytes()?
+            .try_into()
+            .map_err(|_| malformed("wrong CBOR byte string length"))
+    }
+
+    fn biguint(&mut self) -> Result<BigUint, ShamirError> {
+        if self.peek_major() == Some(UNSIGNED) {
+            return Ok(BigUint::from(self.uint()?));
+        }
+        if self.head(TAG)? != POSITIVE_BIGNUM {
+            return Err(malformed("unexpected CBOR tag"));
+        }
+        let bytes = self.bytes()?;
+        if bytes.len() <= 8 || bytes[0] == 0 {
+            return Err(malformed("non-canonical CBOR bignum"));
+        }
+        Ok(BigUint::from_bytes_be(bytes))
+    }
+
+    fn finish(&self) -> Result<(), ShamirError> {
+        if self.input.is_empty() {
+            Ok(())
+        } else {
+            Err(malformed("trailing CBOR data"))
+        }
+    }
+}
+
+/// Reader over the entries of a map with ascending integer keys.
+struct MapReader<'r, 'a> {
+    reader: &'r mut Reader<'a>,
+    remaining: u64,
+    next_key: Option<u64>,
+    last_key: Option<u64>,
+}
+
+impl<'r, 'a> MapReader<'r, 'a> {
+    fn new(reader: &'r mut Reader<'a>) -> Result<Self, ShamirError> {
+        let remaining = reader.head(MAP)?;
+        let mut map = MapReader {
+            reader,
+            remaining,
+            next_key: None,
+            last_key: None,
+        };
+        map.advance()?;
+        Ok(map)
+    }
+
+    fn advance(&mut self) -> Result<(), ShamirError> {
+        self.next_key = None;
+        if self.remaining > 0 {
+            self.remaining -= 1;
+            let key = self.reader.uint()?;
+            if self.last_key.is_some_and(|last| key <= last) {
+                return Err(malformed("CBOR map keys out of order"));
+            }
+            self.last_key = Some(key);
+            self.next_key = Some(key);
+        }
+        Ok(())
+    }
+
+    /// Reads the value of `key` if the map has it.
+    fn value<T>(
+        &mut self,
+        key: u64,
+        read: impl FnOnce(&mut Reader<'a>) -> Result<T, ShamirError>,
+    ) -> Result<Option<T>, ShamirError> {
+        if self.next_key != Some(key) {
+            return Ok(None);
+        }
+        let value = read(self.reader)?;
+        self.advance()?;
+        Ok(Some(value))
+    }
+
+    fn required<T>(
+        &mut self,
+        key: u64,
+        read: impl FnOnce(&mut Reader<'a>) -> Result<T, ShamirError>,
+    ) -> Result<T, ShamirError> {
+        self.value(key, read)?
+            .ok_or(malformed("missing CBOR map entry"))
+    }
+
+    fn finish(self) -> Result<(), ShamirError> {
+        match self.next_key {
+            None => Ok(()),
+            Some(_) => Err(malformed("unknown CBOR map entry")),
+        }
+    }
+}
+
+fn decode<T>(
+    bytes: &[u8],
+    read: impl FnOnce(&mut Reader<'_>) -> Result<T, ShamirError>,
+) -> Result<T, ShamirError> {
+    let mut reader = Reader { input: bytes };
+    let value = read(&mut reader)?;
+    reader.finish()?;
+    Ok(value)
+}
+
+/// Encodes `bytes` as a bare CBOR byte string.
+#[cfg(feature = "sskr")]
+pub(crate) fn bytes_to_cbor(bytes: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(bytes.len() + 9);
+    write_bytes(&mut out, bytes);
+    out
+}
+
+/// Decodes a CBOR byte string, bare or wrapped in one of `tags`.
+#[cfg(feature = "sskr")]
+pub(crate) fn bytes_from_cbor<'a>(cbor: &'a [u8], tags: &[u64]) -> Result<&'a [u8], ShamirError> {
+    let mut reader = Reader { input: cbor };
+    if reader.peek_major() == Some(TAG) && !tags.contains(&reader.head(TAG)?) {
+        return Err(malformed("unexpected CBOR tag"));
+    }
+    let bytes = reader.bytes()?;
+    reader.finish()?;
+    Ok(bytes)
+}
+
+impl Share {
+    /// Encodes the share as deterministic CBOR.
+    pub fn to_cbor(&self) -> Vec<u8> {
+        let entries = 4
+            + self.blinding().is_some() as u64
+            + self.digest().is_some() as u64
+            + self.set_id().is_some() as u64
+            + self.mac().is_some() as u64
+            + self.signature().is_some() as u64;
+        let mut out = Vec::new();
+        write_head(&mut out, MAP, entries);
+        write_head(&mut out, UNSIGNED, 1);
+        write_biguint(&mut out, self.x());
+        write_head(&mut out, UNSIGNED, 2);
+        write_head(&mut out, UNSIGNED, self.threshold() as u64);
+        write_head(&mut out, UNSIGNED, 3);
+        write_bytes(&mut out, self.fingerprint().as_bytes());
+        write_head(&mut out, UNSIGNED, 4);
+        write_biguint(&mut out, self.y());
+        if let Some(blinding) = self.blinding() {
+            write_head(&mut out, UNSIGNED, 5);
+            write_biguint(&mut out, blinding);
+        }
+        if let Some(digest) = self.digest() {
+            let mut bytes = [0u8; 48];
+            bytes[..16].copy_from_slice(digest.salt());
+            bytes[16..].copy_from_slice(digest.hash());
+            write_head(&mut out, UNSIGNED, 6);
+            write_bytes(&mut out, &bytes);
+        }
+        if let Some(set_id) = self.set_id() {
+            write_head(&mut out, UNSIGNED, 7);
+            write_bytes(&mut out, set_id.as_bytes());
+        }
+        if let Some(mac) = self.mac() {
+            write_head(&mut out, UNSIGNED, 8);
+            write_bytes(&mut out, mac.as_bytes());
+        }
+        if let Some(signature) = self.signature() {
+            write_head(&mut out, UNSIGNED, 9);
+            write_bytes(&mut out, &signature.to_bytes());
+        }
+        out
+    }
+
+    /// Decodes a share from deterministic CBOR.
+    pub fn from_cbor(bytes: &[u8]) -> Result<Self, ShamirError> {
+        decode(bytes, |reader| {
+            let mut map = MapReader::new(reader)?;
+            let x = map.required(1, Reader::biguint)?;
+            let threshold = map.required(2, Reader::usize)?;
+            let fingerprint = Fingerprint::from_bytes(map.required(3, Reader::array)?);
+            let y = map.required(4, Reader::biguint)?;
+            let blinding = map.value(5, Reader::biguint)?;
+            let digest = map.value(6, Reader::array::<48>)?;
+            let set_id = map.value(7, Reader::array)?;
+            let mac = map.value(8, Reader::array)?;
+            let signature = map.value(9, Reader::array::<72>)?;
+            map.finish()?;
+            if x.bits() == 0 {
+                return Err(malformed("x must be nonzero"));
+            }
+            if threshold < 2 {
+                return Err(malformed("threshold must be at least 2"));
+            }
+            let mut share = Share::new(x, y, threshold, fingerprint);
+            if let Some(blinding) = blinding {
+                share = share.with_blinding(blinding);
+            }
+            if let Some(digest) = digest {
+                let salt = digest[..16].try_into().expect("16 bytes");
+                let hash = digest[16..].try_into().expect("32 bytes");
+                share = share.with_digest(SecretDigest::from_parts(salt, hash));
+            }
+            if let Some(set_id) = set_id {
+                share = share.with_set_id(ShareSetId::from_bytes(set_id));
+            }
+            if let Some(mac) = mac {
+                share = share.with_mac(ShareMac::from_bytes(mac));
+            }
+            if let Some(signature) = signature {
+                share = share.with_signature(DealerSignature::from_bytes(&signature));
+            }
+            Ok(share)
+        })
+    }
+}
+
+impl ShamirScheme {
+    /// Encodes the scheme parameters as deterministic CBOR.
+    pub fn to_cbor(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        write_head(&mut out, MAP, 3);
+        write_head(&mut out, UNSIGNED, 1);
+        write_head(&mut out, UNSIGNED, self.threshold() as u64);
+        write_head(&mut out, UNSIGNED, 2);
+        write_head(&mut out, UNSIGNED, self.shares() as u64);
+        write_head(&mut out, UNSIGNED, 3);
+        write_biguint(&mut out, self.modulus());
+        out
+    }
+
+    /// Decodes and validates scheme parameters from deterministic CBOR.
+    pub fn from_cbor(bytes: &[u8]) -> Result<Self, ShamirError> {
+        decode(bytes, |reader| {
+            let mut map = MapReader::new(reader)?;
+            let threshold = map.required(1, Reader::usize)?;
+            let shares = map.required(2, Reader::usize)?;
+            let modulus = map.required(3, Reader::biguint)?;
+            map.finish()?;
+            ShamirScheme::from_parts(threshold, shares, modulus)
+        })
+    }
+}
+
+impl PedersenParams {
+    /// Encodes the group parameters as deterministic CBOR.
+    pub fn to_cbor(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        write_head(&mut out, MAP, 4);
+        for (key, value) in [self.p(), self.q(), self.g(), self.h()].iter().enumerate() {
+            write_head(&mut out, UNSIGNED, key as u64 + 1);
+            write_biguint(&mut out, value);
+        }
+        out
+    }
+
+    /// Decodes and validates group parameters from deterministic CBOR.
+    pub fn from_cbor(bytes: &[u8]) -> Result<Self, ShamirError> {
+        decode(bytes, |reader| {
+            let mut map = MapReader::new(reader)?;
+            let p = map.required(1, Reader::biguint)?;
+            let q = map.required(2, Reader::biguint)?;
+            let g = map.required(3, Reader::biguint)?;
+            let h = map.required(4, Reader::biguint)?;
+            map.finish()?;
+            PedersenParams::new(p, q, g, h)
+        })
+    }
+}
+
+impl PedersenCommitments {
+    /// Encodes the commitments as a deterministic CBOR array.
+    pub fn to_cbor(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        write_head(&mut out, ARRAY, self.commitments().len() as u64);
+        for commitment in self.commitments() {
+            write_biguint(&mut out, commitment);
+        }
+        out
+    }
+
+    /// Decodes commitments from deterministic CBOR.
+    pub fn from_cbor(bytes: &[u8]) -> Result<Self, ShamirError> {
+        decode(bytes, |reader| {
+            let count = reader.head(ARRAY)?;
+            let mut commitments = Vec::new();
+            for _ in 0..count {
+                commitments.push(reader.biguint()?);
+            }
+            if commitments.is_empty() {
+                return Err(malformed("empty commitment list"));
+            }
+            Ok(PedersenCommitments::from_commitments(commitments))
+        })
+    }
+}
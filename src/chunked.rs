@@ -0,0 +1,191 @@
+//! Chunked sharing for secrets larger than the field.
+//!
+//! The secret is cut into blocks of `(bits(modulus) - 1) / 8` bytes, each
+//! block is shared with the same x-coordinates, and every holder receives
+//! one [`ChunkedShare`] carrying its share of each block. A salted SHA3-256
+//! digest of the whole secret travels with the shares so reassembly can
+//! detect wrong blocks instead of returning a corrupted secret.
+
+use alloc::vec::Vec;
+
+use num_bigint::BigUint;
+use rand::{CryptoRng, RngCore};
+use sha3::{Digest, Sha3_256};
+use zeroize::Zeroize;
+
+use crate::{reconstruct_batch, shamir_split_shares, zeroize_biguint, ShamirError, Share};
+
+/// One holder's share of a single block.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ShareChunk {
+    index: usize,
+    share: Share,
+}
+
+impl ShareChunk {
+    pub fn new(index: usize, share: Share) -> Self {
+        ShareChunk { index, share }
+    }
+
+    /// Position of the block in the secret, from 0.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    pub fn share(&self) -> &Share {
+        &self.share
+    }
+}
+
+/// One holder's shares of every block of a chunked secret.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ChunkedShare {
+    length: usize,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::array_hex"))]
+    salt: [u8; 16],
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::array_hex"))]
+    digest: [u8; 32],
+    chunks: Vec<ShareChunk>,
+}
+
+impl ChunkedShare {
+    pub fn new(length: usize, salt: [u8; 16], digest: [u8; 32], chunks: Vec<ShareChunk>) -> Self {
+        ChunkedShare {
+            length,
+            salt,
+            digest,
+            chunks,
+        }
+    }
+
+    /// Length of the secret in bytes.
+    pub fn length(&self) -> usize {
+        self.length
+    }
+
+    pub fn salt(&self) -> &[u8; 16] {
+        &self.salt
+    }
+
+    /// Salted SHA3-256 digest of the secret.
+    pub fn digest(&self) -> &[u8; 32] {
+        &self.digest
+    }
+
+    pub fn chunks(&self) -> &[ShareChunk] {
+        &self.chunks
+    }
+}
+
+/// Block size in bytes for `modulus`.
+fn chunk_len(modulus: &BigUint) -> Result<usize, ShamirError> {
+    match (modulus.bits() as usize - 1) / 8 {
+        0 => Err(ShamirError::InvalidModulus(
+            "chunked sharing needs a modulus of at least 9 bits",
+        )),
+        len => Ok(len),
+    }
+}
+
+fn secret_digest(salt: &[u8; 16], secret: &[u8]) -> [u8; 32] {
+    let mut hasher = Sha3_256::new();
+    hasher.update(b"prime-shamir/chunked/v1");
+    hasher.update(salt);
+    hasher.update((secret.len() as u64).to_be_bytes());
+    hasher.update(secret);
+    hasher.finalize().into()
+}
+
+/// Splits a secret of any length into `shares` chunked shares over
+/// `modulus`.
+pub fn split_chunked<R: RngCore + CryptoRng + ?Sized>(
+    secret: &[u8],
+    threshold: usize,
+    shares: usize,
+    modulus: &BigUint,
+    rng: &mut R,
+) -> Result<Vec<ChunkedShare>, ShamirError> {
+    let len = chunk_len(modulus)?;
+    let mut salt = [0u8; 16];
+    rng.fill_bytes(&mut salt);
+    let digest = secret_digest(&salt, secret);
+    let mut result: Vec<ChunkedShare> = (0..shares)
+        .map(|_| ChunkedShare::new(secret.len(), salt, digest, Vec::new()))
+        .collect();
+    for (index, block) in secret.chunks(len).enumerate() {
+        let mut value = BigUint::from_bytes_be(block);
+        let split = shamir_split_shares(&value, threshold, shares, modulus, rng);
+        zeroize_biguint(&mut value);
+        for (holder, share) in result.iter_mut().zip(split?) {
+            holder.chunks.push(ShareChunk::new(index, share));
+        }
+    }
+    Ok(result)
+}
+
+/// Reassembles a chunked secret from at least `threshold` holders' shares
+/// and checks it against the recorded digest.
+pub fn reconstruct_chunked(
+    shares: &[ChunkedShare],
+    modulus: &BigUint,
+) -> Result<Vec<u8>, ShamirError> {
+    let first = shares.first().ok_or(ShamirError::NotEnoughShares {
+        required: 1,
+        provided: 0,
+    })?;
+    let len = chunk_len(modulus)?;
+    let blocks = first.length.div_ceil(len);
+    for share in shares {
+        if share.length != first.length || share.salt != first.salt || share.digest != first.digest
+        {
+            return Err(ShamirError::MalformedShare(
+                "shares belong to different chunked secrets",
+            ));
+        }
+        if share.chunks.len() != blocks {
+            return Err(ShamirError::MalformedShare("unexpected number of chunks"));
+        }
+    }
+    let sets = (0..blocks)
+        .map(|index| {
+            shares
+                .iter()
+                .map(|share| {
+                    share
+                        .chunks
+                        .iter()
+                        .find(|chunk| chunk.index == index)
+                        .map(|chunk| chunk.share.clone())
+                        .ok_or(ShamirError::MalformedShare("missing chunk"))
+                })
+                .collect::<Result<Vec<Share>, _>>()
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    // Every block is shared at the same x-coordinates, so one set of
+    // Lagrange coefficients serves them all.
+    let mut values = reconstruct_batch(&sets, modulus)?;
+    let mut secret = Vec::with_capacity(first.length);
+    for index in 0..blocks {
+        let block_len = len.min(first.length - index * len);
+        let mut value = core::mem::take(&mut values[index]);
+        let mut bytes = value.to_bytes_be();
+        zeroize_biguint(&mut value);
+        let significant = bytes.len() - bytes.iter().take_while(|&&byte| byte == 0).count();
+        if significant > block_len {
+            bytes.zeroize();
+            secret.zeroize();
+            values.iter_mut().for_each(zeroize_biguint);
+            return Err(ShamirError::IntegrityFailure);
+        }
+        secret.resize(secret.len() + block_len - significant, 0);
+        secret.extend_from_slice(&bytes[bytes.len() - significant..]);
+        bytes.zeroize();
+    }
+    if secret_digest(&first.salt, &secret) != first.digest {
+        secret.zeroize();
+        return Err(ShamirError::IntegrityFailure);
+    }
+    Ok(secret)
+}
@@ -0,0 +1,301 @@
+//! Argument parsing and subcommand dispatch.
+
+mod combine;
+mod config;
+mod files;
+mod inspect;
+mod split;
+mod verify;
+mod wizard;
+
+use std::fmt;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use clap::{ArgAction, Parser, Subcommand, ValueEnum};
+use num_bigint::BigUint;
+use prime_pmpt::ShamirError;
+use serde::Serialize;
+use tracing_subscriber::filter::LevelFilter;
+
+/// Split secrets into Shamir shares and combine shares back into secrets.
+#[derive(Debug, Parser)]
+#[command(name = "prime-shamir", version, about)]
+pub struct Cli {
+    /// Output format for results and errors.
+    #[arg(long, global = true, value_enum, default_value_t = OutputFormat::Text)]
+    pub format: OutputFormat,
+    /// Configuration file with defaults for `split`, instead of
+    /// `~/.config/prime-shamir/config.toml`.
+    #[arg(long, global = true, value_name = "FILE")]
+    config: Option<PathBuf>,
+    /// Log more about what happens: `-v` for each file written, `-vv` for
+    /// library operations, `-vvv` for every prime candidate.
+    #[arg(short, long, global = true, action = ArgAction::Count, conflicts_with = "quiet")]
+    verbose: u8,
+    /// Log less: `-q` for errors only, `-qq` for nothing.
+    #[arg(short, long, global = true, action = ArgAction::Count)]
+    quiet: u8,
+    #[command(subcommand)]
+    command: Command,
+}
+
+impl Cli {
+    /// The log level chosen with `-v` or `-q`, or `None` to leave it to
+    /// `RUST_LOG`.
+    pub fn log_level(&self) -> Option<LevelFilter> {
+        match (self.verbose, self.quiet) {
+            (0, 0) => None,
+            (0, 1) => Some(LevelFilter::ERROR),
+            (0, _) => Some(LevelFilter::OFF),
+            (1, _) => Some(LevelFilter::INFO),
+            (2, _) => Some(LevelFilter::DEBUG),
+            _ => Some(LevelFilter::TRACE),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// Lines for people to read.
+    Text,
+    /// One JSON document on standard output, and errors as JSON on standard
+    /// error.
+    Json,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Split a secret file into share files.
+    Split(split::SplitArgs),
+    /// Reconstruct a secret from share files.
+    Combine(combine::CombineArgs),
+    /// Check share files and their consistency without reconstructing.
+    Verify(verify::VerifyArgs),
+    /// Describe share files without revealing their values.
+    Inspect(inspect::InspectArgs),
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum CliError {
+    #[error(transparent)]
+    Shamir(#[from] ShamirError),
+    #[error("{}: {source}", path.display())]
+    Io { path: PathBuf, source: io::Error },
+    #[error("{}: {source}", path.display())]
+    Decode { path: PathBuf, source: ShamirError },
+    #[error("{}: {message}", path.display())]
+    Config { path: PathBuf, message: String },
+    #[error("{} already exists; pass --force to replace it", .0.display())]
+    Exists(PathBuf),
+    #[error("{0}")]
+    Usage(String),
+    #[error("verification found {problems} problem(s)")]
+    Verification { problems: usize, kind: ErrorKind },
+}
+
+/// What went wrong, for scripts to branch on. Each kind has its own exit
+/// status.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ErrorKind {
+    /// Anything not covered below, such as an unreadable share.
+    Failure,
+    /// Bad arguments, configuration or scheme parameters. Argument errors
+    /// caught while parsing also exit with this status.
+    Usage,
+    /// Fewer shares than the threshold.
+    InsufficientShares,
+    /// A digest, MAC, signature, commitment or passphrase check failed.
+    Integrity,
+    /// Shares from different sets, fields or thresholds, or duplicates.
+    MixedShares,
+    /// A file could not be read or written.
+    Io,
+}
+
+impl ErrorKind {
+    /// The process exit status for this kind of error.
+    pub fn exit_code(self) -> u8 {
+        match self {
+            ErrorKind::Failure => 1,
+            ErrorKind::Usage => 2,
+            ErrorKind::InsufficientShares => 3,
+            ErrorKind::Integrity => 4,
+            ErrorKind::MixedShares => 5,
+            ErrorKind::Io => 6,
+        }
+    }
+
+    /// The kind of a library error.
+    fn of(err: &ShamirError) -> Self {
+        match err {
+            ShamirError::InvalidThreshold { .. }
+            | ShamirError::MissingParameter(_)
+            | ShamirError::SecretTooLarge
+            | ShamirError::ModulusTooSmall { .. }
+            | ShamirError::InvalidModulus(_)
+            | ShamirError::InvalidPrimeSize(_)
+            | ShamirError::InvalidShareX(_)
+            | ShamirError::InvalidKey(_) => ErrorKind::Usage,
+            ShamirError::NotEnoughShares { .. } | ShamirError::Unauthorized => {
+                ErrorKind::InsufficientShares
+            }
+            ShamirError::IntegrityFailure
+            | ShamirError::MissingMac(_)
+            | ShamirError::MacMismatch(_)
+            | ShamirError::MissingSignature(_)
+            | ShamirError::UnknownDealer(_)
+            | ShamirError::SignatureMismatch(_)
+            | ShamirError::WrongPassphrase(_)
+            | ShamirError::MissingBlinding(_)
+            | ShamirError::CommitmentMismatch(_)
+            | ShamirError::InconsistentShares => ErrorKind::Integrity,
+            ShamirError::ModulusMismatch
+            | ShamirError::SetMismatch
+            | ShamirError::DuplicateDealer
+            | ShamirError::DuplicateParticipant(_)
+            | ShamirError::ThresholdMismatch
+            | ShamirError::BasisMismatch
+            | ShamirError::DuplicateShareX(_) => ErrorKind::MixedShares,
+            _ => ErrorKind::Failure,
+        }
+    }
+}
+
+impl CliError {
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            CliError::Shamir(err) | CliError::Decode { source: err, .. } => ErrorKind::of(err),
+            CliError::Io { .. } | CliError::Exists(_) => ErrorKind::Io,
+            CliError::Config { .. } | CliError::Usage(_) => ErrorKind::Usage,
+            CliError::Verification { kind, .. } => *kind,
+        }
+    }
+
+    /// The file the error concerns, if any.
+    fn path(&self) -> Option<&Path> {
+        match self {
+            CliError::Io { path, .. }
+            | CliError::Decode { path, .. }
+            | CliError::Config { path, .. }
+            | CliError::Exists(path) => Some(path),
+            _ => None,
+        }
+    }
+
+    /// The error without the file name it concerns.
+    fn detail(&self) -> String {
+        match self {
+            CliError::Io { source, .. } => source.to_string(),
+            CliError::Decode { source, .. } => source.to_string(),
+            err => err.to_string(),
+        }
+    }
+}
+
+pub fn run(cli: Cli) -> Result<(), CliError> {
+    let format = cli.format;
+    match cli.command {
+        Command::Split(args) => {
+            let config = config::Config::load(cli.config.as_deref())?;
+            split::run(args, &config, format)
+        }
+        Command::Combine(args) => combine::run(args, format),
+        Command::Verify(args) => verify::run(args, format),
+        Command::Inspect(args) => inspect::run(args, format),
+    }
+}
+
+/// Prints a command's result in the chosen format.
+fn emit<T: Serialize + fmt::Display>(format: OutputFormat, report: &T, out: &mut dyn Write) {
+    let _ = match format {
+        OutputFormat::Text => write!(out, "{report}"),
+        OutputFormat::Json => {
+            let json = serde_json::to_string_pretty(report).expect("reports serialize");
+            writeln!(out, "{json}")
+        }
+    };
+}
+
+/// A share's x-coordinate in a report: a JSON number while it fits in a
+/// `u64`, a decimal string beyond.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ShareIndex(BigUint);
+
+impl Serialize for ShareIndex {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match u64::try_from(&self.0) {
+            Ok(x) => serializer.serialize_u64(x),
+            Err(_) => serializer.collect_str(&self.0),
+        }
+    }
+}
+
+impl fmt::Display for ShareIndex {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+/// Prints an error in the chosen format to standard error.
+pub fn report_error(format: OutputFormat, err: &CliError) {
+    match format {
+        OutputFormat::Text => eprintln!("prime-shamir: {err}"),
+        OutputFormat::Json => {
+            let kind = err.kind();
+            let json = serde_json::json!({
+                "error": err.to_string(),
+                "kind": kind,
+                "exit_code": kind.exit_code(),
+                "path": err.path(),
+            });
+            eprintln!("{json}");
+        }
+    }
+}
+
+/// Prints an error from parsing the command line and returns the status to
+/// exit with. Under `--format json` the error is printed like any other, as
+/// a usage error; otherwise, and for `--help` and `--version`, clap prints it.
+pub fn report_parse_error(err: clap::Error) -> ExitCode {
+    if !err.use_stderr() || requested_format() != OutputFormat::Json {
+        err.exit();
+    }
+    let rendered = err.render().to_string();
+    // Keep the error itself and leave out clap's usage line and tips.
+    let message: Vec<&str> = rendered
+        .lines()
+        .take_while(|line| !line.is_empty())
+        .map(str::trim)
+        .collect();
+    let message = message.join(" ");
+    let message = message.strip_prefix("error: ").unwrap_or(&message);
+    let err = CliError::Usage(message.to_owned());
+    report_error(OutputFormat::Json, &err);
+    ExitCode::from(err.kind().exit_code())
+}
+
+/// The `--format` given on a command line that clap could not parse.
+fn requested_format() -> OutputFormat {
+    let mut format = OutputFormat::Text;
+    let mut args = std::env::args_os().skip(1);
+    while let Some(arg) = args.next() {
+        let value = match arg.to_str() {
+            Some("--") => break,
+            Some("--format") => args.next(),
+            Some(arg) => match arg.strip_prefix("--format=") {
+                Some(value) => Some(value.into()),
+                None => continue,
+            },
+            None => continue,
+        };
+        if let Some(value) = value.as_ref().and_then(|value| value.to_str()) {
+            if let Ok(value) = OutputFormat::from_str(value, false) {
+                format = value;
+            }
+        }
+    }
+    format
+}
@@ -0,0 +1,108 @@
+//! `prime-shamir combine`.
+
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use clap::Args;
+use prime_pmpt::{shamir_reconstruct, zeroize_biguint, ShamirError};
+use serde::Serialize;
+use zeroize::Zeroizing;
+
+use super::files::{
+    check_stdin_once, check_writable, is_stdio, read_modulus, read_share, resolve_modulus,
+    write_secret,
+};
+use super::wizard::prompt_shares;
+use super::{emit, CliError, OutputFormat};
+
+#[derive(Debug, Args)]
+pub struct CombineArgs {
+    /// Share files, in any encoding written by `split`; `-` reads one from
+    /// standard input.
+    #[arg(
+        required_unless_present = "interactive",
+        conflicts_with = "interactive",
+        value_name = "SHARE"
+    )]
+    shares: Vec<PathBuf>,
+    /// Prompt for the shares one at a time, checking each as it is entered.
+    #[arg(short, long)]
+    interactive: bool,
+    /// File the reconstructed secret is written to, or `-` for standard
+    /// output.
+    #[arg(long, value_name = "FILE")]
+    out: PathBuf,
+    /// Replace the output file if it exists.
+    #[arg(long)]
+    force: bool,
+    /// Hex file holding the modulus, for shares made over a generated or
+    /// custom prime; standard primes are recognised from the shares.
+    #[arg(long, value_name = "FILE")]
+    modulus: Option<PathBuf>,
+}
+
+pub fn run(args: CombineArgs, format: OutputFormat) -> Result<(), CliError> {
+    check_writable(&args.out, args.force)?;
+    check_stdin_once(
+        args.shares
+            .iter()
+            .map(PathBuf::as_path)
+            .chain(args.modulus.as_deref())
+            .chain(args.interactive.then_some(Path::new("-"))),
+    )?;
+    let explicit = args.modulus.as_deref().map(read_modulus).transpose()?;
+    let (shares, modulus) = if args.interactive {
+        prompt_shares(explicit.as_ref())?
+    } else {
+        let shares = args
+            .shares
+            .iter()
+            .map(|path| read_share(path))
+            .collect::<Result<Vec<_>, _>>()?;
+        let modulus = resolve_modulus(shares[0].fingerprint(), explicit.as_ref())?;
+        (shares, modulus)
+    };
+
+    let mut value = shamir_reconstruct(&shares, &modulus)?;
+    let encoded = Zeroizing::new(value.to_bytes_be());
+    zeroize_biguint(&mut value);
+    if encoded.first() != Some(&1) {
+        return Err(ShamirError::MalformedShare("missing byte-string marker").into());
+    }
+    write_secret(&args.out, &encoded[1..], args.force)?;
+    tracing::info!(
+        "wrote the {}-byte secret from {} shares to {}",
+        encoded.len() - 1,
+        shares.len(),
+        args.out.display()
+    );
+
+    let report = CombineReport {
+        out: args.out.clone(),
+        bytes: encoded.len() - 1,
+        shares: shares.len(),
+        set_id: shares[0].set_id().map(|set_id| set_id.to_string()),
+    };
+    // Keep the secret alone on standard output when it is written there.
+    if is_stdio(&args.out) {
+        emit(format, &report, &mut io::stderr().lock());
+    } else {
+        emit(format, &report, &mut io::stdout().lock());
+    }
+    Ok(())
+}
+
+#[derive(Debug, Serialize)]
+struct CombineReport {
+    out: PathBuf,
+    bytes: usize,
+    shares: usize,
+    set_id: Option<String>,
+}
+
+impl fmt::Display for CombineReport {
+    fn fmt(&self, _: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Ok(())
+    }
+}
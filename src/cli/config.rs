@@ -0,0 +1,77 @@
+//! Defaults from `config.toml`.
+//!
+//! The file is read from `--config`, or else from
+//! `$XDG_CONFIG_HOME/prime-shamir/config.toml`, falling back to
+//! `~/.config/prime-shamir/config.toml`. Every key is optional and flags
+//! given on the command line take precedence:
+//!
+//! ```toml
+//! threshold = 3
+//! shares = 5
+//! field = "modp-2048"
+//! encoding = "json"
+//! out-dir = "shares"
+//! ```
+
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use prime_pmpt::StandardPrime;
+use serde::{Deserialize, Deserializer};
+
+use super::files::ShareEncoding;
+use super::CliError;
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+pub struct Config {
+    pub threshold: Option<usize>,
+    pub shares: Option<usize>,
+    #[serde(deserialize_with = "deserialize_field")]
+    pub field: Option<StandardPrime>,
+    pub encoding: Option<ShareEncoding>,
+    pub out_dir: Option<PathBuf>,
+}
+
+impl Config {
+    /// Loads `path`, or the default file if there is one.
+    pub fn load(path: Option<&Path>) -> Result<Self, CliError> {
+        let (path, required) = match path {
+            Some(path) => (path.to_path_buf(), true),
+            None => match default_path() {
+                Some(path) => (path, false),
+                None => return Ok(Config::default()),
+            },
+        };
+        let text = match fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(err) if !required && err.kind() == io::ErrorKind::NotFound => {
+                return Ok(Config::default())
+            }
+            Err(source) => return Err(CliError::Io { path, source }),
+        };
+        toml::from_str(&text).map_err(|err| CliError::Config {
+            path,
+            message: err.message().into(),
+        })
+    }
+}
+
+fn default_path() -> Option<PathBuf> {
+    let base = env::var_os("XDG_CONFIG_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| Path::new(&home).join(".config")))?;
+    Some(base.join("prime-shamir").join("config.toml"))
+}
+
+fn deserialize_field<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<StandardPrime>, D::Error> {
+    let name = String::deserialize(deserializer)?;
+    name.parse()
+        .map(Some)
+        .map_err(|_| serde::de::Error::custom(format!("unknown standard prime {name:?}")))
+}
@@ -0,0 +1,272 @@
+//! Reading and writing secrets, moduli and share files.
+
+use std::fmt;
+use std::fs::{self, DirBuilder, OpenOptions};
+use std::io::{self, IsTerminal, Read, Write};
+#[cfg(unix)]
+use std::os::unix::fs::{DirBuilderExt, OpenOptionsExt, PermissionsExt};
+use std::path::Path;
+
+use clap::ValueEnum;
+use num_bigint::BigUint;
+use prime_pmpt::{
+    ArmorStyle, Bech32Variant, Fingerprint, ShamirError, Share, ShareDocument, StandardPrime,
+    SHARE_HRP,
+};
+use serde::{Deserialize, Serialize};
+use zeroize::Zeroizing;
+
+use super::CliError;
+
+/// How share files are written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ShareEncoding {
+    /// The compact `x-threshold-fingerprint-y` text form.
+    Text,
+    /// A JSON share document with a creation time and optional label.
+    Json,
+    /// DER in a PEM block.
+    Pem,
+    /// Base64 armor with a CRC-24 checksum.
+    Armor,
+    /// A Bech32m string.
+    Bech32,
+    /// BIP-39 words.
+    Mnemonic,
+    /// The binary `PSHR` format.
+    Binary,
+}
+
+impl fmt::Display for ShareEncoding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let value = self.to_possible_value().expect("no variant is skipped");
+        f.write_str(value.get_name())
+    }
+}
+
+/// The file name standing for standard input or standard output.
+const STDIO: &str = "-";
+
+pub fn is_stdio(path: &Path) -> bool {
+    path.as_os_str() == STDIO
+}
+
+/// Rejects more than one `-` among `paths`, as standard input can only be
+/// read once.
+pub fn check_stdin_once<'a>(paths: impl IntoIterator<Item = &'a Path>) -> Result<(), CliError> {
+    if paths.into_iter().filter(|path| is_stdio(path)).count() > 1 {
+        return Err(CliError::Usage(
+            "standard input (-) can only be read once".into(),
+        ));
+    }
+    Ok(())
+}
+
+/// Reads a file, or standard input for `-`.
+pub fn read_file(path: &Path) -> Result<Zeroizing<Vec<u8>>, CliError> {
+    let result = if is_stdio(path) {
+        let mut data = Zeroizing::new(Vec::new());
+        io::stdin().lock().read_to_end(&mut data).map(|_| data)
+    } else {
+        fs::read(path).map(Zeroizing::new)
+    };
+    result.map_err(|source| CliError::Io {
+        path: path.into(),
+        source,
+    })
+}
+
+/// Writes a file readable only by its owner, or standard output for `-`.
+/// Existing files are only replaced when `force` is set.
+pub fn write_file(path: &Path, data: &[u8], force: bool) -> Result<(), CliError> {
+    let result = if is_stdio(path) {
+        let mut stdout = io::stdout().lock();
+        stdout.write_all(data).and_then(|()| stdout.flush())
+    } else {
+        let mut options = OpenOptions::new();
+        options.write(true);
+        if force {
+            options.create(true).truncate(true);
+        } else {
+            options.create_new(true);
+        }
+        #[cfg(unix)]
+        options.mode(0o600);
+        options.open(path).and_then(|mut file| {
+            // The mode only applies to new files, so tighten replaced ones.
+            #[cfg(unix)]
+            file.set_permissions(fs::Permissions::from_mode(0o600))?;
+            file.write_all(data)?;
+            file.sync_all()
+        })
+    };
+    result.map_err(|source| match source.kind() {
+        io::ErrorKind::AlreadyExists => CliError::Exists(path.into()),
+        _ => CliError::Io {
+            path: path.into(),
+            source,
+        },
+    })
+}
+
+/// Fails early if [`write_file`] would refuse to replace `path`.
+pub fn check_writable(path: &Path, force: bool) -> Result<(), CliError> {
+    if !force && !is_stdio(path) && path.exists() {
+        return Err(CliError::Exists(path.into()));
+    }
+    Ok(())
+}
+
+/// Creates `path` and its parents, new directories accessible only by
+/// their owner.
+pub fn create_dir(path: &Path) -> Result<(), CliError> {
+    let mut builder = DirBuilder::new();
+    builder.recursive(true);
+    #[cfg(unix)]
+    builder.mode(0o700);
+    builder.create(path).map_err(|source| CliError::Io {
+        path: path.into(),
+        source,
+    })
+}
+
+/// Reads a secret, refusing to take it from a terminal, which would echo it.
+pub fn read_secret(path: &Path) -> Result<Zeroizing<Vec<u8>>, CliError> {
+    if is_stdio(path) && io::stdin().is_terminal() {
+        return Err(CliError::Usage(
+            "refusing to read the secret from a terminal; pipe it in or pass a file".into(),
+        ));
+    }
+    read_file(path)
+}
+
+/// Writes a secret, refusing to print it to a terminal.
+pub fn write_secret(path: &Path, secret: &[u8], force: bool) -> Result<(), CliError> {
+    if is_stdio(path) && io::stdout().is_terminal() {
+        return Err(CliError::Usage(
+            "refusing to print the secret to a terminal; redirect standard output or pass a file"
+                .into(),
+        ));
+    }
+    write_file(path, secret, force)
+}
+
+/// Reads a modulus written as hex digits, with an optional `0x` prefix.
+pub fn read_modulus(path: &Path) -> Result<BigUint, CliError> {
+    let contents = read_file(path)?;
+    let text = String::from_utf8_lossy(&contents);
+    let text = text.trim();
+    let digits = text.strip_prefix("0x").unwrap_or(text);
+    BigUint::parse_bytes(digits.as_bytes(), 16).ok_or_else(|| CliError::Decode {
+        path: path.into(),
+        source: ShamirError::InvalidModulus("expected a hex modulus"),
+    })
+}
+
+/// The standard prime with the given fingerprint, if any.
+pub fn standard_prime(fingerprint: Fingerprint) -> Option<StandardPrime> {
+    StandardPrime::ALL
+        .into_iter()
+        .find(|field| Fingerprint::of(&field.modulus()) == fingerprint)
+}
+
+/// The modulus of shares with `fingerprint`: `explicit` if given, or else
+/// the standard prime with that fingerprint.
+pub fn resolve_modulus(
+    fingerprint: Fingerprint,
+    explicit: Option<&BigUint>,
+) -> Result<BigUint, CliError> {
+    match explicit {
+        Some(modulus) if Fingerprint::of(modulus) == fingerprint => Ok(modulus.clone()),
+        Some(_) => Err(ShamirError::ModulusMismatch.into()),
+        None => standard_prime(fingerprint)
+            .map(StandardPrime::modulus)
+            .ok_or_else(|| {
+                CliError::Usage(format!(
+                    "modulus {fingerprint} is not a standard prime; pass it with --modulus"
+                ))
+            }),
+    }
+}
+
+pub fn encode_share(
+    share: &Share,
+    encoding: ShareEncoding,
+    modulus: &BigUint,
+    label: Option<&str>,
+) -> Result<Zeroizing<Vec<u8>>, ShamirError> {
+    let text = match encoding {
+        ShareEncoding::Text => format!("{share}\n"),
+        ShareEncoding::Json => {
+            let mut document = ShareDocument::new(share.clone());
+            if let Some(label) = label {
+                document = document.with_label(label);
+            }
+            format!("{}\n", document.to_json())
+        }
+        ShareEncoding::Pem => share.to_pem(),
+        ShareEncoding::Armor => share.to_armor(ArmorStyle::Wrapped),
+        ShareEncoding::Bech32 => format!("{}\n", share.to_bech32(Bech32Variant::Bech32m)),
+        ShareEncoding::Mnemonic => format!("{}\n", share.to_mnemonic()),
+        ShareEncoding::Binary => return share.to_bytes(modulus).map(Zeroizing::new),
+    };
+    Ok(Zeroizing::new(text.into_bytes()))
+}
+
+/// A share read from a file, with how it was stored.
+pub struct ShareFile {
+    pub share: Share,
+    pub encoding: ShareEncoding,
+    /// The JSON document the share came in, if any.
+    pub document: Option<ShareDocument>,
+}
+
+/// Decodes a share in any [`ShareEncoding`], recognised from its contents.
+pub fn decode_share(contents: &[u8]) -> Result<ShareFile, ShamirError> {
+    let plain = |share, encoding| ShareFile {
+        share,
+        encoding,
+        document: None,
+    };
+    if contents.starts_with(b"PSHR") {
+        return Ok(plain(Share::from_bytes(contents)?, ShareEncoding::Binary));
+    }
+    let text = core::str::from_utf8(contents)
+        .map_err(|_| ShamirError::MalformedShare("share file is neither text nor PSHR"))?
+        .trim();
+    let prefix = format!("{SHARE_HRP}1");
+    Ok(if text.starts_with('{') {
+        let document = ShareDocument::from_json(text)?;
+        ShareFile {
+            share: document.share().clone(),
+            encoding: ShareEncoding::Json,
+            document: Some(document),
+        }
+    } else if text.starts_with("-----BEGIN SHAMIR SHARE-----") {
+        plain(Share::from_pem(text)?, ShareEncoding::Pem)
+    } else if text.starts_with("-----BEGIN SHAMIR ARMORED SHARE-----") {
+        plain(Share::from_armor(text)?, ShareEncoding::Armor)
+    } else if text
+        .get(..prefix.len())
+        .is_some_and(|head| head.eq_ignore_ascii_case(&prefix))
+    {
+        plain(Share::from_bech32(text)?, ShareEncoding::Bech32)
+    } else if text.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        plain(Share::from_mnemonic(text)?, ShareEncoding::Mnemonic)
+    } else {
+        plain(text.parse()?, ShareEncoding::Text)
+    })
+}
+
+pub fn read_share_file(path: &Path) -> Result<ShareFile, CliError> {
+    let contents = read_file(path)?;
+    decode_share(&contents).map_err(|source| CliError::Decode {
+        path: path.into(),
+        source,
+    })
+}
+
+pub fn read_share(path: &Path) -> Result<Share, CliError> {
+    read_share_file(path).map(|file| file.share)
+}
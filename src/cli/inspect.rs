@@ -0,0 +1,173 @@
+//! `prime-shamir inspect`.
+
+use std::fmt;
+use std::io;
+use std::path::PathBuf;
+
+use clap::Args;
+use prime_pmpt::Fingerprint;
+use serde::Serialize;
+
+use super::files::{
+    check_stdin_once, read_modulus, read_share_file, standard_prime, ShareEncoding,
+};
+use super::{emit, CliError, OutputFormat, ShareIndex};
+
+#[derive(Debug, Args)]
+pub struct InspectArgs {
+    /// Share files to describe; `-` reads one from standard input.
+    #[arg(required = true, value_name = "SHARE")]
+    shares: Vec<PathBuf>,
+    /// Also print the share value and blinding value.
+    #[arg(long)]
+    reveal: bool,
+    /// Hex file holding the modulus, to report the field size of shares
+    /// made over a generated or custom prime.
+    #[arg(long, value_name = "FILE")]
+    modulus: Option<PathBuf>,
+}
+
+pub fn run(args: InspectArgs, format: OutputFormat) -> Result<(), CliError> {
+    check_stdin_once(
+        args.shares
+            .iter()
+            .chain(&args.modulus)
+            .map(PathBuf::as_path),
+    )?;
+    let modulus = args.modulus.as_deref().map(read_modulus).transpose()?;
+    let mut report = InspectReport(Vec::with_capacity(args.shares.len()));
+    for path in &args.shares {
+        let file = read_share_file(path)?;
+        let share = &file.share;
+        let field = standard_prime(share.fingerprint());
+        let field_bits = match (field, &modulus) {
+            (Some(field), _) => Some(field.bits() as u64),
+            (None, Some(modulus)) if Fingerprint::of(modulus) == share.fingerprint() => {
+                Some(modulus.bits())
+            }
+            _ => None,
+        };
+        report.0.push(ShareInfo {
+            path: path.clone(),
+            encoding: file.encoding,
+            index: ShareIndex(share.x().clone()),
+            threshold: share.threshold(),
+            field: field.map(|field| field.name()),
+            field_bits,
+            fingerprint: share.fingerprint().to_string(),
+            set_id: share.set_id().map(|set_id| set_id.to_string()),
+            created: file
+                .document
+                .as_ref()
+                .map(|document| document.created().to_rfc3339()),
+            label: file
+                .document
+                .as_ref()
+                .and_then(|document| document.label().map(String::from)),
+            digest: share.digest().is_some(),
+            mac: share.mac().is_some(),
+            dealer_key_id: share
+                .signature()
+                .map(|signature| hex::encode(signature.key_id())),
+            blinding: share.blinding().is_some(),
+            y: args.reveal.then(|| format!("{:x}", share.y())),
+            blinding_value: share
+                .blinding()
+                .filter(|_| args.reveal)
+                .map(|blinding| format!("{blinding:x}")),
+        });
+    }
+    emit(format, &report, &mut io::stdout().lock());
+    Ok(())
+}
+
+#[derive(Debug, Serialize)]
+#[serde(transparent)]
+struct InspectReport(Vec<ShareInfo>);
+
+#[derive(Debug, Serialize)]
+struct ShareInfo {
+    path: PathBuf,
+    encoding: ShareEncoding,
+    index: ShareIndex,
+    threshold: usize,
+    field: Option<&'static str>,
+    field_bits: Option<u64>,
+    fingerprint: String,
+    set_id: Option<String>,
+    created: Option<String>,
+    label: Option<String>,
+    digest: bool,
+    mac: bool,
+    dealer_key_id: Option<String>,
+    blinding: bool,
+    /// Only filled in with `--reveal`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    y: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    blinding_value: Option<String>,
+}
+
+impl fmt::Display for InspectReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, info) in self.0.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{info}")?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for ShareInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let present = |present: bool| if present { "present" } else { "none" };
+        let unrecorded = format!("not recorded by the {} encoding", self.encoding);
+
+        writeln!(f, "file:        {}", self.path.display())?;
+        writeln!(f, "encoding:    {}", self.encoding)?;
+        writeln!(f, "index:       {}", self.index)?;
+        writeln!(f, "threshold:   {}", self.threshold)?;
+        match (self.field_bits, self.field) {
+            (Some(bits), Some(name)) => writeln!(f, "field:       {bits} bits ({name})")?,
+            (Some(bits), None) => writeln!(f, "field:       {bits} bits")?,
+            _ => writeln!(f, "field:       unknown, pass --modulus")?,
+        }
+        writeln!(f, "fingerprint: {}", self.fingerprint)?;
+        writeln!(
+            f,
+            "set ID:      {}",
+            self.set_id.as_deref().unwrap_or("none")
+        )?;
+        match &self.created {
+            Some(created) => {
+                writeln!(f, "created:     {created}")?;
+                writeln!(
+                    f,
+                    "label:       {}",
+                    self.label.as_deref().unwrap_or("none")
+                )?;
+            }
+            None => {
+                writeln!(f, "created:     {unrecorded}")?;
+                writeln!(f, "label:       {unrecorded}")?;
+            }
+        }
+        writeln!(f, "digest:      {}", present(self.digest))?;
+        writeln!(f, "MAC:         {}", present(self.mac))?;
+        match &self.dealer_key_id {
+            Some(key_id) => writeln!(f, "signature:   dealer key {key_id}")?,
+            None => writeln!(f, "signature:   none")?,
+        }
+        writeln!(f, "blinding:    {}", present(self.blinding))?;
+        match &self.y {
+            Some(y) => writeln!(f, "y:           {y}")?,
+            None => writeln!(f, "y:           hidden, pass --reveal to show")?,
+        }
+        if let Some(blinding) = &self.blinding_value {
+            writeln!(f, "blinding y:  {blinding}")?;
+        }
+        Ok(())
+    }
+}
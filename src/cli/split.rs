@@ -0,0 +1,291 @@
+//! `prime-shamir split`.
+
+use std::collections::BTreeSet;
+use std::fmt;
+use std::io;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use clap::Args;
+use indicatif::ProgressBar;
+use num_bigint::BigUint;
+use prime_pmpt::{
+    bytes_modulus, generate_large_prime_with_progress, shamir_split_shares,
+    shamir_split_shares_with_digest, zeroize_biguint, ShamirError, Share, ShareMac, StandardPrime,
+    VerificationKey,
+};
+use rand::SeedableRng;
+use rand_chacha::ChaCha20Rng;
+use serde::Serialize;
+use zeroize::Zeroizing;
+
+use super::config::Config;
+use super::files::{
+    check_stdin_once, check_writable, create_dir, encode_share, is_stdio, read_modulus,
+    read_secret, standard_prime, write_file, ShareEncoding,
+};
+use super::{emit, CliError, OutputFormat, ShareIndex};
+
+const DEFAULT_NAME: &str = "share-{index}.shr";
+
+#[derive(Debug, Args)]
+pub struct SplitArgs {
+    /// Number of shares needed to reconstruct the secret.
+    #[arg(short, long)]
+    threshold: Option<usize>,
+    /// Number of shares to create.
+    #[arg(short = 'n', long)]
+    shares: Option<usize>,
+    /// File holding the secret, or `-` for standard input.
+    #[arg(long = "in", value_name = "FILE")]
+    input: PathBuf,
+    /// Directory the share files are written to, created if missing.
+    #[arg(long, value_name = "DIR")]
+    out_dir: Option<PathBuf>,
+    /// Share file names; `{index}` is replaced by the share index, and
+    /// `{threshold}`, `{shares}` and `{set}` by the scheme's parameters.
+    #[arg(long, value_name = "TEMPLATE", default_value = DEFAULT_NAME)]
+    name_template: String,
+    /// Replace existing files in the output directory.
+    #[arg(long)]
+    force: bool,
+    /// Standard prime to share over, e.g. `modp-2048`; by default the
+    /// smallest one that holds the secret.
+    #[arg(long, value_name = "NAME", conflicts_with_all = ["prime_bits", "modulus"])]
+    field: Option<StandardPrime>,
+    /// Share over a fresh prime of this many bits, written to `modulus.hex`
+    /// in the output directory.
+    #[arg(long, value_name = "BITS", conflicts_with = "modulus")]
+    prime_bits: Option<usize>,
+    /// Share over the prime in this hex file.
+    #[arg(long, value_name = "FILE")]
+    modulus: Option<PathBuf>,
+    /// Encoding of the share files [default: text].
+    #[arg(long, value_enum)]
+    encoding: Option<ShareEncoding>,
+    /// Embed a salted digest of the secret so reconstruction detects
+    /// wrong shares.
+    #[arg(long)]
+    digest: bool,
+    /// Tag the shares with MACs and write the verification key to
+    /// `verification.key` in the output directory.
+    #[arg(long)]
+    mac: bool,
+    /// Label stored with each share; needs `--encoding json`.
+    #[arg(long)]
+    label: Option<String>,
+}
+
+pub fn run(args: SplitArgs, config: &Config, format: OutputFormat) -> Result<(), CliError> {
+    let threshold = args
+        .threshold
+        .or(config.threshold)
+        .ok_or_else(|| missing("--threshold"))?;
+    let share_count = args
+        .shares
+        .or(config.shares)
+        .ok_or_else(|| missing("--shares"))?;
+    let out_dir = args
+        .out_dir
+        .clone()
+        .or_else(|| config.out_dir.clone())
+        .ok_or_else(|| missing("--out-dir"))?;
+    let encoding = args
+        .encoding
+        .or(config.encoding)
+        .unwrap_or(ShareEncoding::Text);
+    // A field from the config file gives way to any field flag.
+    let config_field = config
+        .field
+        .filter(|_| args.prime_bits.is_none() && args.modulus.is_none());
+    let field = args.field.or(config_field);
+    if args.label.is_some() && encoding != ShareEncoding::Json {
+        return Err(CliError::Usage(
+            "--label is only stored by --encoding json".into(),
+        ));
+    }
+    if is_stdio(&out_dir) {
+        return Err(CliError::Usage(
+            "shares are written to files; pass a directory to --out-dir".into(),
+        ));
+    }
+    check_stdin_once(
+        [args.input.as_path()]
+            .into_iter()
+            .chain(args.modulus.as_deref()),
+    )?;
+    let mut rng = ChaCha20Rng::from_entropy();
+    let secret = read_secret(&args.input)?;
+
+    let modulus = match (field, args.prime_bits, &args.modulus) {
+        (Some(field), _, _) => field.modulus(),
+        (_, Some(bits), _) => generate_prime(bits, &mut rng, format)?,
+        (_, _, Some(path)) => read_modulus(path)?,
+        _ => bytes_modulus(secret.len())?,
+    };
+    let mut encoded = Zeroizing::new(Vec::with_capacity(secret.len() + 1));
+    encoded.push(1u8);
+    encoded.extend_from_slice(&secret);
+    let mut value = BigUint::from_bytes_be(&encoded);
+    if value >= modulus {
+        zeroize_biguint(&mut value);
+        return Err(ShamirError::SecretTooLarge.into());
+    }
+    let split = if args.digest {
+        shamir_split_shares_with_digest
+    } else {
+        shamir_split_shares
+    };
+    let shares = split(&value, threshold, share_count, &modulus, &mut rng);
+    zeroize_biguint(&mut value);
+    let mut shares = shares?;
+
+    let mut files = Vec::with_capacity(shares.len() + 2);
+    if args.prime_bits.is_some() {
+        let contents = format!("{modulus:x}\n").into_bytes();
+        files.push((
+            out_dir.join("modulus.hex"),
+            FileKind::Modulus,
+            Zeroizing::new(contents),
+        ));
+    }
+    if args.mac {
+        let key = VerificationKey::random(&mut rng);
+        shares = shares
+            .into_iter()
+            .map(|share| {
+                let mac = ShareMac::compute(&share, &key);
+                share.with_mac(mac)
+            })
+            .collect();
+        let contents = format!("{key}\n").into_bytes();
+        files.push((
+            out_dir.join("verification.key"),
+            FileKind::VerificationKey,
+            Zeroizing::new(contents),
+        ));
+    }
+    for share in &shares {
+        let name = share_file_name(&args.name_template, share, share_count)?;
+        let data = encode_share(share, encoding, &modulus, args.label.as_deref())?;
+        let kind = FileKind::Share(ShareIndex(share.x().clone()));
+        files.push((out_dir.join(name), kind, data));
+    }
+
+    // Check every target first so a clash does not leave a partial set.
+    let mut names = BTreeSet::new();
+    for (path, _, _) in &files {
+        if !names.insert(path) {
+            return Err(CliError::Usage(format!(
+                "--name-template gives two files the name {}",
+                path.display()
+            )));
+        }
+        check_writable(path, args.force)?;
+    }
+    create_dir(&out_dir)?;
+    let mut report = SplitReport {
+        threshold,
+        shares: share_count,
+        set_id: shares[0].set_id().map(|set_id| set_id.to_string()),
+        fingerprint: shares[0].fingerprint().to_string(),
+        field: standard_prime(shares[0].fingerprint()).map(StandardPrime::name),
+        files: Vec::with_capacity(files.len()),
+    };
+    for (path, kind, data) in files {
+        write_file(&path, &data, args.force)?;
+        tracing::info!("wrote {}", path.display());
+        report.files.push(WrittenFile { path, kind });
+    }
+    emit(format, &report, &mut io::stdout().lock());
+    Ok(())
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "kebab-case", tag = "kind", content = "index")]
+enum FileKind {
+    Share(ShareIndex),
+    Modulus,
+    VerificationKey,
+}
+
+#[derive(Debug, Serialize)]
+struct WrittenFile {
+    path: PathBuf,
+    #[serde(flatten)]
+    kind: FileKind,
+}
+
+#[derive(Debug, Serialize)]
+struct SplitReport {
+    threshold: usize,
+    shares: usize,
+    set_id: Option<String>,
+    fingerprint: String,
+    field: Option<&'static str>,
+    files: Vec<WrittenFile>,
+}
+
+impl fmt::Display for SplitReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for file in &self.files {
+            writeln!(f, "{}", file.path.display())?;
+        }
+        Ok(())
+    }
+}
+
+/// Generates the `--prime-bits` modulus behind a spinner on standard error.
+fn generate_prime(
+    bits: usize,
+    rng: &mut ChaCha20Rng,
+    format: OutputFormat,
+) -> Result<BigUint, ShamirError> {
+    let spinner = match format {
+        OutputFormat::Text => ProgressBar::new_spinner(),
+        OutputFormat::Json => ProgressBar::hidden(),
+    };
+    spinner.enable_steady_tick(Duration::from_millis(100));
+    spinner.set_message(format!("searching for a {bits}-bit prime"));
+    let prime = generate_large_prime_with_progress(bits, rng, |progress| {
+        spinner.set_message(format!(
+            "searching for a {bits}-bit prime: {} candidates in {:.1}s",
+            progress.candidates(),
+            progress.elapsed().as_secs_f64()
+        ));
+    });
+    spinner.finish_and_clear();
+    prime
+}
+
+fn missing(flag: &str) -> CliError {
+    CliError::Usage(format!("{flag} is required unless set in the config file"))
+}
+
+/// Expands the `--name-template` placeholders for `share`.
+fn share_file_name(template: &str, share: &Share, shares: usize) -> Result<String, CliError> {
+    if !template.contains("{index}") {
+        return Err(CliError::Usage(
+            "--name-template must contain {index}".into(),
+        ));
+    }
+    let set_id = share
+        .set_id()
+        .map_or_else(|| "none".into(), |set_id| set_id.to_string());
+    let name = template
+        .replace("{index}", &share.x().to_string())
+        .replace("{threshold}", &share.threshold().to_string())
+        .replace("{shares}", &shares.to_string())
+        .replace("{set}", &set_id);
+    if name.contains(['{', '}']) {
+        return Err(CliError::Usage(format!(
+            "unknown placeholder in --name-template {template:?}"
+        )));
+    }
+    if name.contains(std::path::is_separator) {
+        return Err(CliError::Usage(
+            "--name-template names a file, not a path".into(),
+        ));
+    }
+    Ok(name)
+}
@@ -0,0 +1,293 @@
+//! `prime-shamir verify`.
+
+use std::collections::BTreeSet;
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use clap::Args;
+use num_bigint::BigUint;
+use prime_pmpt::{
+    Fingerprint, PedersenCommitments, PedersenParams, ShamirError, Share, StandardPrime,
+    VerificationKey,
+};
+use serde::Serialize;
+
+use super::files::{check_stdin_once, read_file, read_modulus, read_share, standard_prime};
+use super::{emit, CliError, ErrorKind, OutputFormat, ShareIndex};
+
+#[derive(Debug, Args)]
+pub struct VerifyArgs {
+    /// Share files to check; `-` reads one from standard input.
+    #[arg(required = true, value_name = "SHARE")]
+    shares: Vec<PathBuf>,
+    /// Verification key written by `split --mac`, to check share MACs.
+    #[arg(long, value_name = "FILE")]
+    key: Option<PathBuf>,
+    /// Hex file holding the modulus, for shares made over a generated or
+    /// custom prime.
+    #[arg(long, value_name = "FILE")]
+    modulus: Option<PathBuf>,
+    /// CBOR Pedersen commitments, to check shares with blinding values.
+    #[arg(long, value_name = "FILE", requires = "pedersen_params")]
+    commitments: Option<PathBuf>,
+    /// CBOR Pedersen group parameters for `--commitments`.
+    #[arg(long, value_name = "FILE", requires = "commitments")]
+    pedersen_params: Option<PathBuf>,
+}
+
+/// Everything a share can be checked against besides itself.
+struct Checks {
+    key: Option<VerificationKey>,
+    modulus: Option<BigUint>,
+    pedersen: Option<(PedersenCommitments, PedersenParams)>,
+}
+
+pub fn run(args: VerifyArgs, format: OutputFormat) -> Result<(), CliError> {
+    check_stdin_once(
+        args.shares
+            .iter()
+            .chain(&args.key)
+            .chain(&args.modulus)
+            .chain(&args.commitments)
+            .chain(&args.pedersen_params)
+            .map(PathBuf::as_path),
+    )?;
+    let checks = Checks {
+        key: args.key.as_deref().map(read_key).transpose()?,
+        modulus: args.modulus.as_deref().map(read_modulus).transpose()?,
+        pedersen: match (&args.commitments, &args.pedersen_params) {
+            (Some(commitments), Some(params)) => Some((
+                read_cbor(commitments, PedersenCommitments::from_cbor)?,
+                read_cbor(params, PedersenParams::from_cbor)?,
+            )),
+            _ => None,
+        },
+    };
+
+    let mut report = VerifyReport::default();
+    // The kind of the first problem found decides the exit status.
+    let mut kind = None;
+    let mut shares = Vec::with_capacity(args.shares.len());
+    for path in &args.shares {
+        let mut check = ShareCheck {
+            path: path.clone(),
+            ok: false,
+            index: None,
+            threshold: None,
+            notes: Vec::new(),
+            error: None,
+        };
+        match read_share(path) {
+            Ok(share) => {
+                check.index = Some(ShareIndex(share.x().clone()));
+                check.threshold = Some(share.threshold());
+                match checks.share(&share) {
+                    Ok(notes) => {
+                        check.ok = true;
+                        check.notes = notes;
+                    }
+                    Err(err) => {
+                        kind = kind.or(Some(ErrorKind::of(&err)));
+                        check.error = Some(err.to_string());
+                    }
+                }
+                shares.push(share);
+            }
+            Err(err) => {
+                kind = kind.or(Some(err.kind()));
+                check.error = Some(err.detail());
+            }
+        }
+        report.problems += usize::from(!check.ok);
+        report.shares.push(check);
+    }
+
+    if let Some(first) = shares.first() {
+        let problems = set_problems(&shares);
+        if let Some(problem) = problems.first() {
+            kind = kind.or(Some(ErrorKind::of(problem)));
+        }
+        let problems: Vec<String> = problems.iter().map(ToString::to_string).collect();
+        report.problems += problems.len();
+        report.set = Some(SetCheck {
+            shares: shares.len(),
+            threshold: first.threshold(),
+            set_id: first.set_id().map(|set_id| set_id.to_string()),
+            modulus: standard_prime(first.fingerprint()).map_or_else(
+                || first.fingerprint().to_string(),
+                |field| field.name().into(),
+            ),
+            missing: first.threshold().saturating_sub(shares.len()),
+            problems,
+        });
+    }
+
+    emit(format, &report, &mut io::stdout().lock());
+    if let Some(kind) = kind {
+        return Err(CliError::Verification {
+            problems: report.problems,
+            kind,
+        });
+    }
+    Ok(())
+}
+
+#[derive(Debug, Default, Serialize)]
+struct VerifyReport {
+    shares: Vec<ShareCheck>,
+    set: Option<SetCheck>,
+    problems: usize,
+}
+
+#[derive(Debug, Serialize)]
+struct ShareCheck {
+    path: PathBuf,
+    ok: bool,
+    index: Option<ShareIndex>,
+    threshold: Option<usize>,
+    notes: Vec<&'static str>,
+    error: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct SetCheck {
+    shares: usize,
+    threshold: usize,
+    set_id: Option<String>,
+    modulus: String,
+    /// Shares still needed to reach the threshold.
+    missing: usize,
+    problems: Vec<String>,
+}
+
+impl fmt::Display for VerifyReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for check in &self.shares {
+            write!(f, "{}: ", check.path.display())?;
+            match (&check.error, &check.index, check.threshold) {
+                (Some(err), _, _) => writeln!(f, "{err}")?,
+                (None, Some(index), Some(threshold)) => {
+                    write!(f, "ok, share {index} of threshold {threshold}")?;
+                    for note in &check.notes {
+                        write!(f, ", {note}")?;
+                    }
+                    writeln!(f)?;
+                }
+                _ => writeln!(f, "ok")?,
+            }
+        }
+        if let Some(set) = &self.set {
+            writeln!(
+                f,
+                "set: {} share(s), threshold {}, set ID {}, modulus {}",
+                set.shares,
+                set.threshold,
+                set.set_id.as_deref().unwrap_or("none"),
+                set.modulus
+            )?;
+            for problem in &set.problems {
+                writeln!(f, "set: {problem}")?;
+            }
+            if set.problems.is_empty() {
+                match set.missing {
+                    0 => writeln!(f, "set: consistent, enough to reconstruct")?,
+                    missing => {
+                        writeln!(f, "set: consistent, {missing} more needed to reconstruct")?
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Checks {
+    /// Checks one share, returning notes on what was and was not checked.
+    fn share(&self, share: &Share) -> Result<Vec<&'static str>, ShamirError> {
+        let mut notes = Vec::new();
+        let modulus = match (&self.modulus, &self.pedersen) {
+            (Some(modulus), _) => Some(modulus.clone()),
+            (None, Some((_, params))) => Some(params.q().clone()),
+            (None, None) => standard_prime(share.fingerprint()).map(StandardPrime::modulus),
+        };
+        match modulus {
+            Some(modulus) => {
+                if share.fingerprint() != Fingerprint::of(&modulus) {
+                    return Err(ShamirError::ModulusMismatch);
+                }
+                if share.y() >= &modulus {
+                    return Err(ShamirError::MalformedShare(
+                        "y is not reduced modulo the field",
+                    ));
+                }
+            }
+            None => notes.push("modulus unknown, range not checked"),
+        }
+        match (share.mac(), &self.key) {
+            (Some(_), Some(key)) => {
+                share.verify_mac(key)?;
+                notes.push("MAC verified");
+            }
+            (None, Some(_)) => return Err(ShamirError::MissingMac(share.x().clone())),
+            (Some(_), None) => notes.push("MAC not checked without --key"),
+            (None, None) => {}
+        }
+        match (share.blinding(), &self.pedersen) {
+            (_, Some((commitments, params))) => {
+                commitments.verify(share, params)?;
+                notes.push("commitment verified");
+            }
+            (Some(_), None) => notes.push("commitment not checked without --commitments"),
+            (None, None) => {}
+        }
+        Ok(notes)
+    }
+}
+
+/// Reasons the shares cannot be combined with each other.
+fn set_problems(shares: &[Share]) -> Vec<ShamirError> {
+    let first = &shares[0];
+    let mut problems = Vec::new();
+    if shares
+        .iter()
+        .any(|share| share.fingerprint() != first.fingerprint())
+    {
+        problems.push(ShamirError::ModulusMismatch);
+    }
+    if shares
+        .iter()
+        .any(|share| share.threshold() != first.threshold())
+    {
+        problems.push(ShamirError::ThresholdMismatch);
+    }
+    if shares.iter().any(|share| share.set_id() != first.set_id()) {
+        problems.push(ShamirError::SetMismatch);
+    }
+    let mut seen = BTreeSet::new();
+    for share in shares {
+        if !seen.insert(share.x()) {
+            problems.push(ShamirError::DuplicateShareX(share.x().clone()));
+        }
+    }
+    problems
+}
+
+fn read_key(path: &Path) -> Result<VerificationKey, CliError> {
+    let contents = read_file(path)?;
+    String::from_utf8_lossy(&contents)
+        .trim()
+        .parse()
+        .map_err(|source| CliError::Decode {
+            path: path.into(),
+            source,
+        })
+}
+
+fn read_cbor<T>(path: &Path, decode: fn(&[u8]) -> Result<T, ShamirError>) -> Result<T, CliError> {
+    let contents = read_file(path)?;
+    decode(&contents).map_err(|source| CliError::Decode {
+        path: path.into(),
+        source,
+    })
+}
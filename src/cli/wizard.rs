@@ -0,0 +1,142 @@
+//! Interactive share entry for `combine --interactive`.
+//!
+//! Prompts go to standard error, so the reconstructed secret can still be
+//! written to standard output.
+
+use std::io::{self, BufRead};
+use std::path::Path;
+
+use num_bigint::BigUint;
+use prime_pmpt::{ShamirError, Share};
+use zeroize::Zeroizing;
+
+use super::files::{decode_share, read_share, resolve_modulus};
+use super::CliError;
+
+/// Reads shares from standard input until the threshold is reached,
+/// rejecting any that do not decode or do not belong with the first.
+pub fn prompt_shares(explicit: Option<&BigUint>) -> Result<(Vec<Share>, BigUint), CliError> {
+    let mut input = io::stdin().lock();
+    let mut shares: Vec<Share> = Vec::new();
+    let mut modulus = None;
+    eprintln!("Enter each share as pasted text, mnemonic words or a file path.");
+    loop {
+        let prompt = match shares.first() {
+            Some(first) if shares.len() >= first.threshold() => break,
+            Some(first) => format!("share {} of {}> ", shares.len() + 1, first.threshold()),
+            None => "share 1> ".into(),
+        };
+        eprint!("{prompt}");
+        let Some(entry) = read_entry(&mut input)? else {
+            return Err(match shares.first() {
+                Some(first) => ShamirError::NotEnoughShares {
+                    required: first.threshold(),
+                    provided: shares.len(),
+                }
+                .into(),
+                None => CliError::Usage("input ended before any share was entered".into()),
+            });
+        };
+        if entry.trim().is_empty() {
+            continue;
+        }
+        match accept(&entry, &shares, modulus.as_ref(), explicit) {
+            Ok((share, field)) => {
+                match shares.first() {
+                    Some(first) => eprintln!(
+                        "  accepted share {} ({} of {})",
+                        share.x(),
+                        shares.len() + 1,
+                        first.threshold()
+                    ),
+                    None => eprintln!(
+                        "  accepted share {} (1 of {}), set ID {}",
+                        share.x(),
+                        share.threshold(),
+                        share
+                            .set_id()
+                            .map_or_else(|| "none".into(), |set_id| set_id.to_string())
+                    ),
+                }
+                modulus = Some(field);
+                shares.push(share);
+            }
+            Err(err) => eprintln!("  rejected: {}", err.detail()),
+        }
+    }
+    Ok((shares, modulus.expect("set by the first share")))
+}
+
+/// Decodes one entry and checks it against the shares accepted so far,
+/// returning it with the modulus of the set.
+fn accept(
+    entry: &str,
+    shares: &[Share],
+    modulus: Option<&BigUint>,
+    explicit: Option<&BigUint>,
+) -> Result<(Share, BigUint), CliError> {
+    let path = Path::new(entry.trim());
+    let share = if path.is_file() {
+        read_share(path)?
+    } else {
+        decode_share(entry.as_bytes())?.share
+    };
+    if let Some(first) = shares.first() {
+        if share.fingerprint() != first.fingerprint() {
+            return Err(ShamirError::ModulusMismatch.into());
+        }
+        if share.threshold() != first.threshold() {
+            return Err(ShamirError::ThresholdMismatch.into());
+        }
+        if share.set_id() != first.set_id() {
+            return Err(ShamirError::SetMismatch.into());
+        }
+        if shares.iter().any(|other| other.x() == share.x()) {
+            return Err(ShamirError::DuplicateShareX(share.x().clone()).into());
+        }
+    }
+    let modulus = match modulus {
+        Some(modulus) => modulus.clone(),
+        None => resolve_modulus(share.fingerprint(), explicit)?,
+    };
+    if share.y() >= &modulus {
+        return Err(ShamirError::MalformedShare("y is not reduced modulo the field").into());
+    }
+    Ok((share, modulus))
+}
+
+/// Reads one share entry: a single line, or for PEM, armor and JSON, lines
+/// up to the end of the block or a blank line. `None` at end of input.
+fn read_entry(input: &mut impl BufRead) -> Result<Option<Zeroizing<String>>, CliError> {
+    let mut entry = Zeroizing::new(String::new());
+    if read_line(input, &mut entry)? == 0 {
+        return Ok(None);
+    }
+    let first = entry.trim_start();
+    let block = first.starts_with("-----BEGIN");
+    let json = first.starts_with('{');
+    while (block || json) && !entry_complete(&entry, block) {
+        let start = entry.len();
+        if read_line(input, &mut entry)? == 0 || entry[start..].trim().is_empty() {
+            break;
+        }
+    }
+    Ok(Some(entry))
+}
+
+fn entry_complete(entry: &str, block: bool) -> bool {
+    if block {
+        entry
+            .lines()
+            .any(|line| line.trim_start().starts_with("-----END"))
+    } else {
+        serde_json::from_str::<serde::de::IgnoredAny>(entry).is_ok()
+    }
+}
+
+fn read_line(input: &mut impl BufRead, entry: &mut String) -> Result<usize, CliError> {
+    input.read_line(entry).map_err(|source| CliError::Io {
+        path: "-".into(),
+        source,
+    })
+}
@@ -0,0 +1,10 @@
+//! CRC-32 as in ISO-HDLC and zlib, used by Bytewords and PNG.
+
+/// CRC-32 of `data` with the reflected polynomial `0xedb88320`.
+pub(crate) fn crc32(data: &[u8]) -> u32 {
+    !data.iter().fold(!0u32, |crc, &byte| {
+        (0..8).fold(crc ^ byte as u32, |crc, _| {
+            (crc >> 1) ^ (0xedb8_8320 & 0u32.wrapping_sub(crc & 1))
+        })
+    })
+}
@@ -0,0 +1,137 @@
+//! Minimal DER reader and writer for the ASN.1 structures the crate
+//! exchanges: sequences, unsigned integers, octet strings, object
+//! identifiers and NULL.
+
+use alloc::vec::Vec;
+
+use num_bigint::BigUint;
+use zeroize::Zeroize;
+
+use crate::ShamirError;
+
+pub(crate) const INTEGER: u8 = 0x02;
+pub(crate) const OCTET_STRING: u8 = 0x04;
+pub(crate) const NULL: u8 = 0x05;
+pub(crate) const OID: u8 = 0x06;
+pub(crate) const SEQUENCE: u8 = 0x30;
+
+/// Cursor over DER input.
+pub(crate) struct Reader<'a> {
+    input: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    pub(crate) fn new(input: &'a [u8]) -> Self {
+        Reader { input }
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.input.is_empty()
+    }
+
+    /// Input not yet consumed.
+    #[cfg(feature = "bip39")]
+    pub(crate) fn remaining(&self) -> &'a [u8] {
+        self.input
+    }
+
+    /// Tag of the next element, if any.
+    pub(crate) fn peek_tag(&self) -> Option<u8> {
+        self.input.first().copied()
+    }
+
+    /// Reads one element with the given tag and returns its contents.
+    pub(crate) fn read(&mut self, tag: u8) -> Result<&'a [u8], ShamirError> {
+        let malformed = ShamirError::MalformedShare("malformed DER");
+        let (&actual, rest) = self.input.split_first().ok_or(malformed.clone())?;
+        if actual != tag {
+            return Err(ShamirError::MalformedShare("unexpected DER tag"));
+        }
+        let (&first, mut rest) = rest.split_first().ok_or(malformed.clone())?;
+        let len = if first < 0x80 {
+            first as usize
+        } else {
+            let count = (first & 0x7f) as usize;
+            if count == 0 || count > 4 || rest.len() < count || rest[0] == 0 {
+                return Err(malformed);
+            }
+            let len = rest[..count]
+                .iter()
+                .fold(0usize, |acc, &byte| (acc << 8) | byte as usize);
+            rest = &rest[count..];
+            if len < 0x80 {
+                return Err(malformed);
+            }
+            len
+        };
+        if rest.len() < len {
+            return Err(malformed);
+        }
+        let (contents, rest) = rest.split_at(len);
+        self.input = rest;
+        Ok(contents)
+    }
+
+    pub(crate) fn sequence(&mut self) -> Result<Reader<'a>, ShamirError> {
+        self.read(SEQUENCE).map(Reader::new)
+    }
+
+    /// Reads a non-negative INTEGER.
+    pub(crate) fn uint(&mut self) -> Result<BigUint, ShamirError> {
+        self.uint_tagged(INTEGER)
+    }
+
+    /// Reads a non-negative INTEGER under an implicit `tag`.
+    pub(crate) fn uint_tagged(&mut self, tag: u8) -> Result<BigUint, ShamirError> {
+        let contents = self.read(tag)?;
+        match contents {
+            [] => Err(ShamirError::MalformedShare("empty DER integer")),
+            [first, ..] if first & 0x80 != 0 => {
+                Err(ShamirError::MalformedShare("negative DER integer"))
+            }
+            [0, second, ..] if second & 0x80 == 0 => {
+                Err(ShamirError::MalformedShare("non-minimal DER integer"))
+            }
+            _ => Ok(BigUint::from_bytes_be(contents)),
+        }
+    }
+
+    /// Fails unless all input has been consumed.
+    pub(crate) fn finish(&self) -> Result<(), ShamirError> {
+        if self.input.is_empty() {
+            Ok(())
+        } else {
+            Err(ShamirError::MalformedShare("trailing DER data"))
+        }
+    }
+}
+
+/// Appends one element with the given tag and contents.
+pub(crate) fn write(out: &mut Vec<u8>, tag: u8, contents: &[u8]) {
+    out.push(tag);
+    let len = contents.len();
+    if len < 0x80 {
+        out.push(len as u8);
+    } else {
+        let bytes = (len as u32).to_be_bytes();
+        let skip = bytes.iter().take_while(|&&byte| byte == 0).count();
+        out.push(0x80 | (4 - skip) as u8);
+        out.extend_from_slice(&bytes[skip..]);
+    }
+    out.extend_from_slice(contents);
+}
+
+/// Appends a non-negative INTEGER in minimal form.
+pub(crate) fn write_uint(out: &mut Vec<u8>, value: &BigUint) {
+    write_uint_tagged(out, INTEGER, value);
+}
+
+/// Appends a non-negative INTEGER in minimal form under an implicit `tag`.
+pub(crate) fn write_uint_tagged(out: &mut Vec<u8>, tag: u8, value: &BigUint) {
+    let mut bytes = value.to_bytes_be();
+    if bytes[0] & 0x80 != 0 {
+        bytes.insert(0, 0);
+    }
+    write(out, tag, &bytes);
+    bytes.zeroize();
+}
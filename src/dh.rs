@@ -0,0 +1,102 @@
+//! Diffie–Hellman group parameters over safe primes.
+//!
+//! A group is a safe prime `p = 2q + 1` and a generator `g` of the
+//! order-`q` subgroup of quadratic residues. Working in a subgroup of prime
+//! order means a public value leaks nothing about the exponent, unlike the
+//! full group, where `g^x` gives away the low bit of `x`.
+
+use num_bigint::BigUint;
+use num_traits::One;
+use rand::{CryptoRng, RngCore};
+
+use crate::bigint::modpow;
+use crate::primes::{generate_safe_prime, is_baillie_psw_prime};
+use crate::ShamirError;
+
+/// A Diffie–Hellman group: a safe prime `p = 2q + 1` and a generator `g` of
+/// order `q`, checked by [`validate_params`] when constructed or
+/// deserialized.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(try_from = "crate::serde_support::DhParamsRepr")
+)]
+pub struct DhParams {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::biguint_hex"))]
+    p: BigUint,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    q: BigUint,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::biguint_hex"))]
+    g: BigUint,
+}
+
+impl DhParams {
+    /// Checks `p` and `g` with [`validate_params`].
+    pub fn new(p: BigUint, g: BigUint) -> Result<Self, ShamirError> {
+        validate_params(&p, &g)?;
+        let q = (&p - 1u32) >> 1;
+        Ok(DhParams { p, q, g })
+    }
+
+    pub fn p(&self) -> &BigUint {
+        &self.p
+    }
+
+    /// The order of `g`, `(p - 1) / 2`.
+    pub fn q(&self) -> &BigUint {
+        &self.q
+    }
+
+    pub fn g(&self) -> &BigUint {
+        &self.g
+    }
+}
+
+/// Generates a group with a random safe prime `p` of exactly `bits` bits.
+///
+/// The prime comes from [`generate_safe_prime`]. The generator is 2 when
+/// it lies in the order-`q` subgroup, which is when `p = 7 mod 8`, and
+/// otherwise 4, which always does. Fails for `bits < 4`, as the 3-bit safe
+/// prime 5 has no subgroup of odd prime order.
+#[tracing::instrument(level = "debug", skip(rng))]
+pub fn generate_params<R: RngCore + CryptoRng + ?Sized>(
+    bits: usize,
+    rng: &mut R,
+) -> Result<DhParams, ShamirError> {
+    if bits < 4 {
+        return Err(ShamirError::InvalidPrimeSize(bits));
+    }
+    tracing::debug!("generating {bits}-bit Diffie-Hellman parameters");
+    let p = generate_safe_prime(bits, rng)?;
+    let g = if &p % 8u32 == BigUint::from(7u32) {
+        BigUint::from(2u32)
+    } else {
+        BigUint::from(4u32)
+    };
+    DhParams::new(p, g)
+}
+
+/// Checks externally supplied group parameters: `p` must be a safe prime
+/// and `g` must generate its subgroup of order `q = (p - 1) / 2`.
+///
+/// Both `p` and `q` are tested with
+/// [`is_baillie_psw_prime`](crate::primes::is_baillie_psw_prime), which
+/// needs no randomness and has no known counterexample. The size of `p` is
+/// left to the caller.
+pub fn validate_params(p: &BigUint, g: &BigUint) -> Result<(), ShamirError> {
+    let one = BigUint::one();
+    if !is_baillie_psw_prime(p) {
+        return Err(ShamirError::InvalidGroup("p is not prime"));
+    }
+    let q = (p - &one) >> 1;
+    if !is_baillie_psw_prime(&q) {
+        return Err(ShamirError::InvalidGroup("p is not a safe prime"));
+    }
+    // The order of g divides 2q, so it is q exactly when g is neither 1 nor
+    // p - 1 and g^q = 1.
+    if *g <= one || *g >= p - &one || modpow(g, &q, p) != one {
+        return Err(ShamirError::InvalidGroup("generator is not of order q"));
+    }
+    Ok(())
+}
@@ -0,0 +1,281 @@
+//! Dealer-free distributed key generation.
+//!
+//! Every participant deals a Pedersen sharing of a random value of its own;
+//! each participant's final share is the sum of the shares it received from
+//! the qualified dealers. The joint secret is the sum of all dealt values and
+//! is never held by anyone. [`DkgParticipant`] only tracks protocol state, so
+//! embedders can carry the messages over any transport: commitment messages
+//! must be broadcast, share messages sent privately to their recipient.
+
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+
+use num_bigint::{BigUint, RandBigInt};
+use num_traits::Zero;
+use rand::{CryptoRng, RngCore};
+use sha3::{Digest, Sha3_256};
+
+use crate::{
+    pedersen_split_shares, zeroize_biguint, Fingerprint, PedersenCommitments, PedersenParams,
+    ShamirError, Share, ShareSetId,
+};
+
+/// Broadcast message carrying a dealer's commitments.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DkgCommitmentMessage {
+    pub from: usize,
+    pub commitments: PedersenCommitments,
+}
+
+/// Private message carrying the share a dealer computed for `to`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DkgShareMessage {
+    pub from: usize,
+    pub to: usize,
+    pub share: Share,
+}
+
+/// Messages a participant sends after dealing.
+#[derive(Debug, Clone)]
+pub struct DkgRound1 {
+    pub broadcast: DkgCommitmentMessage,
+    pub shares: Vec<DkgShareMessage>,
+}
+
+/// Result of a completed key generation.
+#[derive(Debug, Clone)]
+pub struct DkgOutput {
+    /// This participant's share of the joint secret, at `x = index`.
+    pub share: Share,
+    /// Commitments to the joint polynomial, for verifying any final share.
+    pub commitments: PedersenCommitments,
+    /// Dealers whose contributions make up the joint secret.
+    pub qualified: Vec<usize>,
+}
+
+/// One participant's view of a DKG run.
+///
+/// Participants are numbered `1..=participants`; a participant's index is
+/// also its share's x-coordinate. Drive it with [`DkgParticipant::deal`],
+/// feed every incoming message to the `receive_*` methods and call
+/// [`DkgParticipant::finish`] once all qualified dealers have been heard
+/// from. Dealers whose shares fail verification are disqualified locally;
+/// complaints seen from other participants must be applied with
+/// [`DkgParticipant::disqualify`] so all honest parties agree on the set.
+#[derive(Debug)]
+pub struct DkgParticipant {
+    index: usize,
+    threshold: usize,
+    participants: usize,
+    params: PedersenParams,
+    commitments: BTreeMap<usize, PedersenCommitments>,
+    shares: BTreeMap<usize, Share>,
+    disqualified: Vec<usize>,
+    dealt: bool,
+}
+
+impl DkgParticipant {
+    pub fn new(
+        index: usize,
+        threshold: usize,
+        participants: usize,
+        params: PedersenParams,
+    ) -> Result<Self, ShamirError> {
+        if threshold < 2 || participants < threshold {
+            return Err(ShamirError::InvalidThreshold {
+                threshold,
+                shares: participants,
+            });
+        }
+        if index == 0 || index > participants {
+            return Err(ShamirError::InvalidParticipant(BigUint::from(index)));
+        }
+        Ok(DkgParticipant {
+            index,
+            threshold,
+            participants,
+            params,
+            commitments: BTreeMap::new(),
+            shares: BTreeMap::new(),
+            disqualified: Vec::new(),
+            dealt: false,
+        })
+    }
+
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Dealers disqualified so far.
+    pub fn disqualified(&self) -> &[usize] {
+        &self.disqualified
+    }
+
+    /// Deals a sharing of a fresh random value to every participant,
+    /// keeping this participant's own share.
+    pub fn deal<R: RngCore + CryptoRng + ?Sized>(
+        &mut self,
+        rng: &mut R,
+    ) -> Result<DkgRound1, ShamirError> {
+        if self.dealt {
+            return Err(ShamirError::DuplicateMessage(self.index));
+        }
+        let mut contribution = rng.gen_biguint_below(self.params.q());
+        let dealt = pedersen_split_shares(
+            &contribution,
+            self.threshold,
+            self.participants,
+            &self.params,
+            rng,
+        );
+        zeroize_biguint(&mut contribution);
+        let (shares, commitments) = dealt?;
+        self.dealt = true;
+
+        let mut messages = Vec::with_capacity(self.participants - 1);
+        // The dealt shares sit at x = 1, 2, ..., one per participant.
+        for (to, share) in (1..).zip(shares) {
+            if to == self.index {
+                self.shares.insert(self.index, share);
+            } else {
+                messages.push(DkgShareMessage {
+                    from: self.index,
+                    to,
+                    share,
+                });
+            }
+        }
+        self.commitments.insert(self.index, commitments.clone());
+        Ok(DkgRound1 {
+            broadcast: DkgCommitmentMessage {
+                from: self.index,
+                commitments,
+            },
+            shares: messages,
+        })
+    }
+
+    pub fn receive_commitments(
+        &mut self,
+        message: DkgCommitmentMessage,
+    ) -> Result<(), ShamirError> {
+        self.check_sender(message.from)?;
+        if message.commitments.commitments().len() != self.threshold {
+            self.disqualify(message.from);
+            return Err(ShamirError::ThresholdMismatch);
+        }
+        if self.commitments.contains_key(&message.from) {
+            return Err(ShamirError::DuplicateMessage(message.from));
+        }
+        self.commitments.insert(message.from, message.commitments);
+        self.verify_pending(message.from)
+    }
+
+    /// Accepts a share addressed to this participant. It is verified as soon
+    /// as the sender's commitments are known; a failing sender is
+    /// disqualified and reported as [`ShamirError::CommitmentMismatch`].
+    pub fn receive_share(&mut self, message: DkgShareMessage) -> Result<(), ShamirError> {
+        self.check_sender(message.from)?;
+        if message.to != self.index || *message.share.x() != BigUint::from(self.index) {
+            return Err(ShamirError::InvalidParticipant(BigUint::from(message.to)));
+        }
+        if self.shares.contains_key(&message.from) {
+            return Err(ShamirError::DuplicateMessage(message.from));
+        }
+        self.shares.insert(message.from, message.share);
+        self.verify_pending(message.from)
+    }
+
+    /// Excludes `dealer` from the joint secret.
+    pub fn disqualify(&mut self, dealer: usize) {
+        if !self.disqualified.contains(&dealer) {
+            self.disqualified.push(dealer);
+            self.disqualified.sort_unstable();
+        }
+    }
+
+    /// Sums the shares of all qualified dealers into this participant's
+    /// share of the joint secret.
+    ///
+    /// Fails with [`ShamirError::NotEnoughShares`] when fewer than
+    /// `threshold` dealers remain qualified: the joint secret is the sum of
+    /// the qualified dealers' secrets, so with fewer of them a coalition
+    /// below the threshold, or a lone surviving dealer, would know it.
+    pub fn finish(self) -> Result<DkgOutput, ShamirError> {
+        let qualified: Vec<usize> = (1..=self.participants)
+            .filter(|dealer| !self.disqualified.contains(dealer))
+            .collect();
+        if qualified.len() < self.threshold {
+            return Err(ShamirError::NotEnoughShares {
+                required: self.threshold,
+                provided: qualified.len(),
+            });
+        }
+        let q = self.params.q();
+        let mut y = BigUint::zero();
+        let mut blinding = BigUint::zero();
+        let mut sets = Vec::with_capacity(qualified.len());
+        for dealer in &qualified {
+            let (share, commitments) = match (self.shares.get(dealer), self.commitments.get(dealer))
+            {
+                (Some(share), Some(commitments)) => (share, commitments),
+                _ => return Err(ShamirError::MissingDkgMessage(*dealer)),
+            };
+            y = (y + share.y()) % q;
+            if let Some(b) = share.blinding() {
+                blinding = (blinding + b) % q;
+            }
+            sets.push(commitments);
+        }
+        let commitments = PedersenCommitments::aggregate(sets, &self.params)?;
+        let share = Share::new(
+            BigUint::from(self.index),
+            y,
+            self.threshold,
+            Fingerprint::of(q),
+        )
+        .with_blinding(blinding)
+        .with_set_id(set_id(&commitments));
+        commitments.verify(&share, &self.params)?;
+        Ok(DkgOutput {
+            share,
+            commitments,
+            qualified,
+        })
+    }
+
+    fn check_sender(&self, from: usize) -> Result<(), ShamirError> {
+        if from == 0 || from > self.participants || from == self.index {
+            return Err(ShamirError::InvalidParticipant(BigUint::from(from)));
+        }
+        Ok(())
+    }
+
+    fn verify_pending(&mut self, dealer: usize) -> Result<(), ShamirError> {
+        let (Some(share), Some(commitments)) =
+            (self.shares.get(&dealer), self.commitments.get(&dealer))
+        else {
+            return Ok(());
+        };
+        let result = commitments.verify(share, &self.params);
+        if result.is_err() {
+            self.disqualify(dealer);
+        }
+        result
+    }
+}
+
+/// The set id of a DKG's output shares, hashed from the joint commitments,
+/// which every participant that agrees on the qualified dealers holds.
+fn set_id(commitments: &PedersenCommitments) -> ShareSetId {
+    let mut hasher = Sha3_256::new();
+    hasher.update(b"prime-shamir/dkg-set-id/v1");
+    for commitment in commitments.commitments() {
+        let bytes = commitment.to_bytes_be();
+        hasher.update((bytes.len() as u64).to_be_bytes());
+        hasher.update(&bytes);
+    }
+    ShareSetId::from_hash(&hasher.finalize())
+}
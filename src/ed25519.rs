@@ -0,0 +1,52 @@
+//! Ed25519 signing keys shared through their 32-byte seed.
+//!
+//! An Ed25519 secret scalar is derived from the seed by hashing and
+//! clamping, so the scalar cannot be turned back into a seed and is not
+//! what `ed25519-dalek` or libsodium import. These helpers therefore share
+//! the seed itself, as an integer in the field of the smallest prime above
+//! `2^256`, and restore exactly the bytes accepted by
+//! `ed25519_dalek::SigningKey::from_bytes`.
+
+use alloc::vec::Vec;
+
+use num_bigint::BigUint;
+use num_traits::One;
+use rand::{CryptoRng, RngCore};
+use zeroize::Zeroize;
+
+use crate::{shamir_reconstruct, shamir_split_shares, zeroize_biguint, ShamirError, Share};
+
+/// `2^256 + 297`, the smallest prime that holds every 32-byte seed.
+fn seed_modulus() -> BigUint {
+    (BigUint::one() << 256u32) + BigUint::from(297u32)
+}
+
+/// Splits an Ed25519 seed (the first half of a libsodium secret key).
+pub fn split_ed25519_seed<R: RngCore + CryptoRng + ?Sized>(
+    seed: &[u8; 32],
+    threshold: usize,
+    shares: usize,
+    rng: &mut R,
+) -> Result<Vec<Share>, ShamirError> {
+    let mut value = BigUint::from_bytes_be(seed);
+    let result = shamir_split_shares(&value, threshold, shares, &seed_modulus(), rng);
+    zeroize_biguint(&mut value);
+    result
+}
+
+/// Recovers the 32-byte Ed25519 seed.
+pub fn reconstruct_ed25519_seed(shares: &[Share]) -> Result<[u8; 32], ShamirError> {
+    let mut value = shamir_reconstruct(shares, &seed_modulus())?;
+    let mut bytes = value.to_bytes_be();
+    zeroize_biguint(&mut value);
+    if bytes.len() > 32 {
+        bytes.zeroize();
+        return Err(ShamirError::InvalidKey(
+            "reconstructed value does not fit an Ed25519 seed",
+        ));
+    }
+    let mut seed = [0u8; 32];
+    seed[32 - bytes.len()..].copy_from_slice(&bytes);
+    bytes.zeroize();
+    Ok(seed)
+}
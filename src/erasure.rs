@@ -0,0 +1,156 @@
+//! Reed–Solomon style erasure coding over the prime field.
+//!
+//! This is the Shamir machinery applied to non-secret data: `k` data symbols
+//! become the values of a degree `k - 1` polynomial at `x = 1..=k`, and
+//! parity fragments are its values at `x = k + 1..=n`. The code is
+//! systematic, so the first `k` fragments hold the data verbatim, and any
+//! `k` of the `n` fragments recover it.
+//!
+//! Encoding draws no randomness, so the fragments' [`ShareSetId`] is hashed
+//! from the data instead: fragments of different data never share one, and
+//! two encodings of the same data are interchangeable anyway.
+
+use alloc::vec::Vec;
+
+use num_bigint::BigUint;
+use num_traits::Zero;
+use sha3::{Digest, Sha3_256};
+
+use crate::polynomial::map_points;
+use crate::shamir::{check_digest_all, check_share_set, lagrange_coefficient_at};
+use crate::{Fingerprint, ShamirError, Share, ShareSetId};
+
+/// A `(k, n)` erasure code. Fragments are [`Share`]s whose threshold is `k`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ErasureCode {
+    data_symbols: usize,
+    fragments: usize,
+    modulus: BigUint,
+}
+
+impl ErasureCode {
+    /// Creates a code encoding `data_symbols` field elements into
+    /// `fragments` fragments. Fragments are shares, whose threshold must be
+    /// at least 2, so `data_symbols` must be too. The modulus must exceed
+    /// `fragments`.
+    pub fn new(
+        data_symbols: usize,
+        fragments: usize,
+        modulus: BigUint,
+    ) -> Result<Self, ShamirError> {
+        if data_symbols < 2 || fragments < data_symbols {
+            return Err(ShamirError::InvalidThreshold {
+                threshold: data_symbols,
+                shares: fragments,
+            });
+        }
+        if modulus <= BigUint::from(fragments as u64) {
+            return Err(ShamirError::ModulusTooSmall {
+                modulus,
+                shares: fragments,
+            });
+        }
+        Ok(ErasureCode {
+            data_symbols,
+            fragments,
+            modulus,
+        })
+    }
+
+    pub fn data_symbols(&self) -> usize {
+        self.data_symbols
+    }
+
+    pub fn fragments(&self) -> usize {
+        self.fragments
+    }
+
+    pub fn modulus(&self) -> &BigUint {
+        &self.modulus
+    }
+
+    /// Encodes exactly [`ErasureCode::data_symbols`] field elements.
+    pub fn encode(&self, data: &[BigUint]) -> Result<Vec<Share>, ShamirError> {
+        if data.len() != self.data_symbols {
+            return Err(ShamirError::NotEnoughShares {
+                required: self.data_symbols,
+                provided: data.len(),
+            });
+        }
+        if data.iter().any(|symbol| *symbol >= self.modulus) {
+            return Err(ShamirError::SecretTooLarge);
+        }
+        let fingerprint = Fingerprint::of(&self.modulus);
+        let set_id = self.set_id(data);
+        let xs: Vec<BigUint> = (1..=self.data_symbols as u64).map(BigUint::from).collect();
+        let mut fragments: Vec<Share> = data
+            .iter()
+            .zip(&xs)
+            .map(|(symbol, x)| {
+                Share::new(x.clone(), symbol.clone(), self.data_symbols, fingerprint)
+                    .with_set_id(set_id)
+            })
+            .collect();
+        let parity: Vec<BigUint> = (self.data_symbols as u64 + 1..=self.fragments as u64)
+            .map(BigUint::from)
+            .collect();
+        let ys = map_points(&parity, |x| self.interpolate(&xs, data, x));
+        for (x, y) in parity.into_iter().zip(ys) {
+            fragments.push(Share::new(x, y?, self.data_symbols, fingerprint).with_set_id(set_id));
+        }
+        Ok(fragments)
+    }
+
+    /// The set id of the fragments of `data`, hashed from the code and the
+    /// data symbols.
+    fn set_id(&self, data: &[BigUint]) -> ShareSetId {
+        let mut hasher = Sha3_256::new();
+        hasher.update(b"prime-shamir/erasure-set-id/v1");
+        hasher.update(Fingerprint::of(&self.modulus).as_bytes());
+        hasher.update((self.fragments as u64).to_be_bytes());
+        for symbol in data {
+            let bytes = symbol.to_bytes_be();
+            hasher.update((bytes.len() as u64).to_be_bytes());
+            hasher.update(&bytes);
+        }
+        ShareSetId::from_hash(&hasher.finalize())
+    }
+
+    /// Recovers the data symbols from any [`ErasureCode::data_symbols`]
+    /// fragments; extra fragments are ignored. Fragments carrying a
+    /// [`SecretDigest::compute_all`] digest of the data are checked against
+    /// it.
+    ///
+    /// [`SecretDigest::compute_all`]: crate::SecretDigest::compute_all
+    pub fn decode(&self, fragments: &[Share]) -> Result<Vec<BigUint>, ShamirError> {
+        check_share_set(fragments, &self.modulus)?;
+        if fragments[0].threshold() != self.data_symbols {
+            return Err(ShamirError::ThresholdMismatch);
+        }
+        let used = &fragments[..self.data_symbols];
+        let xs: Vec<BigUint> = used.iter().map(|fragment| fragment.x().clone()).collect();
+        let ys: Vec<BigUint> = used.iter().map(|fragment| fragment.y().clone()).collect();
+        let data = (1..=self.data_symbols as u64)
+            .map(BigUint::from)
+            .map(|x| match used.iter().find(|fragment| *fragment.x() == x) {
+                Some(fragment) => Ok(fragment.y().clone()),
+                None => self.interpolate(&xs, &ys, &x),
+            })
+            .collect::<Result<_, _>>()?;
+        check_digest_all(fragments, data)
+    }
+
+    fn interpolate(
+        &self,
+        xs: &[BigUint],
+        ys: &[BigUint],
+        at: &BigUint,
+    ) -> Result<BigUint, ShamirError> {
+        let mut y = BigUint::zero();
+        for (i, value) in ys.iter().enumerate() {
+            y += lagrange_coefficient_at(xs, i, at, &self.modulus)? * value;
+            y %= &self.modulus;
+        }
+        Ok(y)
+    }
+}
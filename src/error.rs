@@ -0,0 +1,87 @@
+use num_bigint::BigUint;
+use thiserror::Error;
+
+/// Errors returned by the splitting, reconstruction and prime generation APIs.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ShamirError {
+    #[error("invalid threshold {threshold} for {shares} shares (need 2 <= threshold <= shares)")]
+    InvalidThreshold { threshold: usize, shares: usize },
+    #[error("missing scheme parameter: {0}")]
+    MissingParameter(&'static str),
+    #[error("secret must be smaller than the modulus")]
+    SecretTooLarge,
+    #[error("modulus {modulus} is too small for {shares} shares")]
+    ModulusTooSmall { modulus: BigUint, shares: usize },
+    #[error("invalid modulus: {0}")]
+    InvalidModulus(&'static str),
+    #[error("cannot generate a prime of {0} bits")]
+    InvalidPrimeSize(usize),
+    #[error("no prime found within {0} candidates")]
+    SearchExhausted(u64),
+    #[error("prime search was cancelled")]
+    Cancelled,
+    #[error("prime search timed out")]
+    TimedOut,
+    #[error("invalid primality certificate: {0}")]
+    InvalidCertificate(&'static str),
+    #[error("duplicate share x-coordinate {0}")]
+    DuplicateShareX(BigUint),
+    #[error("share x-coordinate {0} is zero or not below the modulus")]
+    InvalidShareX(BigUint),
+    #[error("not enough shares: need {required}, got {provided}")]
+    NotEnoughShares { required: usize, provided: usize },
+    #[error("shares were produced under a different modulus")]
+    ModulusMismatch,
+    #[error("shares come from different share sets")]
+    SetMismatch,
+    #[error("sub-shares do not come from distinct dealers")]
+    DuplicateDealer,
+    #[error("participant {0} is supplied more than once")]
+    DuplicateParticipant(usize),
+    #[error("shares disagree on the threshold")]
+    ThresholdMismatch,
+    #[error("shares do not lie at the x-coordinates of the Lagrange basis")]
+    BasisMismatch,
+    #[error("malformed share: {0}")]
+    MalformedShare(&'static str),
+    #[error("invalid group parameters: {0}")]
+    InvalidGroup(&'static str),
+    #[error("share at x = {0} has no blinding value")]
+    MissingBlinding(BigUint),
+    #[error("share at x = {0} does not match the published commitments")]
+    CommitmentMismatch(BigUint),
+    #[error("unknown or misaddressed participant {0}")]
+    InvalidParticipant(BigUint),
+    #[error("duplicate message from participant {0}")]
+    DuplicateMessage(usize),
+    #[error("no message received from participant {0}")]
+    MissingDkgMessage(usize),
+    #[error("shares are inconsistent and no agreeing majority could be found")]
+    InconsistentShares,
+    #[error("invalid access structure: {0}")]
+    InvalidAccessStructure(&'static str),
+    #[error("shares do not satisfy the access structure")]
+    Unauthorized,
+    #[error("interpolation system is singular for these shares")]
+    SingularSystem,
+    #[error("shares at x = {0} and x = {1} differ by a value not invertible modulo the field")]
+    NonInvertibleDenominator(BigUint, BigUint),
+    #[error("invalid key: {0}")]
+    InvalidKey(&'static str),
+    #[error("reconstructed secret does not match its recorded digest")]
+    IntegrityFailure,
+    #[error("share at x = {0} has no MAC")]
+    MissingMac(BigUint),
+    #[error("share at x = {0} does not match its MAC")]
+    MacMismatch(BigUint),
+    #[error("share at x = {0} is not signed by the dealer")]
+    MissingSignature(BigUint),
+    #[error("share at x = {0} was signed by a different dealer key")]
+    UnknownDealer(BigUint),
+    #[error("dealer signature on the share at x = {0} is invalid")]
+    SignatureMismatch(BigUint),
+    #[error("wrong passphrase for the share at x = {0}, or the share was modified")]
+    WrongPassphrase(BigUint),
+    #[error("cannot encode as a QR code: {0}")]
+    QrCode(&'static str),
+}
@@ -0,0 +1,273 @@
+//! Sharing over small fields through a common [`Field`] trait.
+//!
+//! The prime-field API works on `BigUint`s with a runtime modulus; the
+//! types here are fixed at compile time and copyable, which suits
+//! byte-oriented and fixed-width secrets. [`Gf256`](crate::Gf256) and every
+//! [`Gf2n`] implement [`Field`], and [`field_split`]/[`field_reconstruct`]
+//! share values of any of them.
+
+use alloc::vec::Vec;
+use core::fmt;
+use core::ops::{Add, Mul, Sub};
+
+use num_bigint::BigUint;
+use rand::{CryptoRng, RngCore};
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+use crate::ShamirError;
+
+/// A finite field usable as a sharing backend.
+pub trait Field:
+    Copy + Eq + fmt::Debug + Zeroize + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+    /// Bits per element; x-coordinates must stay below `2^BITS`.
+    const BITS: u32;
+
+    /// Multiplicative inverse; zero maps to zero.
+    fn inverse(self) -> Self;
+
+    /// The element whose bit pattern is `index`, used for x-coordinates.
+    fn from_index(index: u64) -> Self;
+
+    fn random<R: RngCore + CryptoRng + ?Sized>(rng: &mut R) -> Self;
+}
+
+/// An element of GF(2^BITS) defined by `x^BITS + POLY`, `BITS <= 128`.
+///
+/// `POLY` holds the low terms of the reduction polynomial, e.g. `0x87` for
+/// `x^128 + x^7 + x^2 + x + 1`; the polynomial must be irreducible. Elements use the natural bit order
+/// (bit `i` is the coefficient of `x^i`), not GHASH's reflected one.
+/// Multiplication is a fixed `BITS`-step shift-and-add.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Gf2n<const BITS: u32, const POLY: u128>(u128);
+
+/// GF(2^128) with the AES-GCM polynomial `x^128 + x^7 + x^2 + x + 1`.
+pub type Gf2_128 = Gf2n<128, 0x87>;
+
+/// GF(2^64) with the polynomial `x^64 + x^4 + x^3 + x + 1`.
+pub type Gf2_64 = Gf2n<64, 0x1b>;
+
+impl<const BITS: u32, const POLY: u128> Gf2n<BITS, POLY> {
+    const MASK: u128 = if BITS >= 128 {
+        u128::MAX
+    } else {
+        (1 << BITS) - 1
+    };
+
+    /// The element with the given bit pattern, truncated to `BITS` bits.
+    pub fn new(bits: u128) -> Self {
+        Gf2n(bits & Self::MASK)
+    }
+
+    pub fn bits(self) -> u128 {
+        self.0
+    }
+}
+
+impl<const BITS: u32, const POLY: u128> Add for Gf2n<BITS, POLY> {
+    type Output = Self;
+
+    // Addition in characteristic 2 is XOR.
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn add(self, other: Self) -> Self {
+        Gf2n(self.0 ^ other.0)
+    }
+}
+
+impl<const BITS: u32, const POLY: u128> Sub for Gf2n<BITS, POLY> {
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn sub(self, other: Self) -> Self {
+        self + other
+    }
+}
+
+impl<const BITS: u32, const POLY: u128> Mul for Gf2n<BITS, POLY> {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        let mut a = self.0;
+        let mut b = other.0;
+        let mut product = 0u128;
+        for _ in 0..BITS {
+            product ^= a & 0u128.wrapping_sub(b & 1);
+            let carry = 0u128.wrapping_sub((a >> (BITS - 1)) & 1);
+            a = ((a << 1) & Self::MASK) ^ (carry & POLY);
+            b >>= 1;
+        }
+        Gf2n(product)
+    }
+}
+
+impl<const BITS: u32, const POLY: u128> Zeroize for Gf2n<BITS, POLY> {
+    fn zeroize(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl<const BITS: u32, const POLY: u128> Field for Gf2n<BITS, POLY> {
+    const ZERO: Self = Gf2n(0);
+    const ONE: Self = Gf2n(1);
+    const BITS: u32 = BITS;
+
+    /// `a^(2^BITS - 2)`, by squaring through every exponent bit.
+    fn inverse(self) -> Self {
+        let mut result = Self::ONE;
+        for bit in (0..BITS).rev() {
+            result = result * result;
+            if bit != 0 {
+                result = result * self;
+            }
+        }
+        result
+    }
+
+    fn from_index(index: u64) -> Self {
+        Gf2n::new(index as u128)
+    }
+
+    fn random<R: RngCore + CryptoRng + ?Sized>(rng: &mut R) -> Self {
+        let mut bytes = [0u8; 16];
+        rng.fill_bytes(&mut bytes);
+        let element = Gf2n::new(u128::from_le_bytes(bytes));
+        bytes.zeroize();
+        element
+    }
+}
+
+/// One holder's share of a vector of field elements.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldShare<F: Field> {
+    x: u64,
+    threshold: usize,
+    values: Vec<F>,
+}
+
+impl<F: Field> FieldShare<F> {
+    pub fn new(x: u64, threshold: usize, values: Vec<F>) -> Self {
+        FieldShare {
+            x,
+            threshold,
+            values,
+        }
+    }
+
+    pub fn x(&self) -> u64 {
+        self.x
+    }
+
+    pub fn threshold(&self) -> usize {
+        self.threshold
+    }
+
+    pub fn values(&self) -> &[F] {
+        &self.values
+    }
+}
+
+impl<F: Field> Zeroize for FieldShare<F> {
+    fn zeroize(&mut self) {
+        self.values.iter_mut().for_each(Zeroize::zeroize);
+    }
+}
+
+impl<F: Field> Drop for FieldShare<F> {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl<F: Field> ZeroizeOnDrop for FieldShare<F> {}
+
+/// Splits each element of `secret` with its own polynomial over `F` into
+/// `shares` shares with x-coordinates `1..=shares`.
+pub fn field_split<F: Field, R: RngCore + CryptoRng + ?Sized>(
+    secret: &[F],
+    threshold: usize,
+    shares: usize,
+    rng: &mut R,
+) -> Result<Vec<FieldShare<F>>, ShamirError> {
+    if threshold < 2 || shares < threshold {
+        return Err(ShamirError::InvalidThreshold { threshold, shares });
+    }
+    if F::BITS < 64 && shares as u64 >= 1 << F::BITS {
+        return Err(ShamirError::InvalidModulus(
+            "too many shares for the field size",
+        ));
+    }
+    let mut result: Vec<FieldShare<F>> = (1..=shares as u64)
+        .map(|x| FieldShare::new(x, threshold, Vec::with_capacity(secret.len())))
+        .collect();
+    let mut coefficients = Vec::with_capacity(threshold);
+    for &value in secret {
+        coefficients.clear();
+        coefficients.push(value);
+        coefficients.extend((1..threshold).map(|_| F::random(rng)));
+        for share in &mut result {
+            let x = F::from_index(share.x);
+            let y = coefficients
+                .iter()
+                .rev()
+                .fold(F::ZERO, |acc, &coeff| acc * x + coeff);
+            share.values.push(y);
+        }
+        coefficients.iter_mut().for_each(Zeroize::zeroize);
+    }
+    Ok(result)
+}
+
+/// Recovers the shared elements from at least `threshold` shares.
+pub fn field_reconstruct<F: Field>(shares: &[FieldShare<F>]) -> Result<Vec<F>, ShamirError> {
+    let first = shares.first().ok_or(ShamirError::NotEnoughShares {
+        required: 1,
+        provided: 0,
+    })?;
+    for (i, share) in shares.iter().enumerate() {
+        if share.threshold != first.threshold {
+            return Err(ShamirError::ThresholdMismatch);
+        }
+        if share.values.len() != first.values.len() {
+            return Err(ShamirError::MalformedShare("share lengths differ"));
+        }
+        if share.x == 0 {
+            return Err(ShamirError::MalformedShare("x must be nonzero"));
+        }
+        if F::BITS < 64 && share.x >> F::BITS != 0 {
+            return Err(ShamirError::MalformedShare("x outside the field"));
+        }
+        if shares[..i].iter().any(|other| other.x == share.x) {
+            return Err(ShamirError::DuplicateShareX(BigUint::from(share.x)));
+        }
+    }
+    if shares.len() < first.threshold {
+        return Err(ShamirError::NotEnoughShares {
+            required: first.threshold,
+            provided: shares.len(),
+        });
+    }
+    let used = &shares[..first.threshold];
+    let coefficients: Vec<F> = used
+        .iter()
+        .map(|share| {
+            let xi = F::from_index(share.x);
+            used.iter()
+                .filter(|other| other.x != share.x)
+                .fold(F::ONE, |acc, other| {
+                    let xj = F::from_index(other.x);
+                    acc * xj * (xj - xi).inverse()
+                })
+        })
+        .collect();
+    Ok((0..first.values.len())
+        .map(|i| {
+            used.iter()
+                .zip(&coefficients)
+                .fold(F::ZERO, |acc, (share, &coeff)| {
+                    acc + coeff * share.values[i]
+                })
+        })
+        .collect())
+}
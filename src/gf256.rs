@@ -0,0 +1,219 @@
+//! Byte-oriented sharing over GF(2^8).
+//!
+//! Each byte of the secret is shared independently with a polynomial over
+//! GF(2^8) using the AES reduction polynomial `x^8 + x^4 + x^3 + x + 1`, as
+//! in `ssss` and libgfshare. Shares are exactly as long as the secret and
+//! need no big-integer arithmetic, at the cost of at most 255 shares.
+//! Multiplication is a fixed-length shift-and-add, so timing does not
+//! depend on the share bytes.
+
+use alloc::vec::Vec;
+use core::fmt;
+use core::ops::{Add, Mul, Sub};
+use core::str::FromStr;
+
+use num_bigint::BigUint;
+use rand::{CryptoRng, RngCore};
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+use crate::field::{field_reconstruct, field_split, Field, FieldShare};
+use crate::ShamirError;
+
+/// An element of GF(2^8).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Gf256(pub u8);
+
+impl Add for Gf256 {
+    type Output = Gf256;
+
+    // Addition in characteristic 2 is XOR.
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn add(self, other: Gf256) -> Gf256 {
+        Gf256(self.0 ^ other.0)
+    }
+}
+
+impl Mul for Gf256 {
+    type Output = Gf256;
+
+    fn mul(self, other: Gf256) -> Gf256 {
+        let mut a = self.0;
+        let mut b = other.0;
+        let mut product = 0u8;
+        for _ in 0..8 {
+            product ^= a & 0u8.wrapping_sub(b & 1);
+            let carry = 0u8.wrapping_sub(a >> 7);
+            a = (a << 1) ^ (carry & 0x1b);
+            b >>= 1;
+        }
+        Gf256(product)
+    }
+}
+
+impl Sub for Gf256 {
+    type Output = Gf256;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn sub(self, other: Gf256) -> Gf256 {
+        self + other
+    }
+}
+
+impl Zeroize for Gf256 {
+    fn zeroize(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl Field for Gf256 {
+    const ZERO: Gf256 = Gf256(0);
+    const ONE: Gf256 = Gf256(1);
+    const BITS: u32 = 8;
+
+    /// `a^254`, by fixed square-and-multiply.
+    fn inverse(self) -> Gf256 {
+        let mut result = Gf256::ONE;
+        let mut base = self;
+        let mut exponent = 254u8;
+        while exponent > 0 {
+            if exponent & 1 == 1 {
+                result = result * base;
+            }
+            base = base * base;
+            exponent >>= 1;
+        }
+        result
+    }
+
+    fn from_index(index: u64) -> Gf256 {
+        Gf256(index as u8)
+    }
+
+    fn random<R: RngCore + CryptoRng + ?Sized>(rng: &mut R) -> Gf256 {
+        let mut byte = [0u8; 1];
+        rng.fill_bytes(&mut byte);
+        Gf256(byte[0])
+    }
+}
+
+/// One holder's share of a byte string split over GF(2^8).
+///
+/// Written as `x-threshold-data` with `data` in hex.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(try_from = "crate::serde_support::ByteShareRepr")
+)]
+pub struct ByteShare {
+    x: u8,
+    threshold: usize,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::bytes_hex"))]
+    data: Vec<u8>,
+}
+
+impl ByteShare {
+    pub fn new(x: u8, threshold: usize, data: Vec<u8>) -> Self {
+        ByteShare { x, threshold, data }
+    }
+
+    pub fn x(&self) -> u8 {
+        self.x
+    }
+
+    pub fn threshold(&self) -> usize {
+        self.threshold
+    }
+
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+}
+
+impl Zeroize for ByteShare {
+    fn zeroize(&mut self) {
+        self.data.zeroize();
+    }
+}
+
+impl Drop for ByteShare {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl ZeroizeOnDrop for ByteShare {}
+
+impl fmt::Display for ByteShare {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{}-", self.x, self.threshold)?;
+        self.data
+            .iter()
+            .try_for_each(|byte| write!(f, "{:02x}", byte))
+    }
+}
+
+impl FromStr for ByteShare {
+    type Err = ShamirError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.trim().splitn(3, '-');
+        let mut next = |what: &'static str| parts.next().ok_or(ShamirError::MalformedShare(what));
+        let x: u8 = next("missing x")?
+            .parse()
+            .map_err(|_| ShamirError::MalformedShare("invalid x"))?;
+        let threshold = next("missing threshold")?
+            .parse()
+            .map_err(|_| ShamirError::MalformedShare("invalid threshold"))?;
+        let data = hex::decode(next("missing data")?)
+            .map_err(|_| ShamirError::MalformedShare("invalid data"))?;
+        if x == 0 {
+            return Err(ShamirError::MalformedShare("x must be nonzero"));
+        }
+        Ok(ByteShare::new(x, threshold, data))
+    }
+}
+
+/// Splits `secret` byte by byte over GF(2^8) into `shares` shares with
+/// x-coordinates `1..=shares`. Requires `2 <= threshold <= shares <= 255`.
+pub fn gf256_split<R: RngCore + CryptoRng + ?Sized>(
+    secret: &[u8],
+    threshold: usize,
+    shares: usize,
+    rng: &mut R,
+) -> Result<Vec<ByteShare>, ShamirError> {
+    if threshold < 2 || shares < threshold {
+        return Err(ShamirError::InvalidThreshold { threshold, shares });
+    }
+    if shares > 255 {
+        return Err(ShamirError::ModulusTooSmall {
+            modulus: BigUint::from(256u32),
+            shares,
+        });
+    }
+    let mut bytes: Vec<Gf256> = secret.iter().map(|&byte| Gf256(byte)).collect();
+    let shares = field_split(&bytes, threshold, shares, rng);
+    bytes.zeroize();
+    Ok(shares?
+        .iter()
+        .map(|share| {
+            let data = share.values().iter().map(|value| value.0).collect();
+            ByteShare::new(share.x() as u8, share.threshold(), data)
+        })
+        .collect())
+}
+
+/// Recovers the byte string from at least `threshold` shares.
+pub fn gf256_reconstruct(shares: &[ByteShare]) -> Result<Vec<u8>, ShamirError> {
+    let shares: Vec<FieldShare<Gf256>> = shares
+        .iter()
+        .map(|share| {
+            let values = share.data.iter().map(|&byte| Gf256(byte)).collect();
+            FieldShare::new(share.x as u64, share.threshold, values)
+        })
+        .collect();
+    let mut values = field_reconstruct(&shares)?;
+    let secret = values.iter().map(|value| value.0).collect();
+    values.zeroize();
+    Ok(secret)
+}
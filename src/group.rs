@@ -0,0 +1,137 @@
+//! Prime-order subgroups of `Z_p^*` for DSA, Schnorr signatures and
+//! discrete-log commitments.
+//!
+//! [`generate`] follows the shape of FIPS 186-4 Appendix A.1.1.2: a prime
+//! `q` is chosen first, then up to `4 * p_bits` random `p_bits`-bit values
+//! are rounded down to `p = 1 mod 2q` and tested for primality, and a fresh
+//! `q` is drawn if none is prime. The seeded hash the standard derives its
+//! candidates from is replaced by the caller's RNG, so the result cannot be
+//! re-derived and audited from a seed. The generator follows Appendix
+//! A.2.1, raising `h = 2, 3, ...` to the cofactor `(p - 1) / q`.
+
+use num_bigint::{BigUint, RandBigInt};
+use num_traits::{One, Zero};
+use rand::{CryptoRng, RngCore};
+
+use crate::bigint::modpow;
+use crate::primes::{generate_large_prime, is_baillie_psw_prime, is_probably_prime};
+use crate::{PrimalityConfig, ShamirError};
+
+/// A prime `p`, a prime `q` dividing `p - 1` and a generator `g` of the
+/// order-`q` subgroup, checked when constructed or deserialized.
+///
+/// The group order `q` is the field that shares live in when the group
+/// carries Feldman or Pedersen commitments. A second generator for the
+/// latter can be derived with
+/// [`PedersenParams::derive`](crate::PedersenParams::derive).
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(try_from = "crate::serde_support::GroupParamsRepr")
+)]
+pub struct GroupParams {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::biguint_hex"))]
+    p: BigUint,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::biguint_hex"))]
+    q: BigUint,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::biguint_hex"))]
+    g: BigUint,
+}
+
+impl GroupParams {
+    /// Checks that `p` and `q` are prime, as decided by
+    /// [`is_baillie_psw_prime`](crate::primes::is_baillie_psw_prime), that
+    /// `q` divides `p - 1` and that `g` has order `q`.
+    pub fn new(p: BigUint, q: BigUint, g: BigUint) -> Result<Self, ShamirError> {
+        let one = BigUint::one();
+        if q <= one || p <= q {
+            return Err(ShamirError::InvalidGroup("need 1 < q < p"));
+        }
+        if !is_baillie_psw_prime(&p) || !is_baillie_psw_prime(&q) {
+            return Err(ShamirError::InvalidGroup("p and q must be prime"));
+        }
+        if !((&p - &one) % &q).is_zero() {
+            return Err(ShamirError::InvalidGroup("q does not divide p - 1"));
+        }
+        // q is prime, so any g other than 1 with g^q = 1 has order q.
+        if g <= one || g >= p || modpow(&g, &q, &p) != one {
+            return Err(ShamirError::InvalidGroup("generator is not of order q"));
+        }
+        Ok(GroupParams { p, q, g })
+    }
+
+    pub fn p(&self) -> &BigUint {
+        &self.p
+    }
+
+    /// The order of `g`.
+    pub fn q(&self) -> &BigUint {
+        &self.q
+    }
+
+    pub fn g(&self) -> &BigUint {
+        &self.g
+    }
+}
+
+/// Generates a group with a `p_bits`-bit prime `p` and a `q_bits`-bit prime
+/// `q` dividing `p - 1`, such as the FIPS 186-4 DSA sizes (2048, 256) and
+/// (3072, 256).
+///
+/// `q` comes from [`generate_large_prime`], redrawn until it has exactly
+/// `q_bits` bits, and candidates for `p` must pass Baillie–PSW and the
+/// Miller–Rabin rounds of the default [`PrimalityConfig`]. Fails for
+/// `q_bits < 2` or `p_bits <= q_bits`.
+#[tracing::instrument(level = "debug", skip(rng))]
+pub fn generate<R: RngCore + CryptoRng + ?Sized>(
+    p_bits: usize,
+    q_bits: usize,
+    rng: &mut R,
+) -> Result<GroupParams, ShamirError> {
+    if q_bits < 2 {
+        return Err(ShamirError::InvalidPrimeSize(q_bits));
+    }
+    if p_bits <= q_bits {
+        return Err(ShamirError::InvalidPrimeSize(p_bits));
+    }
+    tracing::debug!("generating a group with {p_bits}-bit p and {q_bits}-bit q");
+    let config = PrimalityConfig::default();
+    let low = BigUint::one() << (p_bits - 1);
+    loop {
+        let q = loop {
+            let q = generate_large_prime(q_bits, rng)?;
+            if q.bits() == q_bits as u64 {
+                break q;
+            }
+        };
+        let step = &q << 1;
+        for _ in 0..4 * p_bits {
+            let x = rng.gen_biguint(p_bits as u64 - 1) | &low;
+            let p = &x - (&x % &step) + 1u32;
+            if p < low {
+                continue;
+            }
+            if is_baillie_psw_prime(&p) && is_probably_prime(&p, config.rounds_for(p.bits()), rng) {
+                let g = generator(&p, &q);
+                tracing::debug!("found a group with {p_bits}-bit p");
+                return GroupParams::new(p, q, g);
+            }
+        }
+        tracing::debug!("no prime p for this q, drawing another");
+    }
+}
+
+/// The first `h^((p - 1) / q) mod p` other than 1 for `h = 2, 3, ...`.
+fn generator(p: &BigUint, q: &BigUint) -> BigUint {
+    let cofactor = (p - 1u32) / q;
+    let one = BigUint::one();
+    let mut h = BigUint::from(2u32);
+    loop {
+        let g = modpow(&h, &cofactor, p);
+        if g != one {
+            return g;
+        }
+        h += 1u32;
+    }
+}
@@ -0,0 +1,206 @@
+//! Two-level byte sharing over GF(2^8) with digest shares, as specified by
+//! SLIP-0039 and reused unchanged by SSKR.
+//!
+//! The secret is split among groups and each group secret again among its
+//! members. For thresholds above one, the polynomial passes through the
+//! secret at x = 255 and a digest share at x = 254 whose first four bytes
+//! are an HMAC-SHA256 tag of the secret keyed by the rest, so recovery from
+//! inconsistent shares is detected. At threshold one every share is a copy.
+
+use alloc::collections::BTreeMap;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use hmac::{Hmac, Mac, NewMac};
+use num_bigint::BigUint;
+use rand::{CryptoRng, RngCore};
+use sha2::Sha256;
+use zeroize::Zeroize;
+
+use crate::{Field, Gf256, ShamirError};
+
+const DIGEST_INDEX: u8 = 254;
+const SECRET_INDEX: u8 = 255;
+const DIGEST_LEN: usize = 4;
+
+/// One member share as seen by [`combine_groups`].
+pub(crate) struct GroupMember<'a> {
+    pub(crate) group_index: u8,
+    pub(crate) member_index: u8,
+    pub(crate) member_threshold: u8,
+    pub(crate) value: &'a [u8],
+}
+
+/// Evaluates the polynomial through `points` at `x`, byte by byte.
+fn interpolate(points: &[(u8, &[u8])], x: u8) -> Vec<u8> {
+    if let Some((_, value)) = points.iter().find(|(xi, _)| *xi == x) {
+        return value.to_vec();
+    }
+    let x = Gf256(x);
+    let len = points[0].1.len();
+    let mut result = vec![Gf256::ZERO; len];
+    for (i, &(xi, yi)) in points.iter().enumerate() {
+        let xi = Gf256(xi);
+        let basis = points.iter().enumerate().filter(|&(j, _)| j != i).fold(
+            Gf256::ONE,
+            |acc, (_, &(xj, _))| {
+                let xj = Gf256(xj);
+                acc * (x - xj) * (xi - xj).inverse()
+            },
+        );
+        for (out, &y) in result.iter_mut().zip(yi) {
+            *out = *out + basis * Gf256(y);
+        }
+    }
+    let bytes = result.iter().map(|value| value.0).collect();
+    result.zeroize();
+    bytes
+}
+
+fn hmac_digest(key: &[u8], secret: &[u8]) -> [u8; DIGEST_LEN] {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts all key sizes");
+    mac.update(secret);
+    let tag = mac.finalize().into_bytes();
+    let mut digest = [0u8; DIGEST_LEN];
+    digest.copy_from_slice(&tag[..DIGEST_LEN]);
+    digest
+}
+
+/// Splits `secret` into `count` shares at `threshold`, with the digest
+/// share at index 254 for thresholds above one.
+fn split_secret<R: RngCore + CryptoRng + ?Sized>(
+    threshold: u8,
+    count: u8,
+    secret: &[u8],
+    rng: &mut R,
+) -> Vec<(u8, Vec<u8>)> {
+    if threshold == 1 {
+        return (0..count).map(|i| (i, secret.to_vec())).collect();
+    }
+    let mut shares: Vec<(u8, Vec<u8>)> = (0..threshold - 2)
+        .map(|i| {
+            let mut value = vec![0u8; secret.len()];
+            rng.fill_bytes(&mut value);
+            (i, value)
+        })
+        .collect();
+    let mut digest_share = vec![0u8; secret.len()];
+    rng.fill_bytes(&mut digest_share[DIGEST_LEN..]);
+    let digest = hmac_digest(&digest_share[DIGEST_LEN..], secret);
+    digest_share[..DIGEST_LEN].copy_from_slice(&digest);
+
+    let mut base: Vec<(u8, &[u8])> = shares.iter().map(|(x, y)| (*x, y.as_slice())).collect();
+    base.push((DIGEST_INDEX, &digest_share));
+    base.push((SECRET_INDEX, secret));
+    let derived: Vec<(u8, Vec<u8>)> = (threshold - 2..count)
+        .map(|x| (x, interpolate(&base, x)))
+        .collect();
+    drop(base);
+    digest_share.zeroize();
+    shares.extend(derived);
+    shares
+}
+
+/// Recovers the secret from `threshold` shares and checks the digest share.
+fn recover_secret(threshold: u8, points: &[(u8, &[u8])]) -> Result<Vec<u8>, ShamirError> {
+    if threshold == 1 {
+        return Ok(points[0].1.to_vec());
+    }
+    let mut secret = interpolate(points, SECRET_INDEX);
+    let mut digest_share = interpolate(points, DIGEST_INDEX);
+    let valid = hmac_digest(&digest_share[DIGEST_LEN..], &secret) == digest_share[..DIGEST_LEN];
+    digest_share.zeroize();
+    if !valid {
+        secret.zeroize();
+        return Err(ShamirError::IntegrityFailure);
+    }
+    Ok(secret)
+}
+
+/// Splits `secret` among `groups`, given as `(member_threshold,
+/// member_count)` pairs, returning the `(member_index, value)` shares of
+/// each group in order. Parameters must already be validated.
+pub(crate) fn split_groups<R: RngCore + CryptoRng + ?Sized>(
+    group_threshold: u8,
+    groups: &[(u8, u8)],
+    secret: &[u8],
+    rng: &mut R,
+) -> Vec<Vec<(u8, Vec<u8>)>> {
+    let mut group_secrets = split_secret(group_threshold, groups.len() as u8, secret, rng);
+    let shares = group_secrets
+        .iter()
+        .zip(groups)
+        .map(|((_, group_secret), &(threshold, count))| {
+            split_secret(threshold, count, group_secret, rng)
+        })
+        .collect();
+    group_secrets
+        .iter_mut()
+        .for_each(|(_, secret)| secret.zeroize());
+    shares
+}
+
+/// Recovers the secret from member shares of one split.
+///
+/// Groups with fewer than their member threshold of shares are ignored;
+/// `group_threshold` complete groups are required.
+pub(crate) fn combine_groups(
+    group_threshold: u8,
+    members: &[GroupMember<'_>],
+) -> Result<Vec<u8>, ShamirError> {
+    let mut groups: BTreeMap<u8, Vec<&GroupMember<'_>>> = BTreeMap::new();
+    for member in members {
+        let group = groups.entry(member.group_index).or_default();
+        if let Some(other) = group.first() {
+            if other.member_threshold != member.member_threshold {
+                return Err(ShamirError::ThresholdMismatch);
+            }
+        }
+        if group
+            .iter()
+            .any(|other| other.member_index == member.member_index)
+        {
+            return Err(ShamirError::DuplicateShareX(BigUint::from(
+                member.member_index,
+            )));
+        }
+        group.push(member);
+    }
+
+    let complete: Vec<(u8, &Vec<&GroupMember<'_>>)> = groups
+        .iter()
+        .filter(|(_, group)| group.len() >= group[0].member_threshold as usize)
+        .map(|(&index, group)| (index, group))
+        .collect();
+    let required = group_threshold as usize;
+    if complete.len() < required {
+        return Err(ShamirError::NotEnoughShares {
+            required,
+            provided: complete.len(),
+        });
+    }
+
+    let mut group_secrets = Vec::with_capacity(required);
+    for (index, group) in &complete[..required] {
+        let threshold = group[0].member_threshold;
+        let points: Vec<(u8, &[u8])> = group[..threshold as usize]
+            .iter()
+            .map(|member| (member.member_index, member.value))
+            .collect();
+        match recover_secret(threshold, &points) {
+            Ok(secret) => group_secrets.push((*index, secret)),
+            Err(err) => {
+                group_secrets.iter_mut().for_each(|(_, s)| s.zeroize());
+                return Err(err);
+            }
+        }
+    }
+    let points: Vec<(u8, &[u8])> = group_secrets
+        .iter()
+        .map(|(index, secret)| (*index, secret.as_slice()))
+        .collect();
+    let secret = recover_secret(group_threshold, &points);
+    drop(points);
+    group_secrets.iter_mut().for_each(|(_, s)| s.zeroize());
+    secret
+}
@@ -0,0 +1,236 @@
+//! Hierarchical threshold sharing (Tassa's conjunctive scheme).
+//!
+//! Shareholders are grouped into levels `0..m`, level 0 being the most
+//! senior. With cumulative thresholds `t_0 < t_1 < ... < t_m`, a set of
+//! shares is authorized when, for every level `i`, it holds at least `t_i`
+//! shares from levels `0..=i`. A level-`i` shareholder receives the
+//! `t_{i-1}`-th derivative of the sharing polynomial at its x-coordinate,
+//! and reconstruction solves the resulting Birkhoff interpolation problem.
+
+use alloc::vec::Vec;
+use core::fmt;
+use core::str::FromStr;
+
+use num_bigint::BigUint;
+use num_traits::{One, Zero};
+use rand::{CryptoRng, RngCore};
+
+use crate::bigint::modpow;
+use crate::linear::solve;
+use crate::polynomial::Polynomial;
+use crate::shamir::{check_compatible, check_same_set};
+use crate::{Fingerprint, ShamirError, Share, ShareSetId};
+
+/// A share tagged with the hierarchy level of its holder.
+///
+/// Written as `L<level>:<share>` in text form.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HierarchicalShare {
+    level: usize,
+    share: Share,
+}
+
+impl HierarchicalShare {
+    pub fn new(level: usize, share: Share) -> Self {
+        HierarchicalShare { level, share }
+    }
+
+    pub fn level(&self) -> usize {
+        self.level
+    }
+
+    pub fn share(&self) -> &Share {
+        &self.share
+    }
+}
+
+impl fmt::Display for HierarchicalShare {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "L{}:{}", self.level, self.share)
+    }
+}
+
+impl FromStr for HierarchicalShare {
+    type Err = ShamirError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (level, share) = s
+            .trim()
+            .strip_prefix('L')
+            .and_then(|rest| rest.split_once(':'))
+            .ok_or(ShamirError::MalformedShare("missing level"))?;
+        let level = level
+            .parse()
+            .map_err(|_| ShamirError::MalformedShare("invalid level"))?;
+        Ok(HierarchicalShare::new(level, share.parse()?))
+    }
+}
+
+/// A hierarchical access structure over a prime field.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HierarchicalScheme {
+    thresholds: Vec<usize>,
+    modulus: BigUint,
+}
+
+impl HierarchicalScheme {
+    /// `thresholds` are the cumulative thresholds `t_0 < t_1 < ...`; the last
+    /// one is the overall threshold. For "any 3, at least one director" use
+    /// `[1, 3]` with directors at level 0.
+    pub fn new(thresholds: Vec<usize>, modulus: BigUint) -> Result<Self, ShamirError> {
+        let overall = thresholds.last().copied().unwrap_or(0);
+        let increasing = thresholds.windows(2).all(|pair| pair[0] < pair[1]);
+        if thresholds.first().is_none_or(|&t| t == 0) || !increasing || overall < 2 {
+            return Err(ShamirError::InvalidAccessStructure(
+                "thresholds must be positive, strictly increasing and end at least at 2",
+            ));
+        }
+        Ok(HierarchicalScheme {
+            thresholds,
+            modulus,
+        })
+    }
+
+    pub fn thresholds(&self) -> &[usize] {
+        &self.thresholds
+    }
+
+    pub fn threshold(&self) -> usize {
+        *self.thresholds.last().expect("validated in new")
+    }
+
+    pub fn modulus(&self) -> &BigUint {
+        &self.modulus
+    }
+
+    /// Whether holders at the given levels may reconstruct.
+    pub fn is_authorized(&self, levels: &[usize]) -> bool {
+        self.thresholds
+            .iter()
+            .enumerate()
+            .all(|(i, &t)| levels.iter().filter(|&&level| level <= i).count() >= t)
+    }
+
+    /// Splits `secret` among `counts[i]` holders at each level `i`.
+    ///
+    /// x-coordinates are assigned from 1 upwards, senior levels first, which
+    /// keeps the Birkhoff systems of authorized sets solvable over large
+    /// fields.
+    pub fn split_with_rng<R: RngCore + CryptoRng + ?Sized>(
+        &self,
+        secret: &BigUint,
+        counts: &[usize],
+        rng: &mut R,
+    ) -> Result<Vec<HierarchicalShare>, ShamirError> {
+        if counts.len() != self.thresholds.len() {
+            return Err(ShamirError::InvalidAccessStructure(
+                "one holder count per level is required",
+            ));
+        }
+        let levels: Vec<usize> = counts
+            .iter()
+            .enumerate()
+            .flat_map(|(level, &count)| core::iter::repeat_n(level, count))
+            .collect();
+        if !self.is_authorized(&levels) {
+            return Err(ShamirError::InvalidAccessStructure(
+                "the holders could never satisfy the thresholds",
+            ));
+        }
+        if self.modulus <= BigUint::from(levels.len() as u64) {
+            return Err(ShamirError::ModulusTooSmall {
+                modulus: self.modulus.clone(),
+                shares: levels.len(),
+            });
+        }
+        if *secret >= self.modulus {
+            return Err(ShamirError::SecretTooLarge);
+        }
+
+        let threshold = self.threshold();
+        let polynomial = Polynomial::random(secret, threshold - 1, &self.modulus, rng);
+        let fingerprint = Fingerprint::of(&self.modulus);
+        let set_id = ShareSetId::random(rng);
+        Ok(levels
+            .iter()
+            .enumerate()
+            .map(|(i, &level)| {
+                let x = BigUint::from(i as u64 + 1);
+                let order = self.derivative_order(level);
+                let y = self.derivative_at(polynomial.coefficients(), order, &x);
+                let share = Share::new(x, y, threshold, fingerprint).with_set_id(set_id);
+                HierarchicalShare::new(level, share)
+            })
+            .collect())
+    }
+
+    /// Recovers the secret from an authorized set of shares.
+    pub fn reconstruct(&self, shares: &[HierarchicalShare]) -> Result<BigUint, ShamirError> {
+        let plain: Vec<Share> = shares.iter().map(|s| s.share.clone()).collect();
+        check_compatible(&plain, &self.modulus)?;
+        check_same_set(&plain)?;
+        let threshold = self.threshold();
+        if plain[0].threshold() != threshold {
+            return Err(ShamirError::ThresholdMismatch);
+        }
+        let levels: Vec<usize> = shares.iter().map(HierarchicalShare::level).collect();
+        if levels.iter().any(|&level| level >= self.thresholds.len()) {
+            return Err(ShamirError::InvalidAccessStructure("unknown level"));
+        }
+        if !self.is_authorized(&levels) {
+            return Err(ShamirError::Unauthorized);
+        }
+        for (i, share) in plain.iter().enumerate() {
+            if plain[..i].iter().any(|other| other.x() == share.x()) {
+                return Err(ShamirError::DuplicateShareX(share.x().clone()));
+            }
+        }
+
+        let rows = shares
+            .iter()
+            .map(|share| {
+                let order = self.derivative_order(share.level);
+                let mut row: Vec<BigUint> = (0..threshold)
+                    .map(|k| self.derivative_term(k, order, share.share.x()))
+                    .collect();
+                row.push(share.share.y() % &self.modulus);
+                row
+            })
+            .collect();
+        let (solution, rank) = solve(rows, threshold, &self.modulus)?;
+        if rank < threshold {
+            return Err(ShamirError::SingularSystem);
+        }
+        Ok(solution[0].clone())
+    }
+
+    fn derivative_order(&self, level: usize) -> usize {
+        if level == 0 {
+            0
+        } else {
+            self.thresholds[level - 1]
+        }
+    }
+
+    /// Coefficient of `a_k` in the `order`-th derivative evaluated at `x`:
+    /// `k! / (k - order)! * x^(k - order)`.
+    fn derivative_term(&self, k: usize, order: usize, x: &BigUint) -> BigUint {
+        if k < order {
+            return BigUint::zero();
+        }
+        let falling = (k - order + 1..=k).fold(BigUint::one(), |acc, factor| {
+            (acc * BigUint::from(factor as u64)) % &self.modulus
+        });
+        (falling * modpow(x, &BigUint::from((k - order) as u64), &self.modulus)) % &self.modulus
+    }
+
+    fn derivative_at(&self, coefficients: &[BigUint], order: usize, x: &BigUint) -> BigUint {
+        coefficients
+            .iter()
+            .enumerate()
+            .fold(BigUint::zero(), |acc, (k, coeff)| {
+                (acc + coeff * self.derivative_term(k, order, x)) % &self.modulus
+            })
+    }
+}
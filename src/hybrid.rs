@@ -0,0 +1,95 @@
+//! Encrypt-then-split sharing for bulk data.
+//!
+//! The payload is encrypted under a fresh AES-256-GCM key and only the key
+//! is shared, with [`split_bytes`]; the ciphertext can then be stored or
+//! copied freely. Reconstruction recombines the key and decrypts, so a wrong
+//! key or a tampered ciphertext is reported as [`ShamirError::IntegrityFailure`].
+
+use alloc::vec::Vec;
+
+use aes_gcm::aead::{Aead, KeyInit, Payload};
+use aes_gcm::{Aes256Gcm, Nonce};
+use rand::{CryptoRng, RngCore};
+use zeroize::Zeroize;
+
+use crate::{reconstruct_bytes, split_bytes, ShamirError, Share};
+
+/// Associated data binding ciphertexts to this construction.
+const AAD: &[u8] = b"prime-shamir/hybrid/v1";
+
+/// An AES-256-GCM ciphertext whose key is held in shares.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EncryptedPayload {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::array_hex"))]
+    nonce: [u8; 12],
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::bytes_hex"))]
+    ciphertext: Vec<u8>,
+}
+
+impl EncryptedPayload {
+    pub fn new(nonce: [u8; 12], ciphertext: Vec<u8>) -> Self {
+        EncryptedPayload { nonce, ciphertext }
+    }
+
+    pub fn nonce(&self) -> &[u8; 12] {
+        &self.nonce
+    }
+
+    /// The ciphertext followed by the 16-byte GCM tag.
+    pub fn ciphertext(&self) -> &[u8] {
+        &self.ciphertext
+    }
+}
+
+/// Encrypts `payload` under a random key and splits the key into `shares`
+/// shares, any `threshold` of which decrypt it again.
+pub fn hybrid_split<R: RngCore + CryptoRng + ?Sized>(
+    payload: &[u8],
+    threshold: usize,
+    shares: usize,
+    rng: &mut R,
+) -> Result<(EncryptedPayload, Vec<Share>), ShamirError> {
+    let mut key = [0u8; 32];
+    rng.fill_bytes(&mut key);
+    let key_shares = split_bytes(&key, threshold, shares, rng);
+    let cipher = Aes256Gcm::new(&key.into());
+    key.zeroize();
+    let key_shares = key_shares?;
+
+    let mut nonce = [0u8; 12];
+    rng.fill_bytes(&mut nonce);
+    let ciphertext = cipher
+        .encrypt(
+            Nonce::from_slice(&nonce),
+            Payload {
+                msg: payload,
+                aad: AAD,
+            },
+        )
+        .map_err(|_| ShamirError::SecretTooLarge)?;
+    Ok((EncryptedPayload::new(nonce, ciphertext), key_shares))
+}
+
+/// Recombines the key from `shares` and decrypts `payload`.
+pub fn hybrid_reconstruct(
+    payload: &EncryptedPayload,
+    shares: &[Share],
+) -> Result<Vec<u8>, ShamirError> {
+    let mut key = reconstruct_bytes(shares)?;
+    if key.len() != 32 {
+        key.zeroize();
+        return Err(ShamirError::MalformedShare("not an AES-256 key share"));
+    }
+    let cipher = Aes256Gcm::new_from_slice(&key).expect("key is 32 bytes");
+    key.zeroize();
+    cipher
+        .decrypt(
+            Nonce::from_slice(&payload.nonce),
+            Payload {
+                msg: &payload.ciphertext,
+                aad: AAD,
+            },
+        )
+        .map_err(|_| ShamirError::IntegrityFailure)
+}
@@ -0,0 +1,68 @@
+use alloc::vec::Vec;
+
+use num_bigint::BigUint;
+use num_traits::Zero;
+
+use crate::shamir::{check_digest, check_share_set, lagrange_coefficient_at};
+use crate::{zeroize_biguint, ShamirError, Share};
+
+/// Derives the share at `new_x` from a quorum of existing shares, so a new
+/// custodian can be onboarded without re-splitting the secret.
+///
+/// The implicit polynomial is evaluated at `new_x` by Lagrange
+/// interpolation. `new_x` must be nonzero, below the modulus and not already
+/// used by one of `shares`. The secret is only computed when the shares
+/// carry a [`SecretDigest`](crate::SecretDigest): it is checked against the
+/// digest and wiped, so a wrong quorum cannot issue a share that claims the
+/// digest.
+pub fn issue_share(
+    shares: &[Share],
+    new_x: &BigUint,
+    modulus: &BigUint,
+) -> Result<Share, ShamirError> {
+    check_share_set(shares, modulus)?;
+    if new_x.is_zero() || new_x >= modulus {
+        return Err(ShamirError::InvalidParticipant(new_x.clone()));
+    }
+    if shares.iter().any(|share| share.x() == new_x) {
+        return Err(ShamirError::DuplicateShareX(new_x.clone()));
+    }
+    let xs: Vec<BigUint> = shares.iter().map(|share| share.x().clone()).collect();
+    if shares[0].digest().is_some() {
+        let mut secret = interpolate(shares, &xs, &BigUint::zero(), modulus)?;
+        secret = check_digest(shares, secret)?;
+        zeroize_biguint(&mut secret);
+    }
+    let y = interpolate(shares, &xs, new_x, modulus)?;
+    let mut share = Share::new(
+        new_x.clone(),
+        y,
+        shares[0].threshold(),
+        shares[0].fingerprint(),
+    );
+    if let Some(digest) = shares[0].digest() {
+        share = share.with_digest(*digest);
+    }
+    if let Some(set_id) = shares[0].set_id() {
+        share = share.with_set_id(set_id);
+    }
+    Ok(share)
+}
+
+/// The value at `at` of the polynomial through `shares`, whose
+/// x-coordinates are `xs`.
+fn interpolate(
+    shares: &[Share],
+    xs: &[BigUint],
+    at: &BigUint,
+    modulus: &BigUint,
+) -> Result<BigUint, ShamirError> {
+    let mut y = BigUint::zero();
+    for (i, share) in shares.iter().enumerate() {
+        let mut term = lagrange_coefficient_at(xs, i, at, modulus)? * share.y();
+        y += &term;
+        y %= modulus;
+        zeroize_biguint(&mut term);
+    }
+    Ok(y)
+}
@@ -0,0 +1,163 @@
+//! Shamir's Secret Sharing over large prime fields.
+//!
+//! The crate exposes prime generation and primality testing alongside the
+//! split and reconstruct primitives so the scheme can be embedded in other
+//! projects.
+//!
+//! The default `std` feature adds OS-entropy convenience constructors; with
+//! `default-features = false` the crate is `no_std` and only needs `alloc`.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+mod armor;
+mod asn1;
+mod bech32;
+mod bigint;
+#[cfg(feature = "bip39")]
+mod bip39;
+#[cfg(feature = "bls")]
+mod bls;
+mod bytes;
+mod cbor;
+mod chunked;
+#[cfg(any(feature = "sskr", feature = "qr"))]
+mod crc32;
+mod der;
+pub mod dh;
+mod dkg;
+#[cfg(all(feature = "std", feature = "serde"))]
+mod document;
+mod ed25519;
+mod erasure;
+mod error;
+mod field;
+mod gf256;
+pub mod group;
+#[cfg(any(feature = "slip39", feature = "sskr"))]
+mod grouped;
+mod hierarchical;
+#[cfg(feature = "hybrid")]
+mod hybrid;
+mod issue;
+mod linear;
+mod mac;
+mod montgomery;
+mod multi;
+mod multipoint;
+mod packed;
+mod padding;
+#[cfg(feature = "paper")]
+mod paper;
+#[cfg(feature = "passphrase")]
+mod passphrase;
+mod pedersen;
+mod policy;
+mod polynomial;
+pub mod primes;
+#[cfg(feature = "qr")]
+mod qr;
+mod ramp;
+mod refresh;
+mod report;
+mod reshare;
+mod robust;
+pub mod rsa;
+mod scheme;
+mod secp256k1;
+#[cfg(feature = "serde")]
+mod serde_support;
+mod shamir;
+mod share;
+#[cfg(feature = "signing")]
+mod signing;
+#[cfg(feature = "slip39")]
+mod slip39;
+#[cfg(feature = "sskr")]
+mod sskr;
+mod ssss;
+#[cfg(feature = "std")]
+mod stream;
+mod weighted;
+mod wipe;
+
+pub use armor::{armor, dearmor, ArmorStyle};
+#[cfg(feature = "std")]
+pub use asn1::SHARE_PEM_TAG;
+pub use bech32::{Bech32Variant, SHARE_HRP};
+#[cfg(feature = "bls")]
+pub use bls::{
+    bls_combine_public_keys, bls_combine_signatures, bls_modulus, bls_partial_sign, bls_public_key,
+    bls_public_share, bls_split_secret_key, bls_verify, BlsPartialSignature, BlsPublicShare,
+};
+pub use bytes::{bytes_modulus, reconstruct_bytes, split_bytes};
+pub use chunked::{reconstruct_chunked, split_chunked, ChunkedShare, ShareChunk};
+pub use dh::DhParams;
+pub use dkg::{DkgCommitmentMessage, DkgOutput, DkgParticipant, DkgRound1, DkgShareMessage};
+#[cfg(all(feature = "std", feature = "serde"))]
+pub use document::ShareDocument;
+pub use ed25519::{reconstruct_ed25519_seed, split_ed25519_seed};
+pub use erasure::ErasureCode;
+pub use error::ShamirError;
+pub use field::{field_reconstruct, field_split, Field, FieldShare, Gf2_128, Gf2_64, Gf2n};
+pub use gf256::{gf256_reconstruct, gf256_split, ByteShare, Gf256};
+pub use group::GroupParams;
+pub use hierarchical::{HierarchicalScheme, HierarchicalShare};
+#[cfg(feature = "hybrid")]
+pub use hybrid::{hybrid_reconstruct, hybrid_split, EncryptedPayload};
+pub use issue::issue_share;
+pub use mac::{shamir_split_shares_with_mac, VerificationKey};
+pub use multi::{shamir_reconstruct_multi, shamir_split_multi, MultiShare};
+pub use packed::PackedScheme;
+pub use padding::{reconstruct_bytes_padded, split_bytes_padded, unpad, Padding};
+#[cfg(feature = "paper")]
+pub use paper::{paper_checksum, PaperBackup, PaperEncoding};
+#[cfg(feature = "passphrase")]
+pub use passphrase::{unlock_shares, unlock_shares_with, Argon2Params, LockedShare};
+pub use pedersen::{pedersen_split_shares, PedersenCommitments, PedersenParams};
+pub use policy::{Policy, PolicyShare};
+pub use primes::standard::StandardPrime;
+pub use primes::{
+    generate_blum_integer, generate_blum_prime, generate_certified_prime,
+    generate_fips186_rsa_primes, generate_large_prime, generate_large_prime_cancellable,
+    generate_large_prime_with_config, generate_primes, generate_provable_prime,
+    generate_safe_prime, generate_sophie_germain, generate_strong_prime, is_baillie_psw_prime,
+    is_prime, is_probably_prime, is_strong_lucas_prp, next_prime, prev_prime, verify_certificate,
+    BlumInteger, CompositeWitness, Fips186Primes, PocklingtonStep, Primality, PrimalityCertificate,
+    PrimalityConfig, Primes,
+};
+#[cfg(feature = "parallel")]
+pub use primes::{generate_large_prime_parallel, generate_primes_parallel};
+#[cfg(feature = "std")]
+pub use primes::{
+    generate_large_prime_with_progress, generate_large_prime_with_timeout, PrimeProgress,
+};
+#[cfg(feature = "qr")]
+pub use qr::{qr_png, qr_svg, qr_terminal};
+pub use ramp::RampScheme;
+pub use refresh::refresh_shares;
+pub use report::{pedersen_reconstruct_report, shamir_reconstruct_report, ReconstructReport};
+pub use reshare::{reshare, reshare_combine, reshare_deal};
+pub use robust::shamir_reconstruct_robust;
+pub use rsa::{reconstruct_rsa_key, split_rsa_key, RsaKeyPair, RsaPublicKey};
+pub use scheme::{ShamirScheme, ShamirSchemeBuilder};
+pub use secp256k1::{reconstruct_secp256k1_key, split_secp256k1_key};
+pub use shamir::{
+    reconstruct_batch, reconstruct_with_basis, shamir_reconstruct, shamir_reconstruct_ct,
+    shamir_split_prime_shares, shamir_split_shares, shamir_split_shares_at,
+    shamir_split_shares_deterministic, shamir_split_shares_random_x,
+    shamir_split_shares_with_digest, validate_modulus, LagrangeBasis,
+};
+pub use share::{DealerSignature, Fingerprint, SecretDigest, Share, ShareMac, ShareSetId};
+#[cfg(feature = "signing")]
+pub use signing::{dealer_key_id, dealer_public_key};
+#[cfg(feature = "slip39")]
+pub use slip39::{slip39_combine, slip39_combine_mnemonics, Slip39Scheme, Slip39Share};
+#[cfg(feature = "sskr")]
+pub use sskr::{sskr_combine, SskrScheme, SskrShare};
+pub use ssss::{SsssScheme, SsssShare};
+#[cfg(feature = "std")]
+pub use stream::{ShareReader, ShareWriter, STREAM_CHUNK_SIZE};
+pub use weighted::{reconstruct_weighted, split_weighted, WeightedShare};
+pub use wipe::zeroize_biguint;
@@ -0,0 +1,71 @@
+//! Linear algebra modulo the share field.
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+use num_bigint::BigUint;
+use num_traits::Zero;
+
+use crate::ShamirError;
+
+/// Gaussian elimination modulo `modulus` on an augmented matrix with
+/// `unknowns + 1` columns. Free variables are set to zero; the rank is
+/// returned alongside the solution.
+///
+/// Pivots are inverted with extended Euclid, so a composite modulus gives
+/// either the right answer or an error: when a column has nonzero entries
+/// but none of them is a unit, this fails with
+/// [`ShamirError::SingularSystem`]. An inconsistent system fails with
+/// [`ShamirError::InconsistentShares`].
+pub(crate) fn solve(
+    mut rows: Vec<Vec<BigUint>>,
+    unknowns: usize,
+    modulus: &BigUint,
+) -> Result<(Vec<BigUint>, usize), ShamirError> {
+    let mut pivots = Vec::with_capacity(unknowns);
+    let mut rank = 0;
+    for col in 0..unknowns {
+        let mut pivot = None;
+        let mut nonzero = false;
+        for (r, row) in rows.iter().enumerate().skip(rank) {
+            if row[col].is_zero() {
+                continue;
+            }
+            nonzero = true;
+            if let Some(inv) = row[col].modinv(modulus) {
+                pivot = Some((r, inv));
+                break;
+            }
+        }
+        let Some((pivot, inv)) = pivot else {
+            if nonzero {
+                return Err(ShamirError::SingularSystem);
+            }
+            continue;
+        };
+        rows.swap(rank, pivot);
+        for value in rows[rank].iter_mut() {
+            *value = (&*value * &inv) % modulus;
+        }
+        let pivot_row = rows[rank].clone();
+        for (r, row) in rows.iter_mut().enumerate() {
+            if r != rank && !row[col].is_zero() {
+                let factor = row[col].clone();
+                for (value, pivot_value) in row.iter_mut().zip(&pivot_row).skip(col) {
+                    let sub = (&factor * pivot_value) % modulus;
+                    *value = (&*value + modulus - sub) % modulus;
+                }
+            }
+        }
+        pivots.push(col);
+        rank += 1;
+    }
+    if rows[rank..].iter().any(|row| !row[unknowns].is_zero()) {
+        return Err(ShamirError::InconsistentShares);
+    }
+    let mut solution = vec![BigUint::zero(); unknowns];
+    for (row, &col) in pivots.iter().enumerate() {
+        solution[col] = rows[row][unknowns].clone();
+    }
+    Ok((solution, rank))
+}
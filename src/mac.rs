@@ -0,0 +1,115 @@
+//! Per-share MACs under a published share-set verification key.
+//!
+//! At split time a random [`VerificationKey`] is drawn and every share is
+//! tagged with HMAC-SHA256, keyed with it, over the share's DER encoding
+//! without its MAC or dealer signature. The key is published alongside the shares, so any
+//! holder can check that their share was not corrupted in storage before a
+//! reconstruction ceremony. Because the key is public the tag does not
+//! authenticate the dealer; it only detects accidental damage.
+
+use alloc::vec::Vec;
+use core::fmt;
+use core::str::FromStr;
+
+use hmac::{Hmac, Mac, NewMac};
+use num_bigint::BigUint;
+use rand::{CryptoRng, RngCore};
+use sha2::Sha256;
+use zeroize::Zeroize;
+
+use crate::{shamir_split_shares, ShamirError, Share, ShareMac};
+
+/// Key checking the [`ShareMac`]s of one share set, written as 64 hex digits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct VerificationKey([u8; 32]);
+
+impl VerificationKey {
+    /// Draws a fresh key from `rng`.
+    pub fn random<R: RngCore + CryptoRng + ?Sized>(rng: &mut R) -> Self {
+        let mut bytes = [0u8; 32];
+        rng.fill_bytes(&mut bytes);
+        VerificationKey(bytes)
+    }
+
+    pub fn from_bytes(bytes: [u8; 32]) -> Self {
+        VerificationKey(bytes)
+    }
+
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        &self.0
+    }
+}
+
+impl fmt::Display for VerificationKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&hex::encode(self.0))
+    }
+}
+
+impl FromStr for VerificationKey {
+    type Err = ShamirError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut bytes = [0u8; 32];
+        hex::decode_to_slice(s, &mut bytes)
+            .map_err(|_| ShamirError::InvalidKey("invalid verification key"))?;
+        Ok(VerificationKey(bytes))
+    }
+}
+
+impl ShareMac {
+    /// Tags `share` under `key`, ignoring any MAC or signature it already
+    /// carries.
+    pub fn compute(share: &Share, key: &VerificationKey) -> Self {
+        let mut der = share.untagged().to_der();
+        let mut mac =
+            Hmac::<Sha256>::new_from_slice(key.as_bytes()).expect("HMAC accepts all key sizes");
+        mac.update(b"prime-shamir/share-mac/v2");
+        mac.update(&der);
+        der.zeroize();
+        ShareMac::from_bytes(mac.finalize().into_bytes().into())
+    }
+}
+
+impl Share {
+    /// Checks the share's MAC under `key`.
+    #[tracing::instrument(level = "debug", skip_all, fields(x = %self.x()))]
+    pub fn verify_mac(&self, key: &VerificationKey) -> Result<(), ShamirError> {
+        let mac = self
+            .mac()
+            .ok_or(ShamirError::MissingMac(self.x().clone()))?;
+        let expected = ShareMac::compute(self, key);
+        let difference = mac
+            .as_bytes()
+            .iter()
+            .zip(expected.as_bytes())
+            .fold(0u8, |acc, (a, b)| acc | (a ^ b));
+        if difference != 0 {
+            tracing::warn!("MAC mismatch for the share at x = {}", self.x());
+            return Err(ShamirError::MacMismatch(self.x().clone()));
+        }
+        tracing::debug!("MAC verified for the share at x = {}", self.x());
+        Ok(())
+    }
+}
+
+/// Like [`shamir_split_shares`], tagging every share with a [`ShareMac`]
+/// under a fresh [`VerificationKey`], which is returned for publication.
+pub fn shamir_split_shares_with_mac<R: RngCore + CryptoRng + ?Sized>(
+    secret: &BigUint,
+    threshold: usize,
+    shares: usize,
+    modulus: &BigUint,
+    rng: &mut R,
+) -> Result<(Vec<Share>, VerificationKey), ShamirError> {
+    let shares = shamir_split_shares(secret, threshold, shares, modulus, rng)?;
+    let key = VerificationKey::random(rng);
+    let shares = shares
+        .into_iter()
+        .map(|share| {
+            let mac = ShareMac::compute(&share, &key);
+            share.with_mac(mac)
+        })
+        .collect();
+    Ok((shares, key))
+}
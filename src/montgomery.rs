@@ -0,0 +1,240 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+use num_bigint::BigUint;
+use zeroize::Zeroize;
+
+/// Fixed-width Montgomery arithmetic modulo an odd modulus.
+///
+/// Operands are little-endian `u64` limb vectors exactly as wide as the
+/// modulus. `mul` and `add` run in time that depends only on that width,
+/// never on operand values; `pow_vartime` does not.
+pub(crate) struct MontgomeryModulus {
+    limbs: Vec<u64>,
+    /// `-modulus^-1 mod 2^64`.
+    inv: u64,
+    modulus: BigUint,
+}
+
+impl MontgomeryModulus {
+    /// Returns `None` for an even modulus, which has no Montgomery form.
+    pub(crate) fn new(modulus: &BigUint) -> Option<Self> {
+        if !modulus.bit(0) {
+            return None;
+        }
+        let limbs = modulus.to_u64_digits();
+        let mut inv = 1u64;
+        for _ in 0..6 {
+            inv = inv.wrapping_mul(2u64.wrapping_sub(limbs[0].wrapping_mul(inv)));
+        }
+        Some(MontgomeryModulus {
+            limbs,
+            inv: inv.wrapping_neg(),
+            modulus: modulus.clone(),
+        })
+    }
+
+    pub(crate) fn width(&self) -> usize {
+        self.limbs.len()
+    }
+
+    /// Pads `value`, which must be below the modulus, to the fixed width.
+    pub(crate) fn to_limbs(&self, value: &BigUint) -> Vec<u64> {
+        let mut limbs = value.to_u64_digits();
+        limbs.resize(self.width(), 0);
+        limbs
+    }
+
+    /// Loads a secret `value` into fixed-width limbs without dividing it by
+    /// the modulus, or returns `None` if it is not below the modulus.
+    ///
+    /// The comparison runs over every limb and only its outcome is branched
+    /// on. The one thing the timing reveals is how many limbs `value`
+    /// occupies, which a `BigUint` stores explicitly: whether it falls below
+    /// `2^(64 k)` for some `k` smaller than the width.
+    pub(crate) fn load(&self, value: &BigUint) -> Option<Vec<u64>> {
+        let n = self.width();
+        if value.iter_u64_digits().len() > n {
+            return None;
+        }
+        let mut limbs = vec![0u64; n];
+        for (limb, digit) in limbs.iter_mut().zip(value.iter_u64_digits()) {
+            *limb = digit;
+        }
+        // value - modulus borrows exactly when value is below the modulus.
+        let mut borrow = 0u64;
+        for (&limb, &m) in limbs.iter().zip(&self.limbs) {
+            let (d1, b1) = limb.overflowing_sub(m);
+            let (_, b2) = d1.overflowing_sub(borrow);
+            borrow = (b1 | b2) as u64;
+        }
+        if borrow == 1 {
+            Some(limbs)
+        } else {
+            limbs.zeroize();
+            None
+        }
+    }
+
+    /// Converts a public value into Montgomery form, `value * R mod modulus`.
+    pub(crate) fn to_montgomery(&self, value: &BigUint) -> Vec<u64> {
+        let shifted = (value << (64 * self.width())) % &self.modulus;
+        self.to_limbs(&shifted)
+    }
+
+    pub(crate) fn to_biguint(limbs: &[u64]) -> BigUint {
+        let mut digits = Vec::with_capacity(limbs.len() * 2);
+        for limb in limbs {
+            digits.push(*limb as u32);
+            digits.push((*limb >> 32) as u32);
+        }
+        let value = BigUint::from_slice(&digits);
+        digits.zeroize();
+        value
+    }
+
+    /// Montgomery form of one, `R mod modulus`.
+    pub(crate) fn one(&self) -> Vec<u64> {
+        self.to_montgomery(&BigUint::from(1u32))
+    }
+
+    /// Converts out of Montgomery form, `value * R^-1 mod modulus`.
+    #[cfg_attr(feature = "gmp", allow(dead_code))]
+    pub(crate) fn to_plain(&self, value: &[u64]) -> BigUint {
+        let mut unit = vec![0u64; self.width()];
+        unit[0] = 1;
+        let mut limbs = self.mul(value, &unit);
+        let value = Self::to_biguint(&limbs);
+        limbs.zeroize();
+        value
+    }
+
+    /// Montgomery product `a * b * R^-1 mod modulus` (CIOS).
+    pub(crate) fn mul(&self, a: &[u64], b: &[u64]) -> Vec<u64> {
+        let mut t = vec![0u64; self.width() + 1];
+        self.product(a, b, &mut t);
+        let result = self.reduce_once(&t);
+        t.zeroize();
+        result
+    }
+
+    /// `base^exponent` for `base` in Montgomery form, with 4-bit windows.
+    ///
+    /// Unlike [`mul`](Self::mul), this branches on the exponent bits and on
+    /// the final subtraction of each product, so its timing leaks the
+    /// exponent. It is for primality tests and public values, the same
+    /// cases `BigUint::modpow` served before.
+    #[cfg_attr(feature = "gmp", allow(dead_code))]
+    pub(crate) fn pow_vartime(&self, base: &[u64], exponent: &BigUint) -> Vec<u64> {
+        let n = self.width();
+        let mut t = vec![0u64; n + 1];
+        let mut table = Vec::with_capacity(16);
+        table.push(self.one());
+        for i in 1..16 {
+            let mut next = vec![0u64; n];
+            self.mul_vartime_into(&table[i - 1], base, &mut t, &mut next);
+            table.push(next);
+        }
+        let mut acc = self.one();
+        let mut scratch = vec![0u64; n];
+        for window in (0..exponent.bits().div_ceil(4)).rev() {
+            for _ in 0..4 {
+                self.mul_vartime_into(&acc, &acc, &mut t, &mut scratch);
+                core::mem::swap(&mut acc, &mut scratch);
+            }
+            let digit = (0..4).fold(0, |digit, bit| {
+                digit | (usize::from(exponent.bit(window * 4 + bit)) << bit)
+            });
+            if digit != 0 {
+                self.mul_vartime_into(&acc, &table[digit], &mut t, &mut scratch);
+                core::mem::swap(&mut acc, &mut scratch);
+            }
+        }
+        table.zeroize();
+        scratch.zeroize();
+        t.zeroize();
+        acc
+    }
+
+    /// [`mul`](Self::mul) into `out`, using `t` (one limb wider than the
+    /// modulus) as scratch and subtracting the modulus only when needed.
+    #[cfg_attr(feature = "gmp", allow(dead_code))]
+    fn mul_vartime_into(&self, a: &[u64], b: &[u64], t: &mut [u64], out: &mut [u64]) {
+        let n = self.width();
+        self.product(a, b, t);
+        let mut borrow = 0u64;
+        for ((o, &value), &m) in out[..n].iter_mut().zip(&t[..n]).zip(&self.limbs) {
+            let (d1, b1) = value.overflowing_sub(m);
+            let (d2, b2) = d1.overflowing_sub(borrow);
+            *o = d2;
+            borrow = (b1 | b2) as u64;
+        }
+        if t[n] < borrow {
+            out[..n].copy_from_slice(&t[..n]);
+        }
+    }
+
+    /// Leaves `a * b * R^-1` in the `n + 1` limbs of `t`, below twice the
+    /// modulus, interleaving each row's multiplication and reduction.
+    fn product(&self, a: &[u64], b: &[u64], t: &mut [u64]) {
+        let n = self.width();
+        let (a, m_limbs, t) = (&a[..n], &self.limbs[..n], &mut t[..=n]);
+        t.fill(0);
+        for &bi in &b[..n] {
+            let wide = t[0] as u128 + a[0] as u128 * bi as u128;
+            let mut carry = (wide >> 64) as u64;
+            let m = (wide as u64).wrapping_mul(self.inv);
+            // The low limb of this sum is zero by the choice of m.
+            let reduced = (wide as u64) as u128 + m as u128 * m_limbs[0] as u128;
+            let mut reduce_carry = (reduced >> 64) as u64;
+            for j in 1..n {
+                let wide = t[j] as u128 + a[j] as u128 * bi as u128 + carry as u128;
+                carry = (wide >> 64) as u64;
+                let reduced =
+                    (wide as u64) as u128 + m as u128 * m_limbs[j] as u128 + reduce_carry as u128;
+                reduce_carry = (reduced >> 64) as u64;
+                t[j - 1] = reduced as u64;
+            }
+            let top = t[n] as u128 + carry as u128 + reduce_carry as u128;
+            t[n - 1] = top as u64;
+            t[n] = (top >> 64) as u64;
+        }
+    }
+
+    /// `a + b mod modulus` for reduced operands.
+    pub(crate) fn add(&self, a: &[u64], b: &[u64]) -> Vec<u64> {
+        let n = self.width();
+        let mut sum = vec![0u64; n + 1];
+        let mut carry = 0u64;
+        for j in 0..n {
+            let wide = a[j] as u128 + b[j] as u128 + carry as u128;
+            sum[j] = wide as u64;
+            carry = (wide >> 64) as u64;
+        }
+        sum[n] = carry;
+        let result = self.reduce_once(&sum);
+        sum.zeroize();
+        result
+    }
+
+    /// Subtracts the modulus from an `n + 1` limb value below twice the
+    /// modulus when that does not underflow, selecting by mask.
+    fn reduce_once(&self, value: &[u64]) -> Vec<u64> {
+        let n = self.width();
+        let mut diff = vec![0u64; n + 1];
+        let mut borrow = 0u64;
+        for j in 0..=n {
+            let m = if j < n { self.limbs[j] } else { 0 };
+            let (d1, b1) = value[j].overflowing_sub(m);
+            let (d2, b2) = d1.overflowing_sub(borrow);
+            diff[j] = d2;
+            borrow = (b1 | b2) as u64;
+        }
+        let keep_diff = borrow.wrapping_sub(1);
+        let result = (0..n)
+            .map(|j| (diff[j] & keep_diff) | (value[j] & !keep_diff))
+            .collect();
+        diff.zeroize();
+        result
+    }
+}
@@ -0,0 +1,133 @@
+//! Several secrets shared through a single set of shares.
+//!
+//! Each secret gets its own polynomial; participant `x` holds the
+//! evaluation of every polynomial at `x`, so one share per participant
+//! covers all secrets and each secret can be recovered on its own.
+
+use alloc::vec::Vec;
+
+use num_bigint::BigUint;
+use rand::{CryptoRng, RngCore};
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+use crate::shamir::check_split_params;
+use crate::{
+    shamir_reconstruct, shamir_split_shares, zeroize_biguint, Fingerprint, ShamirError, Share,
+};
+
+/// One participant's share of every secret in a multi-secret split.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MultiShare {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::share_x"))]
+    x: BigUint,
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "crate::serde_support::biguint_hex_seq")
+    )]
+    ys: Vec<BigUint>,
+    threshold: usize,
+    fingerprint: Fingerprint,
+}
+
+impl MultiShare {
+    pub fn new(x: BigUint, ys: Vec<BigUint>, threshold: usize, fingerprint: Fingerprint) -> Self {
+        MultiShare {
+            x,
+            ys,
+            threshold,
+            fingerprint,
+        }
+    }
+
+    pub fn x(&self) -> &BigUint {
+        &self.x
+    }
+
+    /// The share values, one per secret in split order.
+    pub fn ys(&self) -> &[BigUint] {
+        &self.ys
+    }
+
+    pub fn threshold(&self) -> usize {
+        self.threshold
+    }
+
+    pub fn fingerprint(&self) -> Fingerprint {
+        self.fingerprint
+    }
+
+    /// Number of secrets covered by this share.
+    pub fn secrets(&self) -> usize {
+        self.ys.len()
+    }
+
+    /// The plain [`Share`] of the secret at `index`, if there is one.
+    pub fn share(&self, index: usize) -> Option<Share> {
+        self.ys
+            .get(index)
+            .map(|y| Share::new(self.x.clone(), y.clone(), self.threshold, self.fingerprint))
+    }
+}
+
+impl Zeroize for MultiShare {
+    fn zeroize(&mut self) {
+        self.ys.iter_mut().for_each(zeroize_biguint);
+    }
+}
+
+impl Drop for MultiShare {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl ZeroizeOnDrop for MultiShare {}
+
+/// Splits every secret in `secrets` among the same `shares` participants,
+/// each of whom receives a single [`MultiShare`].
+pub fn shamir_split_multi<R: RngCore + CryptoRng + ?Sized>(
+    secrets: &[BigUint],
+    threshold: usize,
+    shares: usize,
+    modulus: &BigUint,
+    rng: &mut R,
+) -> Result<Vec<MultiShare>, ShamirError> {
+    if secrets.is_empty() {
+        return Err(ShamirError::MissingParameter("secrets"));
+    }
+    for secret in secrets {
+        check_split_params(secret, threshold, shares, modulus)?;
+    }
+    let fingerprint = Fingerprint::of(modulus);
+    let mut result: Vec<MultiShare> = (1..=shares as u64)
+        .map(|x| {
+            let ys = Vec::with_capacity(secrets.len());
+            MultiShare::new(BigUint::from(x), ys, threshold, fingerprint)
+        })
+        .collect();
+    for secret in secrets {
+        let split = shamir_split_shares(secret, threshold, shares, modulus, rng)?;
+        for (multi, share) in result.iter_mut().zip(&split) {
+            multi.ys.push(share.y().clone());
+        }
+    }
+    Ok(result)
+}
+
+/// Recovers the secret at `index` from at least `threshold` multi-shares.
+pub fn shamir_reconstruct_multi(
+    shares: &[MultiShare],
+    index: usize,
+    modulus: &BigUint,
+) -> Result<BigUint, ShamirError> {
+    let plain = shares
+        .iter()
+        .map(|share| {
+            share
+                .share(index)
+                .ok_or(ShamirError::MalformedShare("secret index out of range"))
+        })
+        .collect::<Result<Vec<Share>, _>>()?;
+    shamir_reconstruct(&plain, modulus)
+}
@@ -0,0 +1,235 @@
+//! Evaluation of one polynomial at many points through subproduct trees.
+//!
+//! The points are cut into blocks at least as long as the polynomial. For
+//! each block the products of `x - x_i` over halves, quarters and so on of
+//! the block are built bottom-up, and the polynomial is reduced modulo them
+//! top-down until every remainder covers a handful of points, which Horner's
+//! rule finishes. Remainders come from Newton iteration on reversed
+//! polynomials, so a block of `b` points costs `O(M(b) log b)` for
+//! polynomial multiplication time `M`, against `b^2` for evaluating each
+//! point separately.
+//!
+//! Products are formed by Kronecker substitution: the coefficients are laid
+//! out in fixed-width slots of one large integer and multiplied with
+//! `num-bigint`, whose Karatsuba and Toom-3 products make `M(b)` grow like
+//! `b^1.5` rather than `b^2`. Unlike [`Polynomial::evaluate`], nothing here
+//! runs in constant time.
+//!
+//! [`Polynomial::evaluate`]: crate::polynomial::Polynomial::evaluate
+
+use alloc::boxed::Box;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use num_bigint::BigUint;
+use num_traits::{One, Zero};
+use zeroize::Zeroize;
+
+use crate::zeroize_biguint;
+
+/// Polynomials of at least this many coefficients are evaluated through
+/// subproduct trees; below it Horner's rule is faster.
+pub(crate) const MIN_COEFFICIENTS: usize = 512;
+
+/// Products with a factor this short are formed term by term.
+const SCHOOLBOOK_MAX: usize = 16;
+
+/// Subtrees over at most this many points are finished by Horner's rule.
+const LEAF_POINTS: usize = 32;
+
+/// Coefficients lowest degree first, each below the modulus.
+type Poly = Vec<BigUint>;
+
+/// Whether [`evaluate`] beats evaluating each of `points` points on its own
+/// for a polynomial of `coefficients` coefficients.
+pub(crate) fn is_worthwhile(coefficients: usize, points: usize) -> bool {
+    coefficients >= MIN_COEFFICIENTS && points >= coefficients
+}
+
+/// Evaluates the polynomial with `coefficients` at every point of `xs`,
+/// keeping their order.
+pub(crate) fn evaluate(
+    coefficients: &[BigUint],
+    xs: &[BigUint],
+    modulus: &BigUint,
+) -> Vec<BigUint> {
+    let mut f: Poly = coefficients.iter().map(|coeff| coeff % modulus).collect();
+    let xs: Vec<BigUint> = xs.iter().map(|x| x % modulus).collect();
+    let block = f.len().next_power_of_two().max(LEAF_POINTS);
+    let mut ys = Vec::with_capacity(xs.len());
+    for points in xs.chunks(block) {
+        let tree = Tree::build(points, modulus);
+        tree.evaluate(&f, points, modulus, &mut ys);
+    }
+    wipe(&mut f);
+    ys
+}
+
+/// The product of `x - x_i` over a run of points, with the products for
+/// its two halves below it.
+struct Tree {
+    product: Poly,
+    halves: Option<(usize, Box<(Tree, Tree)>)>,
+}
+
+impl Tree {
+    fn build(xs: &[BigUint], modulus: &BigUint) -> Self {
+        if xs.len() <= LEAF_POINTS {
+            let mut product = vec![BigUint::one()];
+            for x in xs {
+                let root = (modulus - x) % modulus;
+                product = schoolbook(&product, &[root, BigUint::one()], modulus);
+            }
+            return Tree {
+                product,
+                halves: None,
+            };
+        }
+        let mid = xs.len() / 2;
+        let left = Tree::build(&xs[..mid], modulus);
+        let right = Tree::build(&xs[mid..], modulus);
+        Tree {
+            product: mul(&left.product, &right.product, modulus),
+            halves: Some((mid, Box::new((left, right)))),
+        }
+    }
+
+    /// Appends the values of `f` at `xs`, the points the tree was built on.
+    fn evaluate(&self, f: &[BigUint], xs: &[BigUint], modulus: &BigUint, ys: &mut Vec<BigUint>) {
+        let mut r = rem(f, &self.product, modulus);
+        match &self.halves {
+            None => ys.extend(xs.iter().map(|x| horner(&r, x, modulus))),
+            Some((mid, halves)) => {
+                halves.0.evaluate(&r, &xs[..*mid], modulus, ys);
+                halves.1.evaluate(&r, &xs[*mid..], modulus, ys);
+            }
+        }
+        wipe(&mut r);
+    }
+}
+
+fn horner(f: &[BigUint], x: &BigUint, modulus: &BigUint) -> BigUint {
+    let mut y = BigUint::zero();
+    for coeff in f.iter().rev() {
+        let mut product = y * x + coeff;
+        y = &product % modulus;
+        zeroize_biguint(&mut product);
+    }
+    y
+}
+
+/// `f mod m` for a monic `m`, from the quotient that Newton inversion of
+/// the reversed `m` gives.
+fn rem(f: &[BigUint], m: &[BigUint], modulus: &BigUint) -> Poly {
+    let degree = m.len() - 1;
+    if f.len() <= degree {
+        return f.to_vec();
+    }
+    // rev(f) = rev(q) * rev(m) modulo x^k for the k coefficients of q.
+    let k = f.len() - degree;
+    let reversed: Poly = m.iter().rev().take(k).cloned().collect();
+    let inverse = inverse_series(&reversed, k, modulus);
+    let mut top: Poly = f.iter().rev().take(k).cloned().collect();
+    let mut q = mul(&top, &inverse, modulus);
+    wipe(&mut top);
+    q.truncate(k);
+    q.reverse();
+    let mut qm = mul(&q, m, modulus);
+    wipe(&mut q);
+    let r = f[..degree]
+        .iter()
+        .zip(&qm)
+        .map(|(a, b)| (a + modulus - b) % modulus)
+        .collect();
+    wipe(&mut qm);
+    r
+}
+
+/// The first `k` coefficients of `1 / a` for `a` with constant term 1.
+fn inverse_series(a: &[BigUint], k: usize, modulus: &BigUint) -> Poly {
+    let two = BigUint::from(2u32);
+    let mut g = vec![BigUint::one()];
+    let mut len = 1;
+    while len < k {
+        len = (2 * len).min(k);
+        // g <- g * (2 - a * g), doubling the number of correct terms.
+        let mut error = mul(&a[..a.len().min(len)], &g, modulus);
+        error.truncate(len);
+        for (i, coeff) in error.iter_mut().enumerate() {
+            let constant = if i == 0 { &two } else { &BigUint::ZERO };
+            *coeff = (constant + modulus - &*coeff) % modulus;
+        }
+        g = mul(&g, &error, modulus);
+        g.truncate(len);
+    }
+    g
+}
+
+fn mul(a: &[BigUint], b: &[BigUint], modulus: &BigUint) -> Poly {
+    if a.len().min(b.len()) <= SCHOOLBOOK_MAX {
+        schoolbook(a, b, modulus)
+    } else {
+        kronecker(a, b, modulus)
+    }
+}
+
+fn schoolbook(a: &[BigUint], b: &[BigUint], modulus: &BigUint) -> Poly {
+    if a.is_empty() || b.is_empty() {
+        return Vec::new();
+    }
+    let mut product = vec![BigUint::zero(); a.len() + b.len() - 1];
+    for (i, x) in a.iter().enumerate() {
+        for (j, y) in b.iter().enumerate() {
+            let mut term = x * y;
+            product[i + j] += &term;
+            zeroize_biguint(&mut term);
+        }
+    }
+    for coeff in &mut product {
+        let mut reduced = &*coeff % modulus;
+        core::mem::swap(coeff, &mut reduced);
+        zeroize_biguint(&mut reduced);
+    }
+    product
+}
+
+/// Multiplies by packing both polynomials into integers with one slot per
+/// coefficient, wide enough that no slot of the product overflows.
+fn kronecker(a: &[BigUint], b: &[BigUint], modulus: &BigUint) -> Poly {
+    let terms = a.len().min(b.len()) as u64;
+    let slot_bits = 2 * modulus.bits() + u64::from(u64::BITS - terms.leading_zeros());
+    let words = slot_bits.div_ceil(32) as usize;
+    let mut x = pack(a, words);
+    let mut y = pack(b, words);
+    let mut product = &x * &y;
+    zeroize_biguint(&mut x);
+    zeroize_biguint(&mut y);
+    let mut digits = product.to_u32_digits();
+    zeroize_biguint(&mut product);
+    let coefficients = (0..a.len() + b.len() - 1)
+        .map(|i| {
+            let start = (i * words).min(digits.len());
+            let end = (start + words).min(digits.len());
+            let mut slot = BigUint::from_slice(&digits[start..end]);
+            let coeff = &slot % modulus;
+            zeroize_biguint(&mut slot);
+            coeff
+        })
+        .collect();
+    digits.zeroize();
+    coefficients
+}
+
+fn pack(poly: &[BigUint], words: usize) -> BigUint {
+    let mut digits = vec![0u32; poly.len() * words];
+    for (slot, coeff) in digits.chunks_mut(words).zip(poly) {
+        let mut coeff = coeff.to_u32_digits();
+        slot[..coeff.len()].copy_from_slice(&coeff);
+        coeff.zeroize();
+    }
+    BigUint::new(digits)
+}
+
+fn wipe(poly: &mut Poly) {
+    poly.iter_mut().for_each(zeroize_biguint);
+}
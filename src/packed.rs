@@ -0,0 +1,173 @@
+//! Packed secret sharing (Franklin–Yung).
+//!
+//! `k` secrets are embedded in one polynomial of degree `t + k - 1` as its
+//! values at the points `n + 1..=n + k`, just past the share x-coordinates
+//! `1..=n`. The first `t` shares are drawn at random, which fixes the
+//! polynomial; any `t` shares reveal nothing about the secrets and any
+//! `t + k` shares recover all of them. Each share is a single field
+//! element, so the cost of sharing is amortized over the `k` secrets.
+
+use alloc::vec::Vec;
+
+use num_bigint::{BigUint, RandBigInt};
+use num_traits::Zero;
+use rand::{CryptoRng, RngCore};
+
+use crate::polynomial::map_points;
+use crate::shamir::{check_digest_all, check_share_set, lagrange_coefficient_at};
+use crate::{zeroize_biguint, Fingerprint, ShamirError, Share, ShareSetId};
+
+/// A packed sharing of `secrets` values among `shares` holders with
+/// privacy threshold `privacy`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PackedScheme {
+    secrets: usize,
+    privacy: usize,
+    shares: usize,
+    modulus: BigUint,
+}
+
+impl PackedScheme {
+    /// Requires at least one secret, `privacy >= 1`, `privacy + secrets <=
+    /// shares`, and a modulus above `shares + secrets`.
+    pub fn new(
+        secrets: usize,
+        privacy: usize,
+        shares: usize,
+        modulus: BigUint,
+    ) -> Result<Self, ShamirError> {
+        if secrets == 0 || privacy == 0 || privacy + secrets > shares {
+            return Err(ShamirError::InvalidThreshold {
+                threshold: privacy + secrets,
+                shares,
+            });
+        }
+        if modulus <= BigUint::from((shares + secrets) as u64) {
+            return Err(ShamirError::ModulusTooSmall {
+                modulus,
+                shares: shares + secrets,
+            });
+        }
+        Ok(PackedScheme {
+            secrets,
+            privacy,
+            shares,
+            modulus,
+        })
+    }
+
+    /// Number of secrets packed into each sharing.
+    pub fn secrets(&self) -> usize {
+        self.secrets
+    }
+
+    /// The largest number of shares that reveals nothing.
+    pub fn privacy(&self) -> usize {
+        self.privacy
+    }
+
+    pub fn shares(&self) -> usize {
+        self.shares
+    }
+
+    /// Shares needed to reconstruct, `privacy + secrets`.
+    pub fn threshold(&self) -> usize {
+        self.privacy + self.secrets
+    }
+
+    pub fn modulus(&self) -> &BigUint {
+        &self.modulus
+    }
+
+    /// Shares exactly [`PackedScheme::secrets`] values at once.
+    pub fn split_with_rng<R: RngCore + CryptoRng + ?Sized>(
+        &self,
+        secrets: &[BigUint],
+        rng: &mut R,
+    ) -> Result<Vec<Share>, ShamirError> {
+        if secrets.len() != self.secrets {
+            return Err(ShamirError::NotEnoughShares {
+                required: self.secrets,
+                provided: secrets.len(),
+            });
+        }
+        if secrets.iter().any(|secret| *secret >= self.modulus) {
+            return Err(ShamirError::SecretTooLarge);
+        }
+        let threshold = self.threshold();
+        let fingerprint = Fingerprint::of(&self.modulus);
+        let set_id = ShareSetId::random(rng);
+        let mut xs: Vec<BigUint> = (1..=self.privacy as u64).map(BigUint::from).collect();
+        xs.extend(
+            self.secret_points()
+                .map(|point| BigUint::from(point as u64)),
+        );
+        let mut ys: Vec<BigUint> = (0..self.privacy)
+            .map(|_| rng.gen_biguint_below(&self.modulus))
+            .collect();
+        ys.extend(secrets.iter().cloned());
+
+        let mut result: Vec<Share> = ys[..self.privacy]
+            .iter()
+            .zip(&xs)
+            .map(|(y, x)| {
+                Share::new(x.clone(), y.clone(), threshold, fingerprint).with_set_id(set_id)
+            })
+            .collect();
+        let rest: Vec<BigUint> = (self.privacy as u64 + 1..=self.shares as u64)
+            .map(BigUint::from)
+            .collect();
+        let values = map_points(&rest, |x| interpolate(&xs, &ys, x, &self.modulus));
+        ys.iter_mut().for_each(zeroize_biguint);
+        for (x, y) in rest.into_iter().zip(values) {
+            result.push(Share::new(x, y?, threshold, fingerprint).with_set_id(set_id));
+        }
+        Ok(result)
+    }
+
+    /// Recovers all secrets from at least [`PackedScheme::threshold`] shares,
+    /// checked against their [`SecretDigest::compute_all`] digest if they
+    /// carry one.
+    ///
+    /// [`SecretDigest::compute_all`]: crate::SecretDigest::compute_all
+    pub fn reconstruct(&self, shares: &[Share]) -> Result<Vec<BigUint>, ShamirError> {
+        check_share_set(shares, &self.modulus)?;
+        if shares[0].threshold() != self.threshold() {
+            return Err(ShamirError::ThresholdMismatch);
+        }
+        let last = BigUint::from(self.shares as u64);
+        if shares.iter().any(|share| *share.x() > last) {
+            return Err(ShamirError::MalformedShare(
+                "x-coordinate outside the scheme",
+            ));
+        }
+        let used = &shares[..self.threshold()];
+        let xs: Vec<BigUint> = used.iter().map(|share| share.x().clone()).collect();
+        let mut ys: Vec<BigUint> = used.iter().map(|share| share.y().clone()).collect();
+        let secrets = self
+            .secret_points()
+            .map(|point| interpolate(&xs, &ys, &BigUint::from(point as u64), &self.modulus))
+            .collect::<Result<_, _>>();
+        ys.iter_mut().for_each(zeroize_biguint);
+        check_digest_all(shares, secrets?)
+    }
+
+    fn secret_points(&self) -> core::ops::RangeInclusive<usize> {
+        self.shares + 1..=self.shares + self.secrets
+    }
+}
+
+fn interpolate(
+    xs: &[BigUint],
+    ys: &[BigUint],
+    at: &BigUint,
+    modulus: &BigUint,
+) -> Result<BigUint, ShamirError> {
+    let mut y = BigUint::zero();
+    for (i, value) in ys.iter().enumerate() {
+        let mut term = lagrange_coefficient_at(xs, i, at, modulus)? * value;
+        y = (y + &term) % modulus;
+        zeroize_biguint(&mut term);
+    }
+    Ok(y)
+}
@@ -0,0 +1,84 @@
+//! Length-hiding padding for byte-string secrets.
+//!
+//! The field [`split_bytes`](crate::split_bytes) picks, and the length a
+//! [`ChunkedShare`](crate::ChunkedShare) records, both reveal how long the
+//! secret is. Padding the secret to a fixed or bucketed length first hides
+//! everything but the bucket. The padding is ISO/IEC 7816-4 style, a `0x80`
+//! byte followed by zeros, so it strips off without knowing the target
+//! length.
+
+use alloc::vec::Vec;
+
+use rand::{CryptoRng, RngCore};
+use zeroize::Zeroize;
+
+use crate::{reconstruct_bytes, split_bytes, ShamirError, Share};
+
+/// How far to pad a secret before splitting.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Padding {
+    /// Pads every secret to exactly this many bytes; longer secrets are
+    /// rejected.
+    Fixed(usize),
+    /// Pads to the next power of two, at least 16 bytes.
+    PowerOfTwo,
+}
+
+impl Padding {
+    /// Length of a `len`-byte secret after padding.
+    pub fn padded_len(&self, len: usize) -> Result<usize, ShamirError> {
+        match *self {
+            Padding::Fixed(target) if len < target => Ok(target),
+            Padding::Fixed(_) => Err(ShamirError::SecretTooLarge),
+            Padding::PowerOfTwo => (len + 1)
+                .max(16)
+                .checked_next_power_of_two()
+                .ok_or(ShamirError::SecretTooLarge),
+        }
+    }
+
+    /// Pads `secret`. The padding always adds at least one byte.
+    pub fn pad(&self, secret: &[u8]) -> Result<Vec<u8>, ShamirError> {
+        let target = self.padded_len(secret.len())?;
+        let mut padded = Vec::with_capacity(target);
+        padded.extend_from_slice(secret);
+        padded.push(0x80);
+        padded.resize(target, 0);
+        Ok(padded)
+    }
+}
+
+/// Strips the padding added by [`Padding::pad`] in place.
+pub fn unpad(padded: &mut Vec<u8>) -> Result<(), ShamirError> {
+    let end = padded
+        .iter()
+        .rposition(|&byte| byte != 0)
+        .filter(|&i| padded[i] == 0x80)
+        .ok_or(ShamirError::MalformedShare("invalid padding"))?;
+    padded.truncate(end);
+    Ok(())
+}
+
+/// [`split_bytes`] after padding the secret.
+pub fn split_bytes_padded<R: RngCore + CryptoRng + ?Sized>(
+    secret: &[u8],
+    padding: Padding,
+    threshold: usize,
+    shares: usize,
+    rng: &mut R,
+) -> Result<Vec<Share>, ShamirError> {
+    let mut padded = padding.pad(secret)?;
+    let result = split_bytes(&padded, threshold, shares, rng);
+    padded.zeroize();
+    result
+}
+
+/// Recovers a secret split by [`split_bytes_padded`].
+pub fn reconstruct_bytes_padded(shares: &[Share]) -> Result<Vec<u8>, ShamirError> {
+    let mut secret = reconstruct_bytes(shares)?;
+    if let Err(err) = unpad(&mut secret) {
+        secret.zeroize();
+        return Err(err);
+    }
+    Ok(secret)
+}
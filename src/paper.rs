@@ -0,0 +1,244 @@
+//! Printable paper backups of shares, as plain text or HTML.
+//!
+//! Each sheet carries a title, the share's index and threshold, its set ID
+//! and modulus fingerprint, the share itself as numbered BIP-39 words or a
+//! grouped Bech32m string, a QR code of the Bech32m form, a short checksum
+//! for verifying a transcription, and recovery instructions. The checksum
+//! is the first four bytes of SHA-3-256 over the share's DER encoding, so
+//! it can be recomputed from a retyped share with [`paper_checksum`].
+
+use core::fmt::Write;
+
+use sha3::{Digest, Sha3_256};
+use zeroize::Zeroize;
+
+use crate::qr::qr_blocks;
+use crate::{qr_svg, Bech32Variant, ShamirError, Share};
+
+const DEFAULT_TITLE: &str = "Secret share backup";
+const WORDS_PER_ROW: usize = 4;
+const BECH32_GROUP: usize = 4;
+const BECH32_GROUPS_PER_LINE: usize = 8;
+const RULE_WIDTH: usize = 64;
+
+/// How the share is written out on the sheet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PaperEncoding {
+    /// Numbered BIP-39 words, as read by [`Share::from_mnemonic`].
+    #[default]
+    Mnemonic,
+    /// The Bech32m string in groups of four characters, read by
+    /// [`Share::from_bech32`] once the spaces are removed.
+    Bech32,
+}
+
+/// Transcription checksum printed on paper backups, as `xxxx-xxxx`.
+pub fn paper_checksum(share: &Share) -> String {
+    let mut der = share.to_der();
+    let digest = hex::encode(&Sha3_256::digest(&der)[..4]);
+    der.zeroize();
+    format!("{}-{}", &digest[..4], &digest[4..])
+}
+
+/// A paper backup sheet for one share.
+#[derive(Debug, Clone)]
+pub struct PaperBackup<'a> {
+    share: &'a Share,
+    title: String,
+    encoding: PaperEncoding,
+}
+
+impl<'a> PaperBackup<'a> {
+    pub fn new(share: &'a Share) -> Self {
+        PaperBackup {
+            share,
+            title: DEFAULT_TITLE.into(),
+            encoding: PaperEncoding::default(),
+        }
+    }
+
+    pub fn with_title(mut self, title: impl Into<String>) -> Self {
+        self.title = title.into();
+        self
+    }
+
+    pub fn with_encoding(mut self, encoding: PaperEncoding) -> Self {
+        self.encoding = encoding;
+        self
+    }
+
+    fn set_id(&self) -> String {
+        self.share
+            .set_id()
+            .map_or_else(|| "none".into(), |set_id| set_id.to_string())
+    }
+
+    fn instructions(&self) -> [String; 3] {
+        let threshold = self.share.threshold();
+        let entry = match self.encoding {
+            PaperEncoding::Mnemonic => "the words",
+            PaperEncoding::Bech32 => "the share string without its spaces",
+        };
+        [
+            format!(
+                "Any {threshold} shares with the set ID above reconstruct the secret; \
+                 fewer reveal nothing about it."
+            ),
+            "Store this sheet apart from the other shares and never photograph it.".into(),
+            format!(
+                "To recover, enter {entry} from at least {threshold} sheets, or scan \
+                 their QR codes, and check each checksum before combining."
+            ),
+        ]
+    }
+
+    fn bech32(&self) -> String {
+        self.share.to_bech32(Bech32Variant::Bech32m)
+    }
+
+    /// The share as text lines in the chosen encoding.
+    fn share_lines(&self) -> Vec<String> {
+        match self.encoding {
+            PaperEncoding::Mnemonic => {
+                let mut mnemonic = self.share.to_mnemonic();
+                let words: Vec<&str> = mnemonic.split(' ').collect();
+                let lines = words
+                    .chunks(WORDS_PER_ROW)
+                    .enumerate()
+                    .map(|(row, chunk)| {
+                        let mut line = String::new();
+                        for (i, word) in chunk.iter().enumerate() {
+                            let number = row * WORDS_PER_ROW + i + 1;
+                            let _ = write!(line, "{number:>3}. {word:<10}");
+                        }
+                        line.trim_end().into()
+                    })
+                    .collect();
+                drop(words);
+                mnemonic.zeroize();
+                lines
+            }
+            PaperEncoding::Bech32 => {
+                let mut encoded = self.bech32();
+                let lines = encoded
+                    .as_bytes()
+                    .chunks(BECH32_GROUP * BECH32_GROUPS_PER_LINE)
+                    .map(|line| {
+                        line.chunks(BECH32_GROUP)
+                            .map(|group| core::str::from_utf8(group).expect("bech32 is ASCII"))
+                            .collect::<Vec<_>>()
+                            .join(" ")
+                    })
+                    .collect();
+                encoded.zeroize();
+                lines
+            }
+        }
+    }
+
+    /// The sheet as plain text with a Unicode QR code, dark on light, for a
+    /// monospaced printout.
+    pub fn to_text(&self) -> Result<String, ShamirError> {
+        let mut qr_data = self.share.qr_text();
+        let qr = qr_blocks(&qr_data, false);
+        qr_data.zeroize();
+        let mut qr = qr?;
+
+        let rule = "=".repeat(RULE_WIDTH);
+        let heading = match self.encoding {
+            PaperEncoding::Mnemonic => "Share words",
+            PaperEncoding::Bech32 => "Share string",
+        };
+        let mut text = String::new();
+        let _ = writeln!(text, "{rule}\n{}\n{rule}", self.title);
+        let _ = writeln!(text, "Share index:  {}", self.share.x());
+        let _ = writeln!(text, "Threshold:    {}", self.share.threshold());
+        let _ = writeln!(text, "Set ID:       {}", self.set_id());
+        let _ = writeln!(text, "Modulus:      {}", self.share.fingerprint());
+        let _ = writeln!(text, "Checksum:     {}", paper_checksum(self.share));
+        let _ = writeln!(text, "\n{heading}:\n");
+        for mut line in self.share_lines() {
+            let _ = writeln!(text, "  {line}");
+            line.zeroize();
+        }
+        let _ = writeln!(text, "\nQR code:\n\n{qr}");
+        qr.zeroize();
+        let _ = writeln!(text, "\nRecovery:\n");
+        for (i, step) in self.instructions().iter().enumerate() {
+            let _ = writeln!(text, "  {}. {step}", i + 1);
+        }
+        Ok(text)
+    }
+
+    /// The sheet as a self-contained HTML page with an inline SVG QR code.
+    pub fn to_html(&self) -> Result<String, ShamirError> {
+        let mut qr_data = self.share.qr_text();
+        let svg = qr_svg(&qr_data);
+        qr_data.zeroize();
+        let mut svg = svg?;
+        // Drop the XML declaration when inlining the image.
+        let qr = &svg[svg.find("<svg").unwrap_or(0)..];
+
+        let title = escape_html(&self.title);
+        let mut html = String::new();
+        let _ = write!(
+            html,
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n\
+             <title>{title}</title>\n<style>\n\
+             body {{ font-family: sans-serif; max-width: 44em; margin: 2em auto; }}\n\
+             table td {{ padding: 0.2em 1em 0.2em 0; }}\n\
+             .share {{ font-family: monospace; font-size: 1.1em; line-height: 1.6; }}\n\
+             .qr svg {{ width: 18em; height: 18em; }}\n\
+             @media print {{ body {{ margin: 0; }} }}\n\
+             </style>\n</head>\n<body>\n<h1>{title}</h1>\n<table>\n"
+        );
+        let fields = [
+            ("Share index", self.share.x().to_string()),
+            ("Threshold", self.share.threshold().to_string()),
+            ("Set ID", self.set_id()),
+            ("Modulus", self.share.fingerprint().to_string()),
+            ("Checksum", paper_checksum(self.share)),
+        ];
+        for (name, value) in fields {
+            let _ = writeln!(
+                html,
+                "<tr><td>{name}</td><td><code>{value}</code></td></tr>"
+            );
+        }
+        let heading = match self.encoding {
+            PaperEncoding::Mnemonic => "Share words",
+            PaperEncoding::Bech32 => "Share string",
+        };
+        let _ = writeln!(html, "</table>\n<h2>{heading}</h2>\n<pre class=\"share\">");
+        for mut line in self.share_lines() {
+            let _ = writeln!(html, "{line}");
+            line.zeroize();
+        }
+        let _ = writeln!(
+            html,
+            "</pre>\n<h2>QR code</h2>\n<div class=\"qr\">{qr}</div>"
+        );
+        svg.zeroize();
+        let _ = writeln!(html, "<h2>Recovery</h2>\n<ol>");
+        for step in self.instructions() {
+            let _ = writeln!(html, "<li>{step}</li>");
+        }
+        let _ = write!(html, "</ol>\n</body>\n</html>\n");
+        Ok(html)
+    }
+}
+
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
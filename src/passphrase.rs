@@ -0,0 +1,349 @@
+//! Passphrase-locked shares.
+//!
+//! A locked share keeps its x-coordinate, threshold, modulus fingerprint and
+//! set ID in the clear, so shares can be sorted and matched without their
+//! passphrases, and encrypts the rest of the share, its y value with any
+//! blinding and digest, under XChaCha20-Poly1305. The key is derived from
+//! the passphrase with Argon2id over a random salt. The whole header,
+//! including the Argon2 parameters, salt and nonce, is authenticated, so a
+//! stolen share is useless without its passphrase and an edited header is
+//! rejected like a wrong passphrase.
+//!
+//! The binary form is:
+//!
+//! ```text
+//! "PSHL" | version u8 | flags u8 | x u64 | threshold u32 | fingerprint [8]
+//!        | set id [16]? | memory KiB u32 | iterations u32 | parallelism u32
+//!        | salt [16] | nonce [24] | ciphertext
+//! ```
+//!
+//! An x too large for the `u64` is written in version 2, where `x u64` is
+//! replaced by `x length u16 | x [length]`.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use argon2::{Algorithm, Argon2, Params, Version};
+use chacha20poly1305::aead::{Aead, KeyInit, Payload};
+use chacha20poly1305::{XChaCha20Poly1305, XNonce};
+use num_bigint::BigUint;
+use num_traits::Zero;
+use rand::{CryptoRng, RngCore};
+use zeroize::Zeroize;
+
+use crate::{Fingerprint, ShamirError, Share, ShareSetId};
+
+const LOCKED_MAGIC: &[u8; 4] = b"PSHL";
+const LOCKED_VERSION: u8 = 1;
+const LOCKED_VERSION_WIDE_X: u8 = 2;
+const FLAG_SET_ID: u8 = 1;
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 24;
+/// Upper bound on the memory cost accepted from a locked share, 4 GiB.
+const MAX_MEMORY_KIB: u32 = 4 * 1024 * 1024;
+
+/// Argon2id cost parameters for deriving a share key from a passphrase.
+///
+/// The default is 19 MiB of memory, 2 passes and 1 lane, the minimum
+/// recommended by OWASP for interactive logins; key ceremonies can afford
+/// much more.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Argon2Params {
+    memory_kib: u32,
+    iterations: u32,
+    parallelism: u32,
+}
+
+impl Argon2Params {
+    pub fn new(memory_kib: u32, iterations: u32, parallelism: u32) -> Result<Self, ShamirError> {
+        if memory_kib > MAX_MEMORY_KIB
+            || Params::new(memory_kib, iterations, parallelism, None).is_err()
+        {
+            return Err(ShamirError::InvalidKey("invalid Argon2 parameters"));
+        }
+        Ok(Argon2Params {
+            memory_kib,
+            iterations,
+            parallelism,
+        })
+    }
+
+    pub fn memory_kib(&self) -> u32 {
+        self.memory_kib
+    }
+
+    pub fn iterations(&self) -> u32 {
+        self.iterations
+    }
+
+    pub fn parallelism(&self) -> u32 {
+        self.parallelism
+    }
+
+    fn derive_key(&self, passphrase: &str, salt: &[u8]) -> [u8; 32] {
+        let params = Params::new(self.memory_kib, self.iterations, self.parallelism, Some(32))
+            .expect("parameters are validated");
+        let mut key = [0u8; 32];
+        Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+            .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+            .expect("salt and output lengths are valid");
+        key
+    }
+}
+
+impl Default for Argon2Params {
+    fn default() -> Self {
+        Argon2Params {
+            memory_kib: Params::DEFAULT_M_COST,
+            iterations: Params::DEFAULT_T_COST,
+            parallelism: Params::DEFAULT_P_COST,
+        }
+    }
+}
+
+/// A share whose value is encrypted under a passphrase.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LockedShare {
+    x: BigUint,
+    threshold: usize,
+    fingerprint: Fingerprint,
+    set_id: Option<ShareSetId>,
+    params: Argon2Params,
+    salt: [u8; SALT_LEN],
+    nonce: [u8; NONCE_LEN],
+    ciphertext: Vec<u8>,
+}
+
+impl LockedShare {
+    pub fn x(&self) -> &BigUint {
+        &self.x
+    }
+
+    pub fn threshold(&self) -> usize {
+        self.threshold
+    }
+
+    pub fn fingerprint(&self) -> Fingerprint {
+        self.fingerprint
+    }
+
+    pub fn set_id(&self) -> Option<ShareSetId> {
+        self.set_id
+    }
+
+    pub fn params(&self) -> Argon2Params {
+        self.params
+    }
+
+    /// Everything before the ciphertext, also used as associated data.
+    fn header(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(32 + 16 + 12 + SALT_LEN + NONCE_LEN);
+        out.extend_from_slice(LOCKED_MAGIC);
+        let flags = if self.set_id.is_some() {
+            FLAG_SET_ID
+        } else {
+            0
+        };
+        match u64::try_from(&self.x) {
+            Ok(x) => {
+                out.extend_from_slice(&[LOCKED_VERSION, flags]);
+                out.extend_from_slice(&x.to_be_bytes());
+            }
+            Err(_) => {
+                let x = self.x.to_bytes_be();
+                out.extend_from_slice(&[LOCKED_VERSION_WIDE_X, flags]);
+                out.extend_from_slice(&(x.len() as u16).to_be_bytes());
+                out.extend_from_slice(&x);
+            }
+        }
+        out.extend_from_slice(&(self.threshold as u32).to_be_bytes());
+        out.extend_from_slice(self.fingerprint.as_bytes());
+        if let Some(set_id) = &self.set_id {
+            out.extend_from_slice(set_id.as_bytes());
+        }
+        out.extend_from_slice(&self.params.memory_kib.to_be_bytes());
+        out.extend_from_slice(&self.params.iterations.to_be_bytes());
+        out.extend_from_slice(&self.params.parallelism.to_be_bytes());
+        out.extend_from_slice(&self.salt);
+        out.extend_from_slice(&self.nonce);
+        out
+    }
+
+    /// Decrypts the share. A wrong passphrase or any modification of the
+    /// locked share gives [`ShamirError::WrongPassphrase`].
+    pub fn unlock(&self, passphrase: &str) -> Result<Share, ShamirError> {
+        let mut key = self.params.derive_key(passphrase, &self.salt);
+        let cipher = XChaCha20Poly1305::new(&key.into());
+        key.zeroize();
+        let header = self.header();
+        let mut der = cipher
+            .decrypt(
+                XNonce::from_slice(&self.nonce),
+                Payload {
+                    msg: &self.ciphertext,
+                    aad: &header,
+                },
+            )
+            .map_err(|_| ShamirError::WrongPassphrase(self.x.clone()))?;
+        let share = Share::from_der(&der);
+        der.zeroize();
+        let share = share?;
+        if *share.x() != self.x
+            || share.threshold() != self.threshold
+            || share.fingerprint() != self.fingerprint
+            || share.set_id() != self.set_id
+        {
+            return Err(ShamirError::MalformedShare(
+                "locked share header does not match its contents",
+            ));
+        }
+        Ok(share)
+    }
+
+    /// Encodes the locked share in the binary form described in the module
+    /// documentation.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = self.header();
+        out.extend_from_slice(&self.ciphertext);
+        out
+    }
+
+    /// Decodes a locked share written by [`LockedShare::to_bytes`]. The
+    /// ciphertext is only checked when unlocking.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ShamirError> {
+        let malformed = ShamirError::MalformedShare;
+        let mut rest = bytes;
+        let mut take = |len: usize| {
+            if rest.len() < len {
+                return Err(malformed("truncated locked share"));
+            }
+            let (head, tail) = rest.split_at(len);
+            rest = tail;
+            Ok(head)
+        };
+        if take(4)? != LOCKED_MAGIC {
+            return Err(malformed("not a locked share"));
+        }
+        let header = take(2)?;
+        if header[0] != LOCKED_VERSION && header[0] != LOCKED_VERSION_WIDE_X {
+            return Err(malformed("unsupported locked share version"));
+        }
+        if header[1] & !FLAG_SET_ID != 0 {
+            return Err(malformed("unknown locked share flags"));
+        }
+        let x = if header[0] == LOCKED_VERSION {
+            BigUint::from(u64::from_be_bytes(take(8)?.try_into().expect("8 bytes")))
+        } else {
+            let len = u16::from_be_bytes(take(2)?.try_into().expect("2 bytes")) as usize;
+            BigUint::from_bytes_be(take(len)?)
+        };
+        if x.is_zero() {
+            return Err(malformed("x must be nonzero"));
+        }
+        let threshold = u32::from_be_bytes(take(4)?.try_into().expect("4 bytes")) as usize;
+        let fingerprint = Fingerprint::from_bytes(take(8)?.try_into().expect("8 bytes"));
+        let set_id = if header[1] & FLAG_SET_ID != 0 {
+            Some(ShareSetId::from_bytes(
+                take(16)?.try_into().expect("16 bytes"),
+            ))
+        } else {
+            None
+        };
+        let mut cost =
+            || take(4).map(|bytes| u32::from_be_bytes(bytes.try_into().expect("4 bytes")));
+        let (memory_kib, iterations, parallelism) = (cost()?, cost()?, cost()?);
+        let params = Argon2Params::new(memory_kib, iterations, parallelism)
+            .map_err(|_| malformed("invalid Argon2 parameters"))?;
+        let salt = take(SALT_LEN)?.try_into().expect("16 bytes");
+        let nonce = take(NONCE_LEN)?.try_into().expect("24 bytes");
+        Ok(LockedShare {
+            x,
+            threshold,
+            fingerprint,
+            set_id,
+            params,
+            salt,
+            nonce,
+            ciphertext: rest.to_vec(),
+        })
+    }
+}
+
+impl Share {
+    /// Encrypts the share under `passphrase` with a fresh salt and nonce.
+    pub fn lock_with_rng<R: RngCore + CryptoRng + ?Sized>(
+        &self,
+        passphrase: &str,
+        params: Argon2Params,
+        rng: &mut R,
+    ) -> Result<LockedShare, ShamirError> {
+        if u32::try_from(self.threshold()).is_err() {
+            return Err(ShamirError::MalformedShare(
+                "threshold does not fit the format",
+            ));
+        }
+        if self.x().bits() > 8 * u64::from(u16::MAX) {
+            return Err(ShamirError::MalformedShare("x does not fit the format"));
+        }
+        let mut locked = LockedShare {
+            x: self.x().clone(),
+            threshold: self.threshold(),
+            fingerprint: self.fingerprint(),
+            set_id: self.set_id(),
+            params,
+            salt: [0; SALT_LEN],
+            nonce: [0; NONCE_LEN],
+            ciphertext: Vec::new(),
+        };
+        rng.fill_bytes(&mut locked.salt);
+        rng.fill_bytes(&mut locked.nonce);
+
+        let mut key = params.derive_key(passphrase, &locked.salt);
+        let cipher = XChaCha20Poly1305::new(&key.into());
+        key.zeroize();
+        let header = locked.header();
+        let mut der = self.to_der();
+        let ciphertext = cipher.encrypt(
+            XNonce::from_slice(&locked.nonce),
+            Payload {
+                msg: &der,
+                aad: &header,
+            },
+        );
+        der.zeroize();
+        locked.ciphertext = ciphertext.map_err(|_| ShamirError::SecretTooLarge)?;
+        Ok(locked)
+    }
+}
+
+/// Unlocks each share with the passphrase at the same position.
+pub fn unlock_shares(
+    shares: &[LockedShare],
+    passphrases: &[&str],
+) -> Result<Vec<Share>, ShamirError> {
+    if shares.len() != passphrases.len() {
+        return Err(ShamirError::MissingParameter("one passphrase per share"));
+    }
+    let mut passphrases = passphrases.iter();
+    unlock_shares_with(shares, |_| {
+        Ok::<_, ShamirError>(String::from(*passphrases.next().expect("lengths match")))
+    })
+}
+
+/// Unlocks each share with a passphrase from `passphrase`, which is called
+/// once per share in order, e.g. to prompt the holder of that share. Each
+/// passphrase is wiped after use.
+pub fn unlock_shares_with<E, F>(shares: &[LockedShare], mut passphrase: F) -> Result<Vec<Share>, E>
+where
+    E: From<ShamirError>,
+    F: FnMut(&LockedShare) -> Result<String, E>,
+{
+    let mut unlocked = Vec::with_capacity(shares.len());
+    for share in shares {
+        let mut entered = passphrase(share)?;
+        let result = share.unlock(&entered);
+        entered.zeroize();
+        unlocked.push(result?);
+    }
+    Ok(unlocked)
+}
@@ -0,0 +1,239 @@
+//! Pedersen verifiable secret sharing.
+//!
+//! The secret and a random blinding value are each shared with their own
+//! polynomial over the group order `q`, and every coefficient pair is
+//! published as `g^a * h^b mod p`. Shareholders can then check their share
+//! against the commitments, which reveal nothing about the secret as long as
+//! nobody knows `log_g h`.
+
+use alloc::vec::Vec;
+
+use num_bigint::{BigUint, RandBigInt};
+use num_traits::{One, Zero};
+use rand::{CryptoRng, RngCore};
+use sha3::digest::{ExtendableOutput, Update, XofReader};
+use sha3::Shake256;
+
+use crate::bigint::modpow;
+use crate::polynomial::Polynomial;
+use crate::shamir::check_split_params;
+use crate::{zeroize_biguint, Fingerprint, ShamirError, Share, ShareSetId};
+
+/// A prime-order subgroup of `Z_p^*` with two independent generators.
+///
+/// Shares live in the field of the group order `q`, so `q` is the modulus to
+/// pass to [`shamir_reconstruct`](crate::shamir_reconstruct). Primality of
+/// `p` and `q` is the caller's responsibility.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(try_from = "crate::serde_support::PedersenParamsRepr")
+)]
+pub struct PedersenParams {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::biguint_hex"))]
+    p: BigUint,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::biguint_hex"))]
+    q: BigUint,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::biguint_hex"))]
+    g: BigUint,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::biguint_hex"))]
+    h: BigUint,
+}
+
+impl PedersenParams {
+    /// Checks that `q` divides `p - 1` and that `g` and `h` are distinct
+    /// generators of the order-`q` subgroup.
+    pub fn new(p: BigUint, q: BigUint, g: BigUint, h: BigUint) -> Result<Self, ShamirError> {
+        let one = BigUint::one();
+        if q <= one || p <= q {
+            return Err(ShamirError::InvalidGroup("need 1 < q < p"));
+        }
+        if !((&p - &one) % &q).is_zero() {
+            return Err(ShamirError::InvalidGroup("q does not divide p - 1"));
+        }
+        for generator in [&g, &h] {
+            if *generator <= one || *generator >= p || modpow(generator, &q, &p) != one {
+                return Err(ShamirError::InvalidGroup("generator is not of order q"));
+            }
+        }
+        if g == h {
+            return Err(ShamirError::InvalidGroup("g and h must differ"));
+        }
+        Ok(PedersenParams { p, q, g, h })
+    }
+
+    /// Derives `g` and `h` by hashing into the order-`q` subgroup, so that no
+    /// one knows the discrete log relating them.
+    pub fn derive(p: BigUint, q: BigUint) -> Result<Self, ShamirError> {
+        let g = hash_to_subgroup(&p, &q, b"g")?;
+        let h = hash_to_subgroup(&p, &q, b"h")?;
+        PedersenParams::new(p, q, g, h)
+    }
+
+    pub fn p(&self) -> &BigUint {
+        &self.p
+    }
+
+    /// The group order, which is also the share field modulus.
+    pub fn q(&self) -> &BigUint {
+        &self.q
+    }
+
+    pub fn g(&self) -> &BigUint {
+        &self.g
+    }
+
+    pub fn h(&self) -> &BigUint {
+        &self.h
+    }
+
+    /// `g^value * h^blinding mod p`.
+    pub fn commit(&self, value: &BigUint, blinding: &BigUint) -> BigUint {
+        (modpow(&self.g, value, &self.p) * modpow(&self.h, blinding, &self.p)) % &self.p
+    }
+}
+
+fn hash_to_subgroup(p: &BigUint, q: &BigUint, label: &[u8]) -> Result<BigUint, ShamirError> {
+    if p <= q || q.is_zero() {
+        return Err(ShamirError::InvalidGroup("need 1 < q < p"));
+    }
+    let cofactor = (p - 1u32) / q;
+    let len = p.to_bytes_be().len() + 16;
+    for counter in 0u32..256 {
+        let mut hasher = Shake256::default();
+        hasher.update(b"prime-shamir/pedersen-generator/v1");
+        hasher.update(&p.to_bytes_be());
+        hasher.update(&q.to_bytes_be());
+        hasher.update(label);
+        hasher.update(&counter.to_be_bytes());
+        let mut bytes = alloc::vec![0u8; len];
+        hasher.finalize_xof().read(&mut bytes);
+        let candidate = modpow(&(BigUint::from_bytes_be(&bytes) % p), &cofactor, p);
+        if candidate > BigUint::one() {
+            return Ok(candidate);
+        }
+    }
+    Err(ShamirError::InvalidGroup("no generator found"))
+}
+
+/// Published commitments `C_j = g^a_j * h^b_j mod p` to the coefficients of
+/// the secret and blinding polynomials.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PedersenCommitments {
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "crate::serde_support::biguint_hex_seq")
+    )]
+    commitments: Vec<BigUint>,
+}
+
+impl PedersenCommitments {
+    pub(crate) fn from_commitments(commitments: Vec<BigUint>) -> Self {
+        PedersenCommitments { commitments }
+    }
+
+    pub fn commitments(&self) -> &[BigUint] {
+        &self.commitments
+    }
+
+    /// Multiplies commitment sets of equal length, yielding commitments to
+    /// the sum of the underlying polynomials.
+    pub fn aggregate<'a, I>(sets: I, params: &PedersenParams) -> Result<Self, ShamirError>
+    where
+        I: IntoIterator<Item = &'a PedersenCommitments>,
+    {
+        let mut commitments: Option<Vec<BigUint>> = None;
+        for set in sets {
+            match &mut commitments {
+                None => commitments = Some(set.commitments.clone()),
+                Some(acc) => {
+                    if acc.len() != set.commitments.len() {
+                        return Err(ShamirError::ThresholdMismatch);
+                    }
+                    for (a, c) in acc.iter_mut().zip(&set.commitments) {
+                        *a = (&*a * c) % &params.p;
+                    }
+                }
+            }
+        }
+        let commitments = commitments.ok_or(ShamirError::NotEnoughShares {
+            required: 1,
+            provided: 0,
+        })?;
+        Ok(PedersenCommitments { commitments })
+    }
+
+    /// Checks `g^y * h^blinding == prod C_j^(x^j)` for `share`.
+    #[tracing::instrument(level = "debug", skip_all, fields(x = %share.x()))]
+    pub fn verify(&self, share: &Share, params: &PedersenParams) -> Result<(), ShamirError> {
+        if share.fingerprint() != Fingerprint::of(&params.q) {
+            return Err(ShamirError::ModulusMismatch);
+        }
+        if share.threshold() != self.commitments.len() {
+            return Err(ShamirError::ThresholdMismatch);
+        }
+        let blinding = share
+            .blinding()
+            .ok_or_else(|| ShamirError::MissingBlinding(share.x().clone()))?;
+        let expected = params.commit(share.y(), blinding);
+
+        let x = share.x() % &params.q;
+        let mut power = BigUint::one();
+        let mut actual = BigUint::one();
+        for commitment in &self.commitments {
+            actual = (actual * modpow(commitment, &power, &params.p)) % &params.p;
+            power = (power * &x) % &params.q;
+        }
+        if actual == expected {
+            tracing::debug!("commitment verified for the share at x = {}", share.x());
+            Ok(())
+        } else {
+            tracing::warn!("commitment mismatch for the share at x = {}", share.x());
+            Err(ShamirError::CommitmentMismatch(share.x().clone()))
+        }
+    }
+}
+
+/// Splits `secret` like [`shamir_split_shares`](crate::shamir_split_shares) over the field of
+/// `params.q()`, attaching a blinding share to each share and returning the
+/// Pedersen commitments needed to verify them.
+pub fn pedersen_split_shares<R: RngCore + CryptoRng + ?Sized>(
+    secret: &BigUint,
+    threshold: usize,
+    shares: usize,
+    params: &PedersenParams,
+    rng: &mut R,
+) -> Result<(Vec<Share>, PedersenCommitments), ShamirError> {
+    let q = &params.q;
+    check_split_params(secret, threshold, shares, q)?;
+    let secret_poly = Polynomial::random(secret, threshold - 1, q, rng);
+    let mut blinding_secret = rng.gen_biguint_below(q);
+    let blinding_poly = Polynomial::random(&blinding_secret, threshold - 1, q, rng);
+    zeroize_biguint(&mut blinding_secret);
+
+    let commitments = secret_poly
+        .coefficients()
+        .iter()
+        .zip(blinding_poly.coefficients())
+        .map(|(a, b)| params.commit(a, b))
+        .collect();
+
+    let fingerprint = Fingerprint::of(q);
+    let set_id = ShareSetId::random(rng);
+    let xs: Vec<BigUint> = (1..=shares as u64).map(BigUint::from).collect();
+    let ys = secret_poly.evaluate_all(&xs, q);
+    let blindings = blinding_poly.evaluate_all(&xs, q);
+    let shares = xs
+        .into_iter()
+        .zip(ys)
+        .zip(blindings)
+        .map(|((x, y), blinding)| {
+            Share::new(x, y, threshold, fingerprint)
+                .with_blinding(blinding)
+                .with_set_id(set_id)
+        })
+        .collect();
+    Ok((shares, PedersenCommitments { commitments }))
+}
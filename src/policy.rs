@@ -0,0 +1,456 @@
+//! General monotone access structures as AND/OR/threshold trees.
+//!
+//! Every `k`-of-`n` node shares its value among its children with a
+//! degree-`k - 1` polynomial, starting from the secret at the root, so each
+//! leaf ends up holding one field element. An AND node is the `n`-of-`n`
+//! case and an OR node the `1`-of-`n` case, where every child receives the
+//! node's value unchanged.
+//!
+//! The textual form accepts party names, `AND`, `OR`, parentheses and
+//! `k-of(a, b, ...)`, with AND binding tighter than OR:
+//!
+//! ```text
+//! (2-of(alice, bob, carol) AND legal) OR ceo
+//! ```
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+use core::str::FromStr;
+
+use num_bigint::BigUint;
+use num_traits::Zero;
+use rand::{CryptoRng, RngCore};
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+use crate::polynomial::Polynomial;
+use crate::shamir::lagrange_coefficient;
+use crate::{zeroize_biguint, Fingerprint, ShamirError};
+
+/// An access policy over named parties.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Policy {
+    /// A single party, identified by name.
+    Party(String),
+    /// Satisfied when at least `k` of the children are.
+    Threshold(usize, Vec<Policy>),
+}
+
+/// The value held by a party for one leaf of a [`Policy`].
+///
+/// A party named at several leaves receives one share per leaf; `path`
+/// lists the child indices leading from the root to the leaf.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PolicyShare {
+    party: String,
+    path: Vec<usize>,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::biguint_hex"))]
+    y: BigUint,
+    fingerprint: Fingerprint,
+}
+
+impl PolicyShare {
+    pub fn new(party: String, path: Vec<usize>, y: BigUint, fingerprint: Fingerprint) -> Self {
+        PolicyShare {
+            party,
+            path,
+            y,
+            fingerprint,
+        }
+    }
+
+    pub fn party(&self) -> &str {
+        &self.party
+    }
+
+    pub fn path(&self) -> &[usize] {
+        &self.path
+    }
+
+    pub fn y(&self) -> &BigUint {
+        &self.y
+    }
+
+    pub fn fingerprint(&self) -> Fingerprint {
+        self.fingerprint
+    }
+}
+
+impl Zeroize for PolicyShare {
+    fn zeroize(&mut self) {
+        zeroize_biguint(&mut self.y);
+    }
+}
+
+impl Drop for PolicyShare {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl ZeroizeOnDrop for PolicyShare {}
+
+impl Policy {
+    pub fn party(name: &str) -> Self {
+        Policy::Party(name.to_string())
+    }
+
+    /// All of `children` must be satisfied.
+    pub fn and(children: Vec<Policy>) -> Self {
+        Policy::Threshold(children.len(), children)
+    }
+
+    /// Any one of `children` must be satisfied.
+    pub fn or(children: Vec<Policy>) -> Self {
+        Policy::Threshold(1, children)
+    }
+
+    /// At least `k` of `children` must be satisfied.
+    pub fn threshold(k: usize, children: Vec<Policy>) -> Result<Self, ShamirError> {
+        let policy = Policy::Threshold(k, children);
+        policy.validate()?;
+        Ok(policy)
+    }
+
+    /// Rejects empty names, empty child lists and thresholds outside
+    /// `1..=children`.
+    pub fn validate(&self) -> Result<(), ShamirError> {
+        match self {
+            Policy::Party(name) if name.is_empty() => Err(ShamirError::InvalidAccessStructure(
+                "party names must not be empty",
+            )),
+            Policy::Party(_) => Ok(()),
+            Policy::Threshold(k, children) => {
+                if *k == 0 || *k > children.len() {
+                    return Err(ShamirError::InvalidAccessStructure(
+                        "a threshold node needs 1 <= k <= children",
+                    ));
+                }
+                children.iter().try_for_each(Policy::validate)
+            }
+        }
+    }
+
+    /// Whether the named parties together satisfy the policy.
+    pub fn is_satisfied(&self, parties: &[&str]) -> bool {
+        match self {
+            Policy::Party(name) => parties.contains(&name.as_str()),
+            Policy::Threshold(k, children) => {
+                children
+                    .iter()
+                    .filter(|child| child.is_satisfied(parties))
+                    .count()
+                    >= *k
+            }
+        }
+    }
+
+    /// Splits `secret` into one [`PolicyShare`] per leaf of the policy.
+    pub fn split_with_rng<R: RngCore + CryptoRng + ?Sized>(
+        &self,
+        secret: &BigUint,
+        modulus: &BigUint,
+        rng: &mut R,
+    ) -> Result<Vec<PolicyShare>, ShamirError> {
+        self.validate()?;
+        let widest = self.widest();
+        if *modulus <= BigUint::from(widest as u64) {
+            return Err(ShamirError::ModulusTooSmall {
+                modulus: modulus.clone(),
+                shares: widest,
+            });
+        }
+        if secret >= modulus {
+            return Err(ShamirError::SecretTooLarge);
+        }
+        let fingerprint = Fingerprint::of(modulus);
+        let mut shares = Vec::new();
+        let mut path = Vec::new();
+        self.deal(secret, modulus, fingerprint, rng, &mut path, &mut shares);
+        Ok(shares)
+    }
+
+    /// Recovers the secret from the shares of a satisfying set of parties.
+    ///
+    /// Shares from other leaves or parties are ignored; a set that does not
+    /// satisfy the policy yields [`ShamirError::Unauthorized`].
+    pub fn reconstruct(
+        &self,
+        shares: &[PolicyShare],
+        modulus: &BigUint,
+    ) -> Result<BigUint, ShamirError> {
+        self.validate()?;
+        let fingerprint = Fingerprint::of(modulus);
+        if shares.iter().any(|share| share.fingerprint != fingerprint) {
+            return Err(ShamirError::ModulusMismatch);
+        }
+        let mut path = Vec::new();
+        self.recover(shares, modulus, &mut path)?
+            .ok_or(ShamirError::Unauthorized)
+    }
+
+    fn widest(&self) -> usize {
+        match self {
+            Policy::Party(_) => 0,
+            Policy::Threshold(_, children) => children
+                .iter()
+                .map(Policy::widest)
+                .fold(children.len(), usize::max),
+        }
+    }
+
+    fn deal<R: RngCore + CryptoRng + ?Sized>(
+        &self,
+        value: &BigUint,
+        modulus: &BigUint,
+        fingerprint: Fingerprint,
+        rng: &mut R,
+        path: &mut Vec<usize>,
+        shares: &mut Vec<PolicyShare>,
+    ) {
+        match self {
+            Policy::Party(name) => {
+                shares.push(PolicyShare::new(
+                    name.clone(),
+                    path.clone(),
+                    value.clone(),
+                    fingerprint,
+                ));
+            }
+            Policy::Threshold(k, children) => {
+                let polynomial = Polynomial::random(value, k - 1, modulus, rng);
+                for (i, child) in children.iter().enumerate() {
+                    let mut y = polynomial.evaluate(&BigUint::from(i as u64 + 1), modulus);
+                    path.push(i);
+                    child.deal(&y, modulus, fingerprint, rng, path, shares);
+                    path.pop();
+                    zeroize_biguint(&mut y);
+                }
+            }
+        }
+    }
+
+    fn recover(
+        &self,
+        shares: &[PolicyShare],
+        modulus: &BigUint,
+        path: &mut Vec<usize>,
+    ) -> Result<Option<BigUint>, ShamirError> {
+        match self {
+            Policy::Party(name) => Ok(shares
+                .iter()
+                .find(|share| share.party == *name && share.path == *path)
+                .map(|share| share.y.clone())),
+            Policy::Threshold(k, children) => {
+                let mut xs = Vec::with_capacity(*k);
+                let mut ys = Vec::with_capacity(*k);
+                for (i, child) in children.iter().enumerate() {
+                    if xs.len() == *k {
+                        break;
+                    }
+                    path.push(i);
+                    let value = child.recover(shares, modulus, path);
+                    path.pop();
+                    if let Some(y) = value? {
+                        xs.push(BigUint::from(i as u64 + 1));
+                        ys.push(y);
+                    }
+                }
+                if xs.len() < *k {
+                    ys.iter_mut().for_each(zeroize_biguint);
+                    return Ok(None);
+                }
+                let mut value = BigUint::zero();
+                for (i, y) in ys.iter_mut().enumerate() {
+                    let mut term = lagrange_coefficient(&xs, i, modulus)? * &*y;
+                    value = (value + &term) % modulus;
+                    zeroize_biguint(&mut term);
+                    zeroize_biguint(y);
+                }
+                Ok(Some(value))
+            }
+        }
+    }
+}
+
+impl fmt::Display for Policy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Policy::Party(name) => f.write_str(name),
+            Policy::Threshold(k, children) => {
+                let separator = if children.len() < 2 {
+                    None
+                } else if *k == children.len() {
+                    Some(" AND ")
+                } else if *k == 1 {
+                    Some(" OR ")
+                } else {
+                    None
+                };
+                match separator {
+                    Some(_) => f.write_str("(")?,
+                    None => write!(f, "{k}-of(")?,
+                }
+                for (i, child) in children.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(separator.unwrap_or(", "))?;
+                    }
+                    child.fmt(f)?;
+                }
+                f.write_str(")")
+            }
+        }
+    }
+}
+
+impl FromStr for Policy {
+    type Err = ShamirError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let tokens = tokenize(s)?;
+        let mut parser = Parser { tokens, pos: 0 };
+        let policy = parser.or_expr()?;
+        if parser.pos != parser.tokens.len() {
+            return Err(ShamirError::InvalidAccessStructure(
+                "unexpected trailing input in policy",
+            ));
+        }
+        policy.validate()?;
+        Ok(policy)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Word(String),
+    Open,
+    Close,
+    Comma,
+}
+
+fn tokenize(s: &str) -> Result<Vec<Token>, ShamirError> {
+    let mut tokens = Vec::new();
+    let mut chars = s.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        match c {
+            c if c.is_whitespace() => {
+                chars.next();
+            }
+            '(' | ')' | ',' => {
+                chars.next();
+                tokens.push(match c {
+                    '(' => Token::Open,
+                    ')' => Token::Close,
+                    _ => Token::Comma,
+                });
+            }
+            c if is_word_char(c) => {
+                let mut word = String::new();
+                while let Some(&c) = chars.peek().filter(|&&c| is_word_char(c)) {
+                    word.push(c);
+                    chars.next();
+                }
+                tokens.push(Token::Word(word));
+            }
+            _ => {
+                return Err(ShamirError::InvalidAccessStructure(
+                    "unexpected character in policy",
+                ))
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || matches!(c, '_' | '-' | '.' | '@')
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn or_expr(&mut self) -> Result<Policy, ShamirError> {
+        let mut children = alloc::vec![self.and_expr()?];
+        while self.keyword("OR") {
+            children.push(self.and_expr()?);
+        }
+        Ok(collapse(Policy::or(children)))
+    }
+
+    fn and_expr(&mut self) -> Result<Policy, ShamirError> {
+        let mut children = alloc::vec![self.atom()?];
+        while self.keyword("AND") {
+            children.push(self.atom()?);
+        }
+        Ok(collapse(Policy::and(children)))
+    }
+
+    fn atom(&mut self) -> Result<Policy, ShamirError> {
+        match self.tokens.get(self.pos).cloned() {
+            Some(Token::Open) => {
+                self.pos += 1;
+                let policy = self.or_expr()?;
+                self.expect(Token::Close)?;
+                Ok(policy)
+            }
+            Some(Token::Word(word)) => {
+                self.pos += 1;
+                if let Some(k) = word.strip_suffix("-of") {
+                    let k = k.parse().map_err(|_| {
+                        ShamirError::InvalidAccessStructure("invalid threshold in policy")
+                    })?;
+                    self.expect(Token::Open)?;
+                    let mut children = alloc::vec![self.or_expr()?];
+                    while self.tokens.get(self.pos) == Some(&Token::Comma) {
+                        self.pos += 1;
+                        children.push(self.or_expr()?);
+                    }
+                    self.expect(Token::Close)?;
+                    return Ok(Policy::Threshold(k, children));
+                }
+                if word.eq_ignore_ascii_case("AND") || word.eq_ignore_ascii_case("OR") {
+                    return Err(ShamirError::InvalidAccessStructure(
+                        "operator without operand in policy",
+                    ));
+                }
+                Ok(Policy::Party(word))
+            }
+            _ => Err(ShamirError::InvalidAccessStructure(
+                "expected a party or subexpression in policy",
+            )),
+        }
+    }
+
+    fn keyword(&mut self, keyword: &str) -> bool {
+        match self.tokens.get(self.pos) {
+            Some(Token::Word(word)) if word.eq_ignore_ascii_case(keyword) => {
+                self.pos += 1;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn expect(&mut self, token: Token) -> Result<(), ShamirError> {
+        if self.tokens.get(self.pos) == Some(&token) {
+            self.pos += 1;
+            Ok(())
+        } else {
+            Err(ShamirError::InvalidAccessStructure(
+                "unbalanced parentheses in policy",
+            ))
+        }
+    }
+}
+
+/// Replaces a single-child AND/OR node with its child.
+fn collapse(policy: Policy) -> Policy {
+    match policy {
+        Policy::Threshold(_, mut children) if children.len() == 1 => children.remove(0),
+        policy => policy,
+    }
+}
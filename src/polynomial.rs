@@ -0,0 +1,149 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+use num_bigint::{BigUint, RandBigInt};
+use num_traits::Zero;
+use rand::{CryptoRng, RngCore};
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+use crate::montgomery::MontgomeryModulus;
+use crate::multipoint;
+use crate::zeroize_biguint;
+
+/// A polynomial over the prime field, lowest-degree coefficient first.
+///
+/// Coefficients are wiped on drop since the constant term is the secret.
+pub(crate) struct Polynomial {
+    coefficients: Vec<BigUint>,
+}
+
+impl Polynomial {
+    /// Random polynomial of the given degree with `constant` as its value at zero.
+    pub(crate) fn random<R: RngCore + CryptoRng + ?Sized>(
+        constant: &BigUint,
+        degree: usize,
+        modulus: &BigUint,
+        rng: &mut R,
+    ) -> Self {
+        let mut coefficients = Vec::with_capacity(degree + 1);
+        coefficients.push(constant.clone());
+        for _ in 0..degree {
+            coefficients.push(rng.gen_biguint_below(modulus));
+        }
+        Polynomial { coefficients }
+    }
+
+    /// Polynomial with the given coefficients, lowest degree first.
+    pub(crate) fn from_coefficients(coefficients: Vec<BigUint>) -> Self {
+        Polynomial { coefficients }
+    }
+
+    pub(crate) fn coefficients(&self) -> &[BigUint] {
+        &self.coefficients
+    }
+
+    /// Evaluates at `x` by Horner's rule, on fixed-width Montgomery limbs
+    /// when the modulus is odd.
+    ///
+    /// With an odd modulus, the products and sums take time that depends
+    /// only on the modulus size. Coefficients are loaded into limbs without
+    /// dividing them, which reveals only how many limbs each occupies as a
+    /// `BigUint`. Coefficients that are not below the modulus are reduced
+    /// first, in variable time, but every polynomial the crate builds has
+    /// reduced ones. The result is returned as a `BigUint`, whose length
+    /// depends on its value. Even moduli take plain `num-bigint` arithmetic,
+    /// which is not constant-time.
+    pub(crate) fn evaluate(&self, x: &BigUint, modulus: &BigUint) -> BigUint {
+        let Some(field) = MontgomeryModulus::new(modulus) else {
+            return self.evaluate_plain(x, modulus);
+        };
+        // The Montgomery product of a plain value with x in Montgomery form
+        // is plain, so the accumulator never needs converting back.
+        let x = field.to_montgomery(&(x % modulus));
+        let mut y = vec![0u64; field.width()];
+        for coeff in self.coefficients.iter().rev() {
+            let mut coeff = field.load(coeff).unwrap_or_else(|| {
+                let mut reduced = coeff % modulus;
+                let limbs = field.to_limbs(&reduced);
+                zeroize_biguint(&mut reduced);
+                limbs
+            });
+            let mut product = field.mul(&y, &x);
+            let mut sum = field.add(&product, &coeff);
+            core::mem::swap(&mut y, &mut sum);
+            coeff.zeroize();
+            product.zeroize();
+            sum.zeroize();
+        }
+        let value = MontgomeryModulus::to_biguint(&y);
+        y.zeroize();
+        value
+    }
+
+    /// Evaluates at every point of `xs`, in parallel with the `parallel`
+    /// feature. Polynomials of high degree go through the subproduct trees
+    /// of [`multipoint`](crate::multipoint) instead when there are at least
+    /// as many points as coefficients.
+    pub(crate) fn evaluate_all(&self, xs: &[BigUint], modulus: &BigUint) -> Vec<BigUint> {
+        if multipoint::is_worthwhile(self.coefficients.len(), xs.len()) {
+            return multipoint::evaluate(&self.coefficients, xs, modulus);
+        }
+        map_points(xs, |x| self.evaluate(x, modulus))
+    }
+
+    fn evaluate_plain(&self, x: &BigUint, modulus: &BigUint) -> BigUint {
+        let x = x % modulus;
+        let mut y = BigUint::zero();
+        for coeff in self.coefficients.iter().rev() {
+            let mut product = y * &x + coeff;
+            y = &product % modulus;
+            zeroize_biguint(&mut product);
+        }
+        y
+    }
+}
+
+/// Below this many points a split stays on the calling thread, where it
+/// finishes before rayon's pool would have handed out the work.
+#[cfg(feature = "parallel")]
+const PARALLEL_MIN_POINTS: usize = 32;
+
+/// Maps `f` over `xs`, spreading the points over rayon's global pool with
+/// the `parallel` feature unless there are few points or the pool has a
+/// single thread. The results keep the order of `xs`.
+#[cfg(feature = "parallel")]
+pub(crate) fn map_points<T, F>(xs: &[BigUint], f: F) -> Vec<T>
+where
+    T: Send,
+    F: Fn(&BigUint) -> T + Sync + Send,
+{
+    use rayon::prelude::*;
+
+    if xs.len() < PARALLEL_MIN_POINTS || rayon::current_num_threads() == 1 {
+        return xs.iter().map(f).collect();
+    }
+    xs.par_iter().map(f).collect()
+}
+
+/// Maps `f` over `xs` on the calling thread.
+#[cfg(not(feature = "parallel"))]
+pub(crate) fn map_points<T, F: Fn(&BigUint) -> T>(xs: &[BigUint], f: F) -> Vec<T> {
+    xs.iter().map(f).collect()
+}
+
+impl Zeroize for Polynomial {
+    fn zeroize(&mut self) {
+        for coeff in &mut self.coefficients {
+            zeroize_biguint(coeff);
+        }
+        self.coefficients.clear();
+    }
+}
+
+impl Drop for Polynomial {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl ZeroizeOnDrop for Polynomial {}
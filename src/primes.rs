@@ -0,0 +1,623 @@
+mod blum;
+mod evidence;
+mod fips;
+mod lucas;
+#[cfg(feature = "parallel")]
+mod parallel;
+mod provable;
+mod rounds;
+pub mod standard;
+mod strong;
+
+use alloc::vec;
+use core::ops::ControlFlow;
+
+use num_bigint::{BigUint, RandBigInt};
+use num_traits::{One, ToPrimitive, Zero};
+use rand::{CryptoRng, RngCore};
+#[cfg(feature = "std")]
+use std::time::{Duration, Instant};
+
+use crate::bigint::modpow;
+use crate::ShamirError;
+
+pub use blum::{generate_blum_integer, generate_blum_prime, BlumInteger};
+pub use evidence::{is_prime, CompositeWitness, Primality};
+pub use fips::{generate_fips186_rsa_primes, Fips186Primes};
+pub use lucas::is_strong_lucas_prp;
+#[cfg(feature = "parallel")]
+pub use parallel::{generate_large_prime_parallel, generate_primes_parallel};
+pub use provable::{
+    generate_certified_prime, generate_provable_prime, verify_certificate, PocklingtonStep,
+    PrimalityCertificate,
+};
+pub use rounds::PrimalityConfig;
+pub use strong::generate_strong_prime;
+
+/// Generates a random probable prime of at most `bits` bits.
+///
+/// A random odd start is drawn from `rng` and the odd numbers after it are
+/// sieved by the first 2048 primes. The first survivor that passes the
+/// Baillie–PSW test and then the Miller–Rabin rounds of the default
+/// [`PrimalityConfig`] is returned, so primes that follow long gaps are
+/// somewhat more likely. Fails for `bits < 2`, where no odd prime fits.
+pub fn generate_large_prime<R: RngCore + CryptoRng + ?Sized>(
+    bits: usize,
+    rng: &mut R,
+) -> Result<BigUint, ShamirError> {
+    generate_large_prime_with_config(bits, &PrimalityConfig::default(), rng)
+}
+
+/// Like [`generate_large_prime`], with the Miller–Rabin rounds chosen by
+/// `config`.
+pub fn generate_large_prime_with_config<R: RngCore + CryptoRng + ?Sized>(
+    bits: usize,
+    config: &PrimalityConfig,
+    rng: &mut R,
+) -> Result<BigUint, ShamirError> {
+    search_prime(bits, config, rng, |_| ControlFlow::Continue(())).map(unstoppable)
+}
+
+/// A snapshot of a running prime search.
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PrimeProgress {
+    candidates: u64,
+    elapsed: Duration,
+}
+
+#[cfg(feature = "std")]
+impl PrimeProgress {
+    /// Candidates tested so far, including the current one.
+    pub fn candidates(&self) -> u64 {
+        self.candidates
+    }
+
+    /// Time since the search started.
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+}
+
+/// Like [`generate_large_prime`], calling `progress` after each candidate
+/// is tested, e.g. to drive a progress indicator for large sizes.
+#[cfg(feature = "std")]
+pub fn generate_large_prime_with_progress<R, F>(
+    bits: usize,
+    rng: &mut R,
+    mut progress: F,
+) -> Result<BigUint, ShamirError>
+where
+    R: RngCore + CryptoRng + ?Sized,
+    F: FnMut(PrimeProgress),
+{
+    let start = Instant::now();
+    search_prime(bits, &PrimalityConfig::default(), rng, |candidates| {
+        progress(PrimeProgress {
+            candidates,
+            elapsed: start.elapsed(),
+        });
+        ControlFlow::Continue(())
+    })
+    .map(unstoppable)
+}
+
+/// Like [`generate_large_prime`], checking `cancelled` after each
+/// candidate is tested and failing with [`ShamirError::Cancelled`] once it
+/// returns `true`.
+///
+/// `cancelled` can poll an `AtomicBool` set by another thread, a GUI's
+/// cancel button or a clock, so long searches can be stopped cleanly.
+pub fn generate_large_prime_cancellable<R, F>(
+    bits: usize,
+    rng: &mut R,
+    mut cancelled: F,
+) -> Result<BigUint, ShamirError>
+where
+    R: RngCore + CryptoRng + ?Sized,
+    F: FnMut() -> bool,
+{
+    search_prime(bits, &PrimalityConfig::default(), rng, |_| {
+        if cancelled() {
+            ControlFlow::Break(())
+        } else {
+            ControlFlow::Continue(())
+        }
+    })?
+    .ok_or(ShamirError::Cancelled)
+}
+
+/// Like [`generate_large_prime`], failing with [`ShamirError::TimedOut`]
+/// if no prime is found within `timeout`.
+#[cfg(feature = "std")]
+pub fn generate_large_prime_with_timeout<R: RngCore + CryptoRng + ?Sized>(
+    bits: usize,
+    rng: &mut R,
+    timeout: Duration,
+) -> Result<BigUint, ShamirError> {
+    let start = Instant::now();
+    generate_large_prime_cancellable(bits, rng, || start.elapsed() >= timeout).map_err(|err| {
+        match err {
+            ShamirError::Cancelled => ShamirError::TimedOut,
+            err => err,
+        }
+    })
+}
+
+/// Generates `count` random probable primes of at most `bits` bits, one
+/// per call to [`Iterator::next`].
+///
+/// Each prime comes from its own random starting point rather than the
+/// next survivor in the same sieve window: primes found close together
+/// would make an RSA modulus built from them easy to factor. Small sizes
+/// can repeat a prime. Fails for `bits < 2`.
+pub fn generate_primes<R: RngCore + CryptoRng + ?Sized>(
+    bits: usize,
+    count: usize,
+    rng: &mut R,
+) -> Result<Primes<'_, R>, ShamirError> {
+    if bits < 2 {
+        return Err(ShamirError::InvalidPrimeSize(bits));
+    }
+    Ok(Primes {
+        bits,
+        remaining: count,
+        rng,
+    })
+}
+
+/// The primes of [`generate_primes`], found as they are asked for.
+#[derive(Debug)]
+pub struct Primes<'a, R: ?Sized> {
+    bits: usize,
+    remaining: usize,
+    rng: &'a mut R,
+}
+
+impl<R: RngCore + CryptoRng + ?Sized> Iterator for Primes<'_, R> {
+    type Item = BigUint;
+
+    fn next(&mut self) -> Option<BigUint> {
+        self.remaining = self.remaining.checked_sub(1)?;
+        let config = PrimalityConfig::default();
+        let prime = search_prime(self.bits, &config, self.rng, |_| ControlFlow::Continue(()))
+            .expect("size checked when the batch was made");
+        Some(unstoppable(prime))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<R: RngCore + CryptoRng + ?Sized> ExactSizeIterator for Primes<'_, R> {}
+
+/// Unwraps the result of a search whose callback never breaks.
+fn unstoppable(prime: Option<BigUint>) -> BigUint {
+    prime.expect("the search is only stopped by its callback")
+}
+
+/// Sizes up to which candidates are drawn one at a time rather than
+/// sieved, since a window could run past the small primes themselves.
+const MAX_UNSIEVED_BITS: usize = 32;
+
+/// Draws candidates until one passes Baillie–PSW and the rounds `config`
+/// gives its size, calling `tested` with the running count after each.
+/// Returns `None` as soon as `tested` breaks.
+///
+/// Above [`MAX_UNSIEVED_BITS`], each random odd start is followed by a
+/// window of `2 * bits` consecutive odd numbers. The window is sieved by
+/// [`SMALL_PRIMES`] in one pass, and only the survivors are tested.
+#[tracing::instrument(level = "debug", skip(config, rng, tested))]
+fn search_prime<R, F>(
+    bits: usize,
+    config: &PrimalityConfig,
+    rng: &mut R,
+    mut tested: F,
+) -> Result<Option<BigUint>, ShamirError>
+where
+    R: RngCore + CryptoRng + ?Sized,
+    F: FnMut(u64) -> ControlFlow<()>,
+{
+    if bits < 2 {
+        return Err(ShamirError::InvalidPrimeSize(bits));
+    }
+    tracing::debug!("searching for a prime of up to {bits} bits");
+    let mut candidates = 0u64;
+    let mut test = |candidate: &BigUint, sieved: bool, rng: &mut R| {
+        // Sieving stands in for trial division, except for the rare start
+        // small enough for trial division to decide on its own.
+        let prime = if sieved && candidate.bits() > 64 {
+            passes_baillie_psw(candidate)
+        } else {
+            is_baillie_psw_prime(candidate)
+        } && is_probably_prime(candidate, config.rounds_for(candidate.bits()), rng);
+        candidates += 1;
+        let flow = tested(candidates);
+        if prime {
+            tracing::debug!(
+                "found a {}-bit prime after {candidates} candidates",
+                candidate.bits()
+            );
+            return Some(true);
+        }
+        tracing::trace!("candidate {candidates} is composite");
+        match flow {
+            ControlFlow::Continue(()) => Some(false),
+            ControlFlow::Break(()) => None,
+        }
+    };
+
+    if bits <= MAX_UNSIEVED_BITS {
+        loop {
+            let candidate = rng.gen_biguint(bits as u64) | BigUint::one();
+            match test(&candidate, false, rng) {
+                Some(true) => return Ok(Some(candidate)),
+                Some(false) => {}
+                None => return Ok(None),
+            }
+        }
+    }
+
+    let max = (BigUint::one() << bits) - 1u32;
+    let mut window = vec![true; 2 * bits];
+    loop {
+        let start = rng.gen_biguint(bits as u64) | BigUint::one();
+        window.fill(true);
+        for p in SMALL_PRIMES.iter().skip(1).map(|&p| u64::from(p)) {
+            strike(&mut window, residue(&start, p), 0, p);
+        }
+        for (i, _) in window.iter().enumerate().filter(|(_, &open)| open) {
+            let candidate = &start + 2 * i as u64;
+            if candidate > max {
+                break;
+            }
+            match test(&candidate, true, rng) {
+                Some(true) => return Ok(Some(candidate)),
+                Some(false) => {}
+                None => return Ok(None),
+            }
+        }
+    }
+}
+
+/// Generates a random safe prime `p = 2q + 1`, with `q` also prime, of
+/// exactly `bits` bits.
+///
+/// Safe primes give the prime-order subgroups used by discrete-log
+/// commitments and classic Diffie–Hellman. Candidates for `q` are sieved so
+/// that neither `q` nor `2q + 1` has a small factor, and both must pass the
+/// same tests as [`generate_large_prime`]. Safe primes are rare, so large
+/// sizes still take a while. Fails for `bits < 3`, below the smallest safe
+/// prime, 5.
+pub fn generate_safe_prime<R: RngCore + CryptoRng + ?Sized>(
+    bits: usize,
+    rng: &mut R,
+) -> Result<BigUint, ShamirError> {
+    if bits < 3 {
+        return Err(ShamirError::InvalidPrimeSize(bits));
+    }
+    search_safe_prime(bits, None, rng).map(|(_, p)| p)
+}
+
+/// Generates a random Sophie Germain prime `q` of exactly `bits` bits,
+/// returned with its safe prime `2q + 1`.
+///
+/// This is [`generate_safe_prime`] seen from the other side, for deriving
+/// group parameters. At most `max_candidates` values of `q` that survive
+/// the sieve are tested before giving up with
+/// [`ShamirError::SearchExhausted`]. The number needed grows with the
+/// square of `bits` and varies widely between runs; at 512 bits it averages
+/// a few hundred. Fails for `bits < 2`, below the smallest one, 2.
+pub fn generate_sophie_germain<R: RngCore + CryptoRng + ?Sized>(
+    bits: usize,
+    max_candidates: u64,
+    rng: &mut R,
+) -> Result<(BigUint, BigUint), ShamirError> {
+    if bits < 2 {
+        return Err(ShamirError::InvalidPrimeSize(bits));
+    }
+    search_safe_prime(bits + 1, Some(max_candidates), rng)
+}
+
+/// Finds `q` and `p = 2q + 1`, both prime, with `p` of `bits >= 3` bits,
+/// testing at most `budget` candidates for `q`.
+#[tracing::instrument(level = "debug", skip(rng))]
+fn search_safe_prime<R: RngCore + CryptoRng + ?Sized>(
+    bits: usize,
+    budget: Option<u64>,
+    rng: &mut R,
+) -> Result<(BigUint, BigUint), ShamirError> {
+    tracing::debug!("searching for a {bits}-bit safe prime");
+    let top = BigUint::one() << (bits - 2);
+    let mut candidates = 0u64;
+    let mut spend = || {
+        candidates += 1;
+        match budget {
+            Some(budget) if candidates > budget => {
+                tracing::debug!("gave up on a {bits}-bit safe prime after {budget} candidates");
+                Err(ShamirError::SearchExhausted(budget))
+            }
+            _ => Ok(candidates),
+        }
+    };
+    let config = PrimalityConfig::default();
+    let safe = |q: &BigUint, rng: &mut R| {
+        let p: BigUint = (q << 1) + 1u32;
+        is_probably_prime(q, config.rounds_for(q.bits()), rng)
+            && is_probably_prime(&p, config.rounds_for(p.bits()), rng)
+    };
+
+    if bits <= MAX_UNSIEVED_BITS {
+        loop {
+            spend()?;
+            let q = rng.gen_biguint(bits as u64 - 2) | &top;
+            let p: BigUint = (&q << 1) + 1u32;
+            if is_baillie_psw_prime(&q) && is_baillie_psw_prime(&p) && safe(&q, rng) {
+                return Ok((q, p));
+            }
+        }
+    }
+
+    let max = (&top << 1) - 1u32;
+    let mut window = vec![true; 16 * bits];
+    let two = BigUint::from(2u64);
+    loop {
+        let start = rng.gen_biguint(bits as u64 - 2) | &top | BigUint::one();
+        window.fill(true);
+        for p in SMALL_PRIMES.iter().skip(1).map(|&p| u64::from(p)) {
+            let r = residue(&start, p);
+            strike(&mut window, r, 0, p);
+            // 2q + 1 = 0 mod p exactly when q = (p - 1) / 2 mod p.
+            strike(&mut window, r, (p - 1) / 2, p);
+        }
+        for (i, _) in window.iter().enumerate().filter(|(_, &open)| open) {
+            let q = &start + 2 * i as u64;
+            if q > max {
+                break;
+            }
+            let candidates = spend()?;
+            let p: BigUint = (&q << 1) + 1u32;
+            // The cheap base-2 rounds reject nearly every pair first.
+            if is_strong_probable_prime(&q, &two)
+                && is_strong_probable_prime(&p, &two)
+                && lucas::is_strong_lucas_probable_prime(&q)
+                && lucas::is_strong_lucas_probable_prime(&p)
+                && safe(&q, rng)
+            {
+                tracing::debug!("found a {bits}-bit safe prime after {candidates} candidates");
+                return Ok((q, p));
+            }
+        }
+    }
+}
+
+/// Odd numbers sieved at a time by [`next_prime`] and [`prev_prime`].
+const STEP_WINDOW: usize = 1024;
+
+/// Returns the smallest prime greater than `n`.
+///
+/// Primality is decided by [`is_baillie_psw_prime`], so no randomness is
+/// needed and the answer is exact below 2^64. Useful for a modulus just
+/// above a given secret or power of two.
+pub fn next_prime(n: &BigUint) -> BigUint {
+    let two = BigUint::from(2u32);
+    if *n < two {
+        return two;
+    }
+    // The first odd number above n.
+    let mut start = (n + 1u32) | BigUint::one();
+    if start.bits() <= 64 {
+        while !is_baillie_psw_prime(&start) {
+            start += 2u32;
+        }
+        return start;
+    }
+    let mut window = vec![true; STEP_WINDOW];
+    loop {
+        window.fill(true);
+        for p in SMALL_PRIMES.iter().skip(1).map(|&p| u64::from(p)) {
+            strike(&mut window, residue(&start, p), 0, p);
+        }
+        for (i, _) in window.iter().enumerate().filter(|(_, &open)| open) {
+            let candidate = &start + 2 * i as u64;
+            if passes_baillie_psw(&candidate) {
+                return candidate;
+            }
+        }
+        start += 2 * STEP_WINDOW as u64;
+    }
+}
+
+/// Returns the largest prime less than `n`, or `None` if `n <= 2`.
+///
+/// Decided the same way as [`next_prime`].
+pub fn prev_prime(n: &BigUint) -> Option<BigUint> {
+    if *n <= BigUint::from(3u32) {
+        return (*n == BigUint::from(3u32)).then(|| BigUint::from(2u32));
+    }
+    // The first odd number below n.
+    let mut start = (n - 1u32) | BigUint::one();
+    if start >= *n {
+        start -= 2u32;
+    }
+    if start.bits() <= 64 {
+        while !is_baillie_psw_prime(&start) {
+            start -= 2u32;
+        }
+        return Some(start);
+    }
+    let mut window = vec![true; STEP_WINDOW];
+    loop {
+        window.fill(true);
+        for p in SMALL_PRIMES.iter().skip(1).map(|&p| u64::from(p)) {
+            // start - 2i = 0 mod p is -start + 2i = 0 mod p.
+            let r = residue(&start, p);
+            strike(&mut window, (p - r) % p, 0, p);
+        }
+        for (i, _) in window.iter().enumerate().filter(|(_, &open)| open) {
+            let candidate = &start - 2 * i as u64;
+            if passes_baillie_psw(&candidate) {
+                return Some(candidate);
+            }
+        }
+        start -= 2 * STEP_WINDOW as u64;
+    }
+}
+
+/// `n mod p` for a small `p`.
+fn residue(n: &BigUint, p: u64) -> u64 {
+    (n % p).to_u64().expect("remainder below p")
+}
+
+/// Strikes every `i` in `window` with `start + 2i = target mod p`, given
+/// `start mod p` and an odd prime `p`.
+fn strike(window: &mut [bool], start: u64, target: u64, p: u64) {
+    // 2i = target - start, and (p + 1) / 2 is the inverse of 2.
+    let mut i = ((target + p - start) % p * p.div_ceil(2) % p) as usize;
+    while i < window.len() {
+        window[i] = false;
+        i += p as usize;
+    }
+}
+
+/// The first 2048 primes, up to 17863, for trial division.
+const SMALL_PRIMES: [u32; 2048] = small_primes();
+
+const fn small_primes<const N: usize>() -> [u32; N] {
+    let mut primes = [0; N];
+    let mut count = 0;
+    let mut n = 2;
+    while count < N {
+        let mut prime = true;
+        let mut i = 0;
+        while i < count && primes[i] * primes[i] <= n {
+            if n % primes[i] == 0 {
+                prime = false;
+                break;
+            }
+            i += 1;
+        }
+        if prime {
+            primes[count] = n;
+            count += 1;
+        }
+        n += 1;
+    }
+    primes
+}
+
+/// Decides `n` by dividing it by [`SMALL_PRIMES`], or returns `None` if
+/// it has no small factor and is too large for that to prove it prime.
+///
+/// Almost every composite has a small factor, so this rejects most
+/// candidates before any modular exponentiation.
+fn trial_division(n: &BigUint) -> Option<bool> {
+    if *n < BigUint::from(2u32) || small_factor(n).is_some() {
+        return Some(false);
+    }
+    trial_division_decides(n).then_some(true)
+}
+
+/// The smallest of [`SMALL_PRIMES`] that divides `n` and is below it.
+fn small_factor(n: &BigUint) -> Option<u64> {
+    let mut primes = SMALL_PRIMES.iter().map(|&p| u64::from(p));
+    match n.to_u64() {
+        Some(n) => primes.take_while(|p| p * p <= n).find(|p| n % p == 0),
+        None => primes.find(|&p| (n % p).is_zero()),
+    }
+}
+
+/// Whether `n` is small enough that having no factor in [`SMALL_PRIMES`]
+/// proves it prime.
+fn trial_division_decides(n: &BigUint) -> bool {
+    let largest = u64::from(SMALL_PRIMES[SMALL_PRIMES.len() - 1]);
+    n.to_u64().is_some_and(|n| n < largest * largest)
+}
+
+/// Runs the Baillie–PSW test: a strong Miller–Rabin test to base 2 followed
+/// by a strong Lucas test.
+///
+/// The test is deterministic and no composite is known to pass it; none
+/// exists below 2^64. Inputs with a small factor, or small enough to be
+/// settled by trial division, never reach the exponentiations.
+pub fn is_baillie_psw_prime(n: &BigUint) -> bool {
+    if let Some(prime) = trial_division(n) {
+        return prime;
+    }
+    passes_baillie_psw(n)
+}
+
+/// The exponentiation half of [`is_baillie_psw_prime`], for an odd `n`
+/// already known to have no small factor.
+fn passes_baillie_psw(n: &BigUint) -> bool {
+    is_strong_probable_prime(n, &BigUint::from(2u64)) && lucas::is_strong_lucas_probable_prime(n)
+}
+
+/// Runs one strong Miller–Rabin round to `base` on an odd `n > base`.
+fn is_strong_probable_prime(n: &BigUint, base: &BigUint) -> bool {
+    let one = BigUint::one();
+    let n_minus_one = n - &one;
+    let s = n_minus_one.trailing_zeros().expect("n > 1");
+    let d = &n_minus_one >> s;
+    let mut x = modpow(base, &d, n);
+    if x == one || x == n_minus_one {
+        return true;
+    }
+    for _ in 1..s {
+        x = &x * &x % n;
+        if x == n_minus_one {
+            return true;
+        }
+    }
+    false
+}
+
+/// The tests every generated prime passes: Baillie–PSW, then the
+/// Miller–Rabin rounds of the default [`PrimalityConfig`].
+pub(crate) fn is_accepted_prime<R: RngCore + CryptoRng + ?Sized>(n: &BigUint, rng: &mut R) -> bool {
+    let rounds = PrimalityConfig::default().rounds_for(n.bits());
+    is_baillie_psw_prime(n) && is_probably_prime(n, rounds, rng)
+}
+
+/// Witnesses that make Miller–Rabin exact for every `n < 2^64`.
+const DETERMINISTIC_BASES: [u64; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+
+/// Runs `k` rounds of the Miller–Rabin test with bases drawn from `rng`.
+///
+/// A composite `n` passes with probability at most `4^-k`. Below 2^64 the
+/// answer is exact instead: `n` is tested against the first twelve primes
+/// as witnesses, and `k` and `rng` are not used.
+pub fn is_probably_prime<R: RngCore + CryptoRng + ?Sized>(
+    n: &BigUint,
+    k: usize,
+    rng: &mut R,
+) -> bool {
+    if *n <= BigUint::from(1u64) {
+        return false;
+    }
+    if *n == BigUint::from(2u64) {
+        return true;
+    }
+    if n % 2u64 == BigUint::zero() {
+        return false;
+    }
+
+    if n.bits() <= 64 {
+        for base in DETERMINISTIC_BASES {
+            let base = BigUint::from(base);
+            // Only a prime gets this far, as no odd composite below 37
+            // passes the smaller bases.
+            if base >= *n {
+                return true;
+            }
+            if !is_strong_probable_prime(n, &base) {
+                return false;
+            }
+        }
+        return true;
+    }
+
+    let two = BigUint::from(2u64);
+    (0..k).all(|_| is_strong_probable_prime(n, &rng.gen_biguint_range(&two, n)))
+}
@@ -0,0 +1,137 @@
+//! Blum primes, `p = 3 mod 4`, and Blum integers, products of two of them.
+//!
+//! Blum–Blum–Shub and several commitment and coin-flipping schemes need
+//! them: modulo a Blum integer, -1 is a non-residue with Jacobi symbol 1
+//! and squaring permutes the quadratic residues.
+
+use alloc::vec;
+
+use num_bigint::{BigUint, RandBigInt};
+use num_traits::One;
+use rand::{CryptoRng, RngCore};
+
+use super::{
+    is_accepted_prime, is_probably_prime, passes_baillie_psw, residue, strike, PrimalityConfig,
+    MAX_UNSIEVED_BITS, SMALL_PRIMES,
+};
+use crate::{zeroize_biguint, ShamirError};
+
+/// Generates a random Blum prime, a prime `p = 3 mod 4`, of exactly `bits`
+/// bits.
+///
+/// Candidates step by 4 from a random start and are sieved by the small
+/// primes, then pass the same tests as
+/// [`generate_large_prime`](super::generate_large_prime). Fails for
+/// `bits < 2`, below the smallest one, 3.
+#[tracing::instrument(level = "debug", skip(rng))]
+pub fn generate_blum_prime<R: RngCore + CryptoRng + ?Sized>(
+    bits: usize,
+    rng: &mut R,
+) -> Result<BigUint, ShamirError> {
+    if bits < 2 {
+        return Err(ShamirError::InvalidPrimeSize(bits));
+    }
+    Ok(search_blum_prime(bits, 1, rng))
+}
+
+/// A Blum integer `n = pq` from [`generate_blum_integer`], with its
+/// factors. The factors are wiped on drop.
+#[derive(Clone)]
+pub struct BlumInteger {
+    n: BigUint,
+    p: BigUint,
+    q: BigUint,
+}
+
+impl BlumInteger {
+    pub fn n(&self) -> &BigUint {
+        &self.n
+    }
+
+    /// The factor with `ceil(bits / 2)` bits.
+    pub fn p(&self) -> &BigUint {
+        &self.p
+    }
+
+    pub fn q(&self) -> &BigUint {
+        &self.q
+    }
+}
+
+impl Drop for BlumInteger {
+    fn drop(&mut self) {
+        zeroize_biguint(&mut self.p);
+        zeroize_biguint(&mut self.q);
+    }
+}
+
+/// Generates a Blum integer of exactly `bits` bits, the product of two
+/// distinct Blum primes of about half that size.
+///
+/// Both primes have their top two bits set so their product always has
+/// the full size. Fails for `bits < 16`, where there are too few such
+/// primes to be worth hiding.
+#[tracing::instrument(level = "debug", skip(rng))]
+pub fn generate_blum_integer<R: RngCore + CryptoRng + ?Sized>(
+    bits: usize,
+    rng: &mut R,
+) -> Result<BlumInteger, ShamirError> {
+    if bits < 16 {
+        return Err(ShamirError::InvalidPrimeSize(bits));
+    }
+    tracing::debug!("generating a {bits}-bit Blum integer");
+    let p_bits = bits.div_ceil(2);
+    let p = search_blum_prime(p_bits, 2, rng);
+    let q = loop {
+        let mut q = search_blum_prime(bits - p_bits, 2, rng);
+        if q != p {
+            break q;
+        }
+        zeroize_biguint(&mut q);
+    };
+    Ok(BlumInteger { n: &p * &q, p, q })
+}
+
+/// Finds a Blum prime of `bits >= 2` bits whose top `high_bits` bits are
+/// set.
+fn search_blum_prime<R: RngCore + CryptoRng + ?Sized>(
+    bits: usize,
+    high_bits: usize,
+    rng: &mut R,
+) -> BigUint {
+    let top = ((BigUint::one() << high_bits) - 1u32) << (bits - high_bits);
+    if bits <= MAX_UNSIEVED_BITS {
+        loop {
+            let p = rng.gen_biguint(bits as u64) | &top | BigUint::from(3u32);
+            if is_accepted_prime(&p, rng) {
+                return p;
+            }
+        }
+    }
+
+    // p = 4m + 3 = 2u + 1 for the odd u = 2m + 1, so stepping u through
+    // odd numbers steps p through 3 mod 4, and p = 0 mod r exactly when
+    // u = (r - 1) / 2 mod r.
+    let config = PrimalityConfig::default();
+    let max = (BigUint::one() << (bits - 1)) - 1u32;
+    let mut window = vec![true; 2 * bits];
+    loop {
+        let start = rng.gen_biguint(bits as u64 - 1) | (&top >> 1) | BigUint::one();
+        window.fill(true);
+        for r in SMALL_PRIMES.iter().skip(1).map(|&r| u64::from(r)) {
+            strike(&mut window, residue(&start, r), (r - 1) / 2, r);
+        }
+        for (i, _) in window.iter().enumerate().filter(|(_, &open)| open) {
+            let u = &start + 2 * i as u64;
+            if u > max {
+                break;
+            }
+            let p: BigUint = (u << 1) + 1u32;
+            // p is above 2^32, past every small prime the sieve removed.
+            if passes_baillie_psw(&p) && is_probably_prime(&p, config.rounds_for(p.bits()), rng) {
+                tracing::debug!("found a {bits}-bit Blum prime");
+                return p;
+            }
+        }
+    }
+}
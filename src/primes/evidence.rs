@@ -0,0 +1,98 @@
+//! Primality tests that say why, for callers that log or audit rejected
+//! candidates.
+
+use num_bigint::{BigUint, RandBigInt};
+use rand::{CryptoRng, RngCore};
+
+use super::{
+    is_strong_probable_prime, lucas, small_factor, trial_division_decides, DETERMINISTIC_BASES,
+};
+
+/// The outcome of [`is_prime`], with the evidence behind it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Primality {
+    /// Not prime, as `witness` shows.
+    Composite { witness: CompositeWitness },
+    /// Passed Baillie–PSW and then `rounds` Miller–Rabin rounds with random
+    /// bases.
+    ProbablyPrime { rounds: usize },
+    /// Prime for certain: below 2^64, trial division and the deterministic
+    /// Miller–Rabin bases decide exactly.
+    ProvenPrime,
+}
+
+/// Why [`is_prime`] found a number not to be prime.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CompositeWitness {
+    /// The number is 0 or 1, which are not prime (nor composite).
+    BelowTwo,
+    /// A small prime factor, found by trial division.
+    Factor(u64),
+    /// A base to which the number is not a strong probable prime.
+    MillerRabin(
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::biguint_hex"))] BigUint,
+    ),
+    /// The number failed the strong Lucas test. Perfect squares, which have
+    /// no Selfridge parameters, end up here.
+    Lucas,
+}
+
+impl Primality {
+    /// `true` unless the number was shown to be composite.
+    pub fn is_prime(&self) -> bool {
+        !matches!(self, Primality::Composite { .. })
+    }
+}
+
+/// Tests `n` for primality, returning the evidence for the answer.
+///
+/// Trial division runs first, then below 2^64 the deterministic
+/// Miller–Rabin bases, which settle the question. Larger numbers must pass
+/// Baillie–PSW and then `rounds` Miller–Rabin rounds with bases from `rng`,
+/// so a composite that was not chosen at random still passes with
+/// probability at most `4^-rounds`, on top of getting past Baillie–PSW.
+pub fn is_prime<R: RngCore + CryptoRng + ?Sized>(
+    n: &BigUint,
+    rounds: usize,
+    rng: &mut R,
+) -> Primality {
+    let composite = |witness| Primality::Composite { witness };
+    if *n < BigUint::from(2u32) {
+        return composite(CompositeWitness::BelowTwo);
+    }
+    if let Some(factor) = small_factor(n) {
+        return composite(CompositeWitness::Factor(factor));
+    }
+    if trial_division_decides(n) {
+        return Primality::ProvenPrime;
+    }
+
+    if n.bits() <= 64 {
+        // n is above 17863^2, so larger than every base.
+        return match DETERMINISTIC_BASES
+            .into_iter()
+            .map(BigUint::from)
+            .find(|base| !is_strong_probable_prime(n, base))
+        {
+            Some(base) => composite(CompositeWitness::MillerRabin(base)),
+            None => Primality::ProvenPrime,
+        };
+    }
+
+    let two = BigUint::from(2u32);
+    if !is_strong_probable_prime(n, &two) {
+        return composite(CompositeWitness::MillerRabin(two));
+    }
+    if !lucas::is_strong_lucas_probable_prime(n) {
+        return composite(CompositeWitness::Lucas);
+    }
+    for _ in 0..rounds {
+        let base = rng.gen_biguint_range(&two, n);
+        if !is_strong_probable_prime(n, &base) {
+            return composite(CompositeWitness::MillerRabin(base));
+        }
+    }
+    Primality::ProbablyPrime { rounds }
+}
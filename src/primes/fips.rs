@@ -0,0 +1,169 @@
+//! RSA prime generation following FIPS 186-4 Appendix B.3.3 (unchanged as
+//! Appendix A.1.3 of FIPS 186-5).
+//!
+//! Each candidate is a fresh random odd number of `nlen / 2` bits. It is
+//! rejected if it is below `sqrt(2) * 2^(nlen/2 - 1)` or if `p - 1` shares a
+//! factor with `e`, and otherwise run through the number of Miller–Rabin
+//! rounds Table C.3 gives for its size. The second prime must also differ
+//! from the first by more than `2^(nlen/2 - 100)`. Each prime gets up to
+//! `5 * nlen / 2` candidates before the procedure fails.
+
+use num_bigint::{BigUint, RandBigInt};
+use num_integer::Integer;
+use num_traits::One;
+use rand::{CryptoRng, RngCore};
+
+use super::{is_strong_probable_prime, trial_division};
+use crate::{zeroize_biguint, ShamirError};
+
+/// Miller–Rabin rounds for each prime size, from the "M-R tests only"
+/// column of FIPS 186-4 Table C.3 (Table B.1 of FIPS 186-5).
+const ROUNDS: [(usize, usize); 3] = [(1024, 5), (1536, 4), (2048, 4)];
+
+/// RSA primes generated by [`generate_fips186_rsa_primes`], with the values
+/// an auditor needs to check the procedure was followed.
+#[derive(Clone)]
+pub struct Fips186Primes {
+    nlen: usize,
+    e: BigUint,
+    p: BigUint,
+    q: BigUint,
+    rounds: usize,
+    p_candidates: usize,
+    q_candidates: usize,
+}
+
+impl Fips186Primes {
+    /// The modulus size the primes were generated for.
+    pub fn nlen(&self) -> usize {
+        self.nlen
+    }
+
+    /// The public exponent `p - 1` and `q - 1` are coprime to.
+    pub fn e(&self) -> &BigUint {
+        &self.e
+    }
+
+    pub fn p(&self) -> &BigUint {
+        &self.p
+    }
+
+    pub fn q(&self) -> &BigUint {
+        &self.q
+    }
+
+    /// Miller–Rabin rounds each prime passed.
+    pub fn rounds(&self) -> usize {
+        self.rounds
+    }
+
+    /// The step 4 iteration count `i`: candidates drawn for `p`, including
+    /// the one accepted.
+    pub fn p_candidates(&self) -> usize {
+        self.p_candidates
+    }
+
+    /// The step 5 iteration count `i` for `q`.
+    pub fn q_candidates(&self) -> usize {
+        self.q_candidates
+    }
+}
+
+impl Drop for Fips186Primes {
+    fn drop(&mut self) {
+        zeroize_biguint(&mut self.p);
+        zeroize_biguint(&mut self.q);
+    }
+}
+
+/// Generates the primes `p` and `q` of an `nlen`-bit RSA modulus with public
+/// exponent `e`, following FIPS 186-4 Appendix B.3.3.
+///
+/// `nlen` must be 2048, 3072 or 4096, and `e` odd with
+/// `2^16 < e < 2^256`. `rng` must be an approved random bit generator with
+/// at least the security strength of the modulus for the result to be
+/// compliant. Fails with [`ShamirError::SearchExhausted`] if either prime
+/// runs out of candidates, as the standard requires.
+#[tracing::instrument(level = "debug", skip(e, rng))]
+pub fn generate_fips186_rsa_primes<R: RngCore + CryptoRng + ?Sized>(
+    nlen: usize,
+    e: &BigUint,
+    rng: &mut R,
+) -> Result<Fips186Primes, ShamirError> {
+    let half = nlen / 2;
+    let rounds = ROUNDS
+        .iter()
+        .find(|&&(bits, _)| bits == half)
+        .map(|&(_, rounds)| rounds)
+        .ok_or(ShamirError::InvalidPrimeSize(nlen))?;
+    if e.is_even() || e.bits() <= 16 || e.bits() > 256 {
+        return Err(ShamirError::InvalidKey(
+            "RSA exponent must be odd and between 2^16 and 2^256",
+        ));
+    }
+    tracing::debug!("generating FIPS 186-4 primes for a {nlen}-bit RSA modulus");
+
+    let (p, p_candidates) = fips_prime(nlen, e, rounds, None, rng)?;
+    let (q, q_candidates) = match fips_prime(nlen, e, rounds, Some(&p), rng) {
+        Ok(found) => found,
+        Err(err) => {
+            let mut p = p;
+            zeroize_biguint(&mut p);
+            return Err(err);
+        }
+    };
+    tracing::debug!("found p after {p_candidates} and q after {q_candidates} candidates");
+    Ok(Fips186Primes {
+        nlen,
+        e: e.clone(),
+        p,
+        q,
+        rounds,
+        p_candidates,
+        q_candidates,
+    })
+}
+
+/// Steps 4 (without `other`) and 5 (with `p` as `other`) of B.3.3,
+/// returning the prime and the number of candidates drawn.
+fn fips_prime<R: RngCore + CryptoRng + ?Sized>(
+    nlen: usize,
+    e: &BigUint,
+    rounds: usize,
+    other: Option<&BigUint>,
+    rng: &mut R,
+) -> Result<(BigUint, usize), ShamirError> {
+    let half = nlen / 2;
+    let limit = 5 * half;
+    // candidate < sqrt(2) * 2^(half - 1) exactly when candidate^2 < 2^(nlen - 1).
+    let floor = BigUint::one() << (nlen - 1);
+    let min_distance = BigUint::one() << (half - 100);
+    let two = BigUint::from(2u32);
+    for i in 1..=limit {
+        let candidate = rng.gen_biguint(half as u64) | BigUint::one();
+        if let Some(other) = other {
+            let distance = if candidate > *other {
+                &candidate - other
+            } else {
+                other - &candidate
+            };
+            if distance <= min_distance {
+                continue;
+            }
+        }
+        if &candidate * &candidate < floor
+            || !(&candidate - 1u32).gcd(e).is_one()
+            || trial_division(&candidate) == Some(false)
+        {
+            continue;
+        }
+        // Bases are drawn from 1 < b < candidate - 1, as in C.3.1.
+        let upper = &candidate - 1u32;
+        if (0..rounds)
+            .all(|_| is_strong_probable_prime(&candidate, &rng.gen_biguint_range(&two, &upper)))
+        {
+            return Ok((candidate, i));
+        }
+    }
+    Err(ShamirError::SearchExhausted(limit as u64))
+}
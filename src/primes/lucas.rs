@@ -0,0 +1,155 @@
+//! The strong Lucas probable prime test, the second half of Baillie–PSW.
+//!
+//! Parameters are chosen with Selfridge's method A: `D` is the first of
+//! 5, -7, 9, -11, ... with Jacobi symbol `(D/n) = -1`, `P = 1` and
+//! `Q = (1 - D) / 4`. Everything is computed over residues mod `n`, so a
+//! negative value `v` is held as `n - |v|`.
+
+use num_bigint::BigUint;
+use num_integer::Integer;
+use num_traits::{One, Zero};
+
+/// Runs the strong Lucas probable prime test on `n`, with `D`, `P` and `Q`
+/// chosen by Selfridge's method A.
+///
+/// Every prime passes. Composites that pass, the strong Lucas
+/// pseudoprimes, are rare (the smallest is 5459) and no one has found one
+/// that is also a strong pseudoprime to base 2, which is what
+/// [`is_baillie_psw_prime`](super::is_baillie_psw_prime) relies on. Use it
+/// to cross-check Miller–Rabin or to build other combined tests. Returns
+/// `false` for 0, 1, even numbers above 2 and perfect squares.
+pub fn is_strong_lucas_prp(n: &BigUint) -> bool {
+    if *n < BigUint::from(3u32) {
+        return *n == BigUint::from(2u32);
+    }
+    n.is_odd() && is_strong_lucas_probable_prime(n)
+}
+
+/// Runs the strong Lucas test on an odd `n > 1`.
+pub(crate) fn is_strong_lucas_probable_prime(n: &BigUint) -> bool {
+    // Squares have no D with (D/n) = -1, so the search below would not end.
+    let root = n.sqrt();
+    if &root * &root == *n {
+        return false;
+    }
+    let Some((d, q)) = selfridge_parameters(n) else {
+        return false;
+    };
+
+    // n + 1 = k * 2^s with k odd.
+    let n_plus_one = n + 1u64;
+    let s = n_plus_one.trailing_zeros().expect("n + 1 is nonzero");
+    let k = &n_plus_one >> s;
+
+    let (u, mut v, mut q_k) = lucas_sequence(n, &k, &d, &q);
+    if u.is_zero() || v.is_zero() {
+        return true;
+    }
+    for _ in 1..s {
+        // V_2m = V_m^2 - 2 Q^m.
+        v = (&v * &v + (n - &q_k) * 2u64) % n;
+        if v.is_zero() {
+            return true;
+        }
+        q_k = &q_k * &q_k % n;
+    }
+    false
+}
+
+/// Finds `D` and `Q` as residues mod `n`, or `None` if a candidate `D`
+/// shows that `n` is composite.
+fn selfridge_parameters(n: &BigUint) -> Option<(BigUint, BigUint)> {
+    let mut magnitude = 5u64;
+    let mut negative = false;
+    loop {
+        let abs = BigUint::from(magnitude);
+        let d = if negative { n - &abs % n } else { &abs % n };
+        match jacobi(&d, n) {
+            -1 => {
+                // Q = (1 - D) / 4, which is (1 - |D|) / 4 or (1 + |D|) / 4.
+                let (q_abs, q_negative) = if negative {
+                    ((magnitude + 1) / 4, false)
+                } else {
+                    ((magnitude - 1) / 4, true)
+                };
+                let q_abs = BigUint::from(q_abs);
+                let common = q_abs.gcd(n);
+                if !common.is_one() && common != *n {
+                    return None;
+                }
+                let q = if q_negative {
+                    n - &q_abs % n
+                } else {
+                    &q_abs % n
+                };
+                return Some((d, q));
+            }
+            // |D| shares a factor with n.
+            0 if abs < *n => return None,
+            _ => {}
+        }
+        magnitude += 2;
+        negative = !negative;
+    }
+}
+
+/// Computes `U_k`, `V_k` and `Q^k` mod `n` for `P = 1`.
+fn lucas_sequence(
+    n: &BigUint,
+    k: &BigUint,
+    d: &BigUint,
+    q: &BigUint,
+) -> (BigUint, BigUint, BigUint) {
+    let mut u = BigUint::one();
+    let mut v = BigUint::one();
+    let mut q_k = q.clone();
+    for bit in (0..k.bits() - 1).rev() {
+        // U_2m = U_m V_m, V_2m = V_m^2 - 2 Q^m.
+        u = &u * &v % n;
+        v = (&v * &v + (n - &q_k) * 2u64) % n;
+        q_k = &q_k * &q_k % n;
+        if k.bit(bit) {
+            // U_m+1 = (U_m + V_m) / 2, V_m+1 = (D U_m + V_m) / 2.
+            let next_u = half(&u + &v, n);
+            v = half(d * &u + &v, n);
+            u = next_u;
+            q_k = &q_k * q % n;
+        }
+    }
+    (u, v, q_k)
+}
+
+/// Divides by 2 mod the odd `n`.
+fn half(x: BigUint, n: &BigUint) -> BigUint {
+    let x = x % n;
+    if x.is_odd() {
+        (x + n) >> 1
+    } else {
+        x >> 1
+    }
+}
+
+/// The Jacobi symbol `(a/n)` for odd `n`.
+fn jacobi(a: &BigUint, n: &BigUint) -> i8 {
+    let mut a = a % n;
+    let mut n = n.clone();
+    let mut result = 1;
+    while !a.is_zero() {
+        let twos = a.trailing_zeros().expect("a is nonzero");
+        a >>= twos;
+        // (2/n) = -1 exactly when n is 3 or 5 mod 8.
+        if twos % 2 == 1 && n.bit(1) != n.bit(2) {
+            result = -result;
+        }
+        core::mem::swap(&mut a, &mut n);
+        if a.bit(1) && n.bit(1) {
+            result = -result;
+        }
+        a %= &n;
+    }
+    if n.is_one() {
+        result
+    } else {
+        0
+    }
+}
@@ -0,0 +1,154 @@
+//! Prime searches spread over a rayon thread pool.
+
+use alloc::vec::Vec;
+use core::ops::ControlFlow;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::mpsc::{self, Receiver};
+use std::sync::Arc;
+
+use num_bigint::BigUint;
+use rand::{CryptoRng, RngCore, SeedableRng};
+use rand_chacha::ChaCha20Rng;
+use rayon::prelude::*;
+use rayon::ThreadPoolBuilder;
+use zeroize::Zeroizing;
+
+use super::{search_prime, PrimalityConfig};
+use crate::ShamirError;
+
+/// Like [`generate_large_prime`](super::generate_large_prime), with
+/// `threads` workers searching independent candidates at once and the
+/// first prime found returned.
+///
+/// Each worker draws from its own ChaCha20 generator seeded from `rng`.
+/// `threads = 0` uses one worker per CPU. If the pool cannot be started,
+/// the search runs on rayon's global pool instead.
+#[tracing::instrument(level = "debug", skip(rng))]
+pub fn generate_large_prime_parallel<R: RngCore + CryptoRng + ?Sized>(
+    bits: usize,
+    threads: usize,
+    rng: &mut R,
+) -> Result<BigUint, ShamirError> {
+    if bits < 2 {
+        return Err(ShamirError::InvalidPrimeSize(bits));
+    }
+    let pool = ThreadPoolBuilder::new()
+        .num_threads(threads)
+        .build()
+        .map_err(|err| {
+            tracing::warn!(
+                "cannot start {threads} prime search threads, using the global pool: {err}"
+            )
+        })
+        .ok();
+    let workers = pool
+        .as_ref()
+        .map_or_else(rayon::current_num_threads, |pool| {
+            pool.current_num_threads()
+        });
+    let seeds: Vec<Zeroizing<[u8; 32]>> = (0..workers)
+        .map(|_| {
+            let mut seed = Zeroizing::new([0u8; 32]);
+            rng.fill_bytes(seed.as_mut());
+            seed
+        })
+        .collect();
+    let config = PrimalityConfig::default();
+    let found = AtomicBool::new(false);
+    let search = || {
+        seeds
+            .into_par_iter()
+            .find_map_any(|seed| {
+                let mut rng = ChaCha20Rng::from_seed(*seed);
+                let prime = search_prime(bits, &config, &mut rng, |_| {
+                    if found.load(Ordering::Relaxed) {
+                        ControlFlow::Break(())
+                    } else {
+                        ControlFlow::Continue(())
+                    }
+                })
+                .ok()
+                .flatten()?;
+                found.store(true, Ordering::Relaxed);
+                Some(prime)
+            })
+            .expect("a worker runs until a prime is found")
+    };
+    Ok(match pool {
+        Some(pool) => pool.install(search),
+        None => search(),
+    })
+}
+
+/// Like [`generate_primes`](super::generate_primes), with `threads` workers
+/// sending primes down the returned channel as they find them.
+///
+/// The channel closes once `count` primes have been sent. Workers run in
+/// the background on their own pool, so the caller can take each prime as
+/// it arrives; dropping the receiver stops them after their current search.
+/// `threads = 0` uses one worker per CPU.
+pub fn generate_primes_parallel<R: RngCore + CryptoRng + ?Sized>(
+    bits: usize,
+    count: usize,
+    threads: usize,
+    rng: &mut R,
+) -> Result<Receiver<BigUint>, ShamirError> {
+    if bits < 2 {
+        return Err(ShamirError::InvalidPrimeSize(bits));
+    }
+    let pool = ThreadPoolBuilder::new()
+        .num_threads(threads)
+        .build()
+        .map_err(|err| {
+            tracing::warn!(
+                "cannot start {threads} prime search threads, using the global pool: {err}"
+            )
+        })
+        .ok();
+    let workers = pool
+        .as_ref()
+        .map_or_else(rayon::current_num_threads, |pool| {
+            pool.current_num_threads()
+        })
+        .min(count);
+    let (sender, receiver) = mpsc::channel();
+    let remaining = Arc::new(AtomicUsize::new(count));
+    let config = PrimalityConfig::default();
+    for _ in 0..workers {
+        let mut seed = Zeroizing::new([0u8; 32]);
+        rng.fill_bytes(seed.as_mut());
+        let sender = sender.clone();
+        let remaining = Arc::clone(&remaining);
+        let work = move || {
+            let mut rng = ChaCha20Rng::from_seed(*seed);
+            let done = || remaining.load(Ordering::Relaxed) == 0;
+            while !done() {
+                let stop = |_| {
+                    if done() {
+                        ControlFlow::Break(())
+                    } else {
+                        ControlFlow::Continue(())
+                    }
+                };
+                let Ok(Some(prime)) = search_prime(bits, &config, &mut rng, stop) else {
+                    break;
+                };
+                // Claim one of the remaining slots, unless others took them
+                // all while this prime was being found.
+                let claimed = remaining
+                    .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |left| {
+                        left.checked_sub(1)
+                    })
+                    .is_ok();
+                if !claimed || sender.send(prime).is_err() {
+                    break;
+                }
+            }
+        };
+        match &pool {
+            Some(pool) => pool.spawn(work),
+            None => rayon::spawn(work),
+        }
+    }
+    Ok(receiver)
+}
@@ -0,0 +1,198 @@
+//! Provable primes by recursive Pocklington steps, after Shawe-Taylor and
+//! Maurer.
+//!
+//! Each prime is built from a smaller proven prime `q`: candidates have the
+//! form `p = 2kq + 1` with `q > sqrt(p)`, and Pocklington's theorem proves
+//! `p` prime once some `a` has `a^(p-1) = 1` and `gcd(a^(2k) - 1, p) = 1`
+//! mod `p`. The recursion bottoms out at primes small enough for trial
+//! division to decide.
+//!
+//! Recording the small prime and each `(p, a)` gives a
+//! [`PrimalityCertificate`] that [`verify_certificate`] checks with a
+//! handful of exponentiations, without trusting whoever generated it.
+
+use alloc::vec::Vec;
+
+use num_bigint::{BigUint, RandBigInt};
+use num_integer::Integer;
+use num_traits::{One, Zero};
+use rand::{CryptoRng, RngCore};
+
+use super::trial_division;
+use crate::bigint::modpow;
+use crate::ShamirError;
+
+/// Sizes at which [`trial_division`] settles primality on its own.
+const TRIAL_DIVISION_BITS: usize = 28;
+
+/// Generates a random prime of exactly `bits` bits whose primality is
+/// proven rather than tested.
+///
+/// The result does not depend on Miller–Rabin, Baillie–PSW or any other
+/// probable prime test, only on trial division for the first small prime
+/// and Pocklington's theorem for each larger one. Only primes with a large
+/// factor of `p - 1` can come out, so the choice is not uniform over all
+/// primes of that size. Fails for `bits < 2`.
+pub fn generate_provable_prime<R: RngCore + CryptoRng + ?Sized>(
+    bits: usize,
+    rng: &mut R,
+) -> Result<BigUint, ShamirError> {
+    generate_certified_prime(bits, rng).map(|certificate| certificate.prime().clone())
+}
+
+/// Like [`generate_provable_prime`], returning the proof as a certificate
+/// that can be published alongside the prime.
+#[tracing::instrument(level = "debug", skip(rng))]
+pub fn generate_certified_prime<R: RngCore + CryptoRng + ?Sized>(
+    bits: usize,
+    rng: &mut R,
+) -> Result<PrimalityCertificate, ShamirError> {
+    if bits < 2 {
+        return Err(ShamirError::InvalidPrimeSize(bits));
+    }
+    tracing::debug!("constructing a provable {bits}-bit prime");
+    let mut steps = Vec::new();
+    let base = provable_prime(bits, rng, &mut steps);
+    Ok(PrimalityCertificate { base, steps })
+}
+
+/// A proof that [`prime`](Self::prime) is prime.
+///
+/// The base is a prime small enough to check by trial division. Each step
+/// proves a larger prime `p` from the one before it, `q`, with a witness
+/// `a`: `q` divides `p - 1`, `q^2 > p`, `a^(p-1) = 1` and
+/// `gcd(a^((p-1)/q) - 1, p) = 1` mod `p`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PrimalityCertificate {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::biguint_hex"))]
+    base: BigUint,
+    steps: Vec<PocklingtonStep>,
+}
+
+/// One step of a [`PrimalityCertificate`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PocklingtonStep {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::biguint_hex"))]
+    prime: BigUint,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::biguint_hex"))]
+    witness: BigUint,
+}
+
+impl PrimalityCertificate {
+    pub fn new(base: BigUint, steps: Vec<PocklingtonStep>) -> Self {
+        PrimalityCertificate { base, steps }
+    }
+
+    /// The prime the certificate is for.
+    pub fn prime(&self) -> &BigUint {
+        self.steps.last().map_or(&self.base, |step| &step.prime)
+    }
+
+    pub fn base(&self) -> &BigUint {
+        &self.base
+    }
+
+    /// Steps from the smallest prime to [`prime`](Self::prime).
+    pub fn steps(&self) -> &[PocklingtonStep] {
+        &self.steps
+    }
+}
+
+impl PocklingtonStep {
+    pub fn new(prime: BigUint, witness: BigUint) -> Self {
+        PocklingtonStep { prime, witness }
+    }
+
+    pub fn prime(&self) -> &BigUint {
+        &self.prime
+    }
+
+    pub fn witness(&self) -> &BigUint {
+        &self.witness
+    }
+}
+
+/// Checks every step of `certificate`, proving its prime.
+///
+/// Compare [`PrimalityCertificate::prime`], or its [`Fingerprint`](crate::Fingerprint),
+/// with the modulus in question to tie the proof to a share set.
+#[tracing::instrument(level = "debug", skip_all, fields(bits = certificate.prime().bits()))]
+pub fn verify_certificate(certificate: &PrimalityCertificate) -> Result<(), ShamirError> {
+    if trial_division(&certificate.base) != Some(true) {
+        return Err(ShamirError::InvalidCertificate("base is not a small prime"));
+    }
+    let mut q = &certificate.base;
+    for step in &certificate.steps {
+        let p = &step.prime;
+        let a = &step.witness;
+        let (cofactor, remainder) = (p - 1u32).div_rem(q);
+        if !remainder.is_zero() || cofactor.is_zero() {
+            return Err(ShamirError::InvalidCertificate(
+                "previous prime does not divide p - 1",
+            ));
+        }
+        if q * q <= *p {
+            return Err(ShamirError::InvalidCertificate(
+                "previous prime is not above sqrt(p)",
+            ));
+        }
+        if *a < BigUint::from(2u32) || *a >= p - 1u32 {
+            return Err(ShamirError::InvalidCertificate("witness out of range"));
+        }
+        let x = modpow(a, &cofactor, p);
+        if x.is_zero() || !modpow(&x, q, p).is_one() || !(&x - 1u32).gcd(p).is_one() {
+            return Err(ShamirError::InvalidCertificate(
+                "witness does not prove p prime",
+            ));
+        }
+        q = p;
+    }
+    Ok(())
+}
+
+/// Builds a proven prime of exactly `bits` bits, appending a step to
+/// `steps` for each Pocklington proof and returning the base prime if
+/// the recursion ends here.
+fn provable_prime<R: RngCore + CryptoRng + ?Sized>(
+    bits: usize,
+    rng: &mut R,
+    steps: &mut Vec<PocklingtonStep>,
+) -> BigUint {
+    let lowest = BigUint::one() << (bits - 1);
+    let highest = (BigUint::one() << bits) - 1u32;
+    if bits <= TRIAL_DIVISION_BITS {
+        loop {
+            let n = rng.gen_biguint_range(&lowest, &(&highest + 1u32));
+            if trial_division(&n) == Some(true) {
+                return n;
+            }
+        }
+    }
+
+    // q has at least half the bits of p plus one, so q > sqrt(p).
+    let base = provable_prime(bits.div_ceil(2) + 1, rng, steps);
+    let q = steps.last().map_or(&base, |step| &step.prime).clone();
+    let two_q: BigUint = &q << 1;
+    // 2kq + 1 must have exactly `bits` bits.
+    let first_k = (&lowest - 1u32).div_ceil(&two_q);
+    let last_k = (&highest - 1u32) / &two_q;
+    loop {
+        let k = rng.gen_biguint_range(&first_k, &(&last_k + 1u32));
+        let p = &two_q * &k + 1u32;
+        if trial_division(&p) == Some(false) {
+            continue;
+        }
+        let a = rng.gen_biguint_range(&BigUint::from(2u32), &(&p - 1u32));
+        let x = modpow(&a, &(&k << 1), &p);
+        if x.is_zero() || !modpow(&x, &q, &p).is_one() {
+            continue;
+        }
+        if (&x - 1u32).gcd(&p).is_one() {
+            tracing::trace!("proved a {bits}-bit prime from a {}-bit one", q.bits());
+            steps.push(PocklingtonStep::new(p, a));
+            return base;
+        }
+    }
+}
@@ -0,0 +1,148 @@
+//! How many Miller–Rabin rounds a generated prime gets.
+//!
+//! The worst-case `4^-t` bound of `t` rounds is far too pessimistic for
+//! candidates drawn at random: Damgård, Landrock and Pomerance ("Average
+//! case error estimates for the strong probable prime test", 1993) bound
+//! the chance that a random `k`-bit number passing `t` rounds is composite
+//! much more tightly, and FIPS 186-4 Appendix F derives its round counts
+//! from the same bounds. They are evaluated here in log2, with small
+//! `no_std` stand-ins for `f64::sqrt` and `f64::log2`.
+
+/// How sure a generated probable prime must be, as a fixed number of
+/// Miller–Rabin rounds or an error bound the rounds are derived from.
+///
+/// The bound covers the Miller–Rabin rounds alone. Candidates first pass
+/// Baillie–PSW, which no known composite survives, so the true error is
+/// lower still. The default asks for an error of at most `2^-128`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PrimalityConfig {
+    target: Target,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Target {
+    Rounds(usize),
+    ErrorBits(u32),
+}
+
+impl PrimalityConfig {
+    /// Runs `rounds` Miller–Rabin rounds whatever the size of the prime.
+    pub fn fixed_rounds(rounds: usize) -> Self {
+        PrimalityConfig {
+            target: Target::Rounds(rounds),
+        }
+    }
+
+    /// Runs as many rounds as the Damgård–Landrock–Pomerance bounds need
+    /// for a random candidate to be composite with probability at most
+    /// `2^-error_bits`.
+    pub fn error_bound(error_bits: u32) -> Self {
+        PrimalityConfig {
+            target: Target::ErrorBits(error_bits),
+        }
+    }
+
+    /// Miller–Rabin rounds for a randomly generated candidate of `bits`
+    /// bits.
+    ///
+    /// Sizes below 21 bits, where the bounds do not apply, get the
+    /// worst-case count of `error_bits / 2` rounds, rounded up. For
+    /// testing numbers that were not chosen at random, only the worst case
+    /// holds; see [`is_probably_prime`](super::is_probably_prime).
+    pub fn rounds_for(&self, bits: u64) -> usize {
+        match self.target {
+            Target::Rounds(rounds) => rounds,
+            Target::ErrorBits(error_bits) => dlp_rounds(bits, error_bits),
+        }
+    }
+}
+
+impl Default for PrimalityConfig {
+    fn default() -> Self {
+        PrimalityConfig::error_bound(128)
+    }
+}
+
+/// The fewest rounds `t >= 1` with `p_{k,t} <= 2^-error_bits`.
+fn dlp_rounds(k: u64, error_bits: u32) -> usize {
+    if k < 21 {
+        return error_bits.div_ceil(2).max(1) as usize;
+    }
+    let target = -f64::from(error_bits);
+    (1..)
+        .find(|&t| log2_error(k as f64, t as f64) <= target)
+        .expect("the t >= k/4 bound falls by two bits a round")
+}
+
+/// log2 of the smallest bound on `p_{k,t}` that applies, for `k >= 21`.
+fn log2_error(k: f64, t: f64) -> f64 {
+    let log_k = log2(k);
+    // p_{k,t} < (1/7) k^(15/4) 2^(-k/2 - 2t)
+    let tail = |t: f64| 3.75 * log_k - log2(7.0) - k / 2.0 - 2.0 * t;
+    let mut best = f64::INFINITY;
+    if t == 1.0 {
+        // p_{k,1} < k^2 4^(2 - sqrt(k))
+        best = 2.0 * log_k + 2.0 * (2.0 - sqrt(k));
+    }
+    if (t == 2.0 && k >= 88.0) || (t >= 3.0 && t <= k / 9.0) {
+        // p_{k,t} < k^(3/2) 2^t t^(-1/2) 4^(2 - sqrt(tk))
+        let bound = 1.5 * log_k + t - 0.5 * log2(t) + 2.0 * (2.0 - sqrt(t * k));
+        best = best.min(bound);
+    }
+    if t >= k / 9.0 && t <= k / 4.0 {
+        // p_{k,t} < (7/20) k 2^(-5t) + (1/7) k^(15/4) 2^(-k/2 - 2t)
+        //         + 12 k 2^(-k/4 - 3t),
+        // which is below three times its largest term.
+        let terms = [
+            log2(7.0 / 20.0) + log_k - 5.0 * t,
+            tail(t),
+            log2(12.0) + log_k - k / 4.0 - 3.0 * t,
+        ];
+        let largest = terms.into_iter().fold(f64::NEG_INFINITY, f64::max);
+        best = best.min(largest + log2(3.0));
+    }
+    if t >= k / 4.0 {
+        best = best.min(tail(t));
+    }
+    best
+}
+
+/// Base-2 logarithm of a positive `x`, to about 40 bits of precision.
+fn log2(mut x: f64) -> f64 {
+    let mut result = 0.0;
+    while x >= 2.0 {
+        x /= 2.0;
+        result += 1.0;
+    }
+    while x < 1.0 {
+        x *= 2.0;
+        result -= 1.0;
+    }
+    // Each squaring of the mantissa in [1, 2) yields the next binary digit.
+    let mut digit = 1.0;
+    for _ in 0..40 {
+        x *= x;
+        digit /= 2.0;
+        if x >= 2.0 {
+            x /= 2.0;
+            result += digit;
+        }
+    }
+    result
+}
+
+/// Square root of a nonnegative `x` by Newton's method.
+fn sqrt(x: f64) -> f64 {
+    if x == 0.0 {
+        return 0.0;
+    }
+    let mut root = if x > 1.0 { x } else { 1.0 };
+    for _ in 0..100 {
+        let next = (root + x / root) / 2.0;
+        if next >= root {
+            break;
+        }
+        root = next;
+    }
+    root
+}
@@ -0,0 +1,71 @@
+//! Strong primes by Gordon's construction.
+//!
+//! A strong prime `p` has a large prime factor `r` of `p - 1`, a large
+//! prime factor `s` of `p + 1`, and a large prime factor `t` of `r - 1`.
+//! Older RSA policies (ANSI X9.31 among them) require both RSA primes to
+//! be strong, to resist Pollard's `p - 1` and Williams' `p + 1` methods.
+
+use num_bigint::{BigUint, RandBigInt};
+use num_integer::Integer;
+use num_traits::{One, Zero};
+use rand::{CryptoRng, RngCore};
+
+use super::{generate_large_prime, is_accepted_prime};
+use crate::bigint::modpow;
+use crate::ShamirError;
+
+/// How many bits `s` and `t` fall short of half of `bits`, leaving room to
+/// choose `p` among many multiples of `2rs`.
+const FACTOR_MARGIN: usize = 12;
+
+/// Generates a random strong prime of exactly `bits` bits with Gordon's
+/// algorithm.
+///
+/// `s` and `t` are random primes of about `bits / 2 - 12` bits, and
+/// `r = 2it + 1` is the first prime of that form. `p` is the first prime
+/// `p0 + 2jrs` from a random `j` onward, where `p0 = 2(s^(r-2) mod r)s - 1`
+/// makes every such candidate `1 mod r` and `-1 mod s`. Fails for
+/// `bits < 64`, where the factors would be too small to matter.
+#[tracing::instrument(level = "debug", skip(rng))]
+pub fn generate_strong_prime<R: RngCore + CryptoRng + ?Sized>(
+    bits: usize,
+    rng: &mut R,
+) -> Result<BigUint, ShamirError> {
+    if bits < 64 {
+        return Err(ShamirError::InvalidPrimeSize(bits));
+    }
+    tracing::debug!("searching for a {bits}-bit strong prime");
+    let factor_bits = bits / 2 - FACTOR_MARGIN;
+    let lowest = BigUint::one() << (bits - 1);
+    let highest = (BigUint::one() << bits) - 1u32;
+    loop {
+        let s = generate_large_prime(factor_bits, rng)?;
+        let t = generate_large_prime(factor_bits, rng)?;
+
+        let mut r = (&t << 1) + 1u32;
+        while !is_accepted_prime(&r, rng) {
+            r += &t << 1;
+        }
+
+        let two_rs: BigUint = (&r * &s) << 1;
+        let p0: BigUint = ((modpow(&s, &(&r - 2u32), &r) * &s) << 1) - 1u32;
+        // The first j putting p at `bits` bits, then a random step into
+        // the lower half of the range so different runs start apart.
+        let first = if lowest > p0 {
+            (&lowest - &p0).div_ceil(&two_rs)
+        } else {
+            BigUint::zero()
+        };
+        let span = (&highest - &p0) / &two_rs - &first;
+        let mut p = &p0 + (first + rng.gen_biguint_below(&((span >> 1) + 1u32))) * &two_rs;
+        while p <= highest {
+            if is_accepted_prime(&p, rng) {
+                tracing::debug!("found a {bits}-bit strong prime");
+                return Ok(p);
+            }
+            p += &two_rs;
+        }
+        // Ran off the top, which takes a very unlucky search; start over
+        // with new factors.
+    }
+}
@@ -0,0 +1,167 @@
+//! QR codes for shares, for paper backups and for moving shares between
+//! air-gapped machines with a camera.
+//!
+//! A share is encoded as its uppercase Bech32m string, which QR codes store
+//! in the compact alphanumeric mode and [`Share::from_bech32`] parses back
+//! as is. Codes use error correction level M, so a smudged or creased print
+//! still scans. The free functions render any text, e.g. an
+//! `SsssShare` or an uppercased `ur:sskr` string.
+//!
+//! PNG output is 1-bit grayscale with uncompressed deflate blocks; QR
+//! matrices are small enough that compression is not worth a dependency.
+
+use qrcode::render::{svg, unicode};
+use qrcode::types::QrError;
+use qrcode::{Color, EcLevel, QrCode};
+use zeroize::Zeroize;
+
+use crate::crc32::crc32;
+use crate::{Bech32Variant, ShamirError, Share};
+
+/// Light modules around the code, as required by ISO/IEC 18004.
+const QUIET_ZONE: usize = 4;
+/// Pixels per module in PNG output.
+const PNG_SCALE: usize = 8;
+const MAX_STORED_BLOCK: usize = 0xffff;
+
+fn encode(text: &str) -> Result<QrCode, ShamirError> {
+    QrCode::with_error_correction_level(text, EcLevel::M).map_err(|err| {
+        ShamirError::QrCode(match err {
+            QrError::DataTooLong => "data is too long",
+            _ => "data cannot be encoded",
+        })
+    })
+}
+
+/// SVG image of `text` as a QR code.
+pub fn qr_svg(text: &str) -> Result<String, ShamirError> {
+    Ok(encode(text)?
+        .render::<svg::Color<'_>>()
+        .min_dimensions(256, 256)
+        .build())
+}
+
+/// `text` as a QR code drawn with Unicode half blocks, two modules per
+/// character cell, light on dark for terminals with a dark background.
+pub fn qr_terminal(text: &str) -> Result<String, ShamirError> {
+    qr_blocks(text, true)
+}
+
+/// Unicode half-block rendering; `inverted` swaps the block colors so the
+/// code reads correctly as light text on a dark background.
+pub(crate) fn qr_blocks(text: &str, inverted: bool) -> Result<String, ShamirError> {
+    let code = encode(text)?;
+    let mut renderer = code.render::<unicode::Dense1x2>();
+    if inverted {
+        renderer
+            .dark_color(unicode::Dense1x2::Light)
+            .light_color(unicode::Dense1x2::Dark);
+    }
+    Ok(renderer.build())
+}
+
+/// PNG image of `text` as a QR code, eight pixels per module.
+pub fn qr_png(text: &str) -> Result<Vec<u8>, ShamirError> {
+    let code = encode(text)?;
+    let modules = code.width();
+    let colors = code.to_colors();
+    let size = (modules + 2 * QUIET_ZONE) * PNG_SCALE;
+    let row_len = 1 + size.div_ceil(8);
+
+    let mut raw = Vec::with_capacity(row_len * size);
+    for py in 0..size {
+        // Filter type 0; set bits are white in 1-bit grayscale.
+        raw.push(0);
+        let start = raw.len();
+        raw.resize(start + row_len - 1, 0xff);
+        let y = (py / PNG_SCALE).wrapping_sub(QUIET_ZONE);
+        for px in 0..size {
+            let x = (px / PNG_SCALE).wrapping_sub(QUIET_ZONE);
+            if x < modules && y < modules && colors[y * modules + x] == Color::Dark {
+                raw[start + px / 8] &= !(0x80 >> (px % 8));
+            }
+        }
+    }
+
+    let mut header = Vec::with_capacity(13);
+    header.extend_from_slice(&(size as u32).to_be_bytes());
+    header.extend_from_slice(&(size as u32).to_be_bytes());
+    // 1-bit grayscale, deflate, adaptive filtering, no interlace.
+    header.extend_from_slice(&[1, 0, 0, 0, 0]);
+
+    let mut png = b"\x89PNG\r\n\x1a\n".to_vec();
+    write_chunk(&mut png, b"IHDR", &header);
+    write_chunk(&mut png, b"IDAT", &zlib_stored(&raw));
+    write_chunk(&mut png, b"IEND", &[]);
+    Ok(png)
+}
+
+fn write_chunk(png: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    png.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    let start = png.len();
+    png.extend_from_slice(kind);
+    png.extend_from_slice(data);
+    let crc = crc32(&png[start..]);
+    png.extend_from_slice(&crc.to_be_bytes());
+}
+
+/// A zlib stream of uncompressed deflate blocks (RFC 1950, RFC 1951 §3.2.4).
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    let blocks = data.len().div_ceil(MAX_STORED_BLOCK).max(1);
+    let mut out = Vec::with_capacity(data.len() + 5 * blocks + 6);
+    out.extend_from_slice(&[0x78, 0x01]);
+    let mut chunks = data.chunks(MAX_STORED_BLOCK).peekable();
+    if chunks.peek().is_none() {
+        out.extend_from_slice(&[1, 0, 0, 0xff, 0xff]);
+    }
+    while let Some(chunk) = chunks.next() {
+        out.push(chunks.peek().is_none() as u8);
+        let len = chunk.len() as u16;
+        out.extend_from_slice(&len.to_le_bytes());
+        out.extend_from_slice(&(!len).to_le_bytes());
+        out.extend_from_slice(chunk);
+    }
+    out.extend_from_slice(&adler32(data).to_be_bytes());
+    out
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    let (a, b) = data.iter().fold((1u32, 0u32), |(a, b), &byte| {
+        let a = (a + byte as u32) % 65521;
+        (a, (b + a) % 65521)
+    });
+    (b << 16) | a
+}
+
+impl Share {
+    /// The uppercase Bech32m string encoded in share QR codes.
+    pub(crate) fn qr_text(&self) -> String {
+        let mut text = self.to_bech32(Bech32Variant::Bech32m);
+        text.make_ascii_uppercase();
+        text
+    }
+
+    /// QR code of the share as an SVG image.
+    pub fn to_qr_svg(&self) -> Result<String, ShamirError> {
+        let mut text = self.qr_text();
+        let svg = qr_svg(&text);
+        text.zeroize();
+        svg
+    }
+
+    /// QR code of the share as a PNG image.
+    pub fn to_qr_png(&self) -> Result<Vec<u8>, ShamirError> {
+        let mut text = self.qr_text();
+        let png = qr_png(&text);
+        text.zeroize();
+        png
+    }
+
+    /// QR code of the share for printing to a terminal.
+    pub fn to_qr_terminal(&self) -> Result<String, ShamirError> {
+        let mut text = self.qr_text();
+        let code = qr_terminal(&text);
+        text.zeroize();
+        code
+    }
+}
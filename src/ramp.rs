@@ -0,0 +1,154 @@
+//! Ramp secret sharing (Blakley–Meadows).
+//!
+//! A `(t, r, n)` ramp scheme trades secrecy below the threshold for share
+//! size: the secret is `t - r` field elements placed in the low coefficients
+//! of a degree `t - 1` polynomial whose `r` high coefficients are random.
+//! Every share is a single field element, so shares are `t - r` times
+//! smaller than the secret, but only sets of at most `r` shares learn
+//! nothing. Each share beyond `r` reveals up to one field element's worth of
+//! information about the secret, and `t` shares recover it completely.
+
+use alloc::vec::Vec;
+
+use num_bigint::{BigUint, RandBigInt};
+use num_traits::One;
+use rand::{CryptoRng, RngCore};
+
+use crate::linear::solve;
+use crate::polynomial::Polynomial;
+use crate::shamir::{check_digest_all, check_share_set};
+use crate::{zeroize_biguint, Fingerprint, ShamirError, Share, ShareSetId};
+
+/// A `(t, r, n)` ramp scheme over a prime field.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RampScheme {
+    threshold: usize,
+    privacy: usize,
+    shares: usize,
+    modulus: BigUint,
+}
+
+impl RampScheme {
+    /// `threshold` shares reconstruct, any `privacy` shares reveal nothing,
+    /// and the secret consists of `threshold - privacy` field elements.
+    /// Requires `1 <= privacy < threshold <= shares`.
+    pub fn new(
+        threshold: usize,
+        privacy: usize,
+        shares: usize,
+        modulus: BigUint,
+    ) -> Result<Self, ShamirError> {
+        if threshold < 2 || shares < threshold {
+            return Err(ShamirError::InvalidThreshold { threshold, shares });
+        }
+        if privacy == 0 || privacy >= threshold {
+            return Err(ShamirError::InvalidAccessStructure(
+                "the privacy threshold must satisfy 1 <= r < t",
+            ));
+        }
+        if modulus <= BigUint::from(shares as u64) {
+            return Err(ShamirError::ModulusTooSmall { modulus, shares });
+        }
+        Ok(RampScheme {
+            threshold,
+            privacy,
+            shares,
+            modulus,
+        })
+    }
+
+    pub fn threshold(&self) -> usize {
+        self.threshold
+    }
+
+    /// The largest number of shares that reveals nothing about the secret.
+    pub fn privacy(&self) -> usize {
+        self.privacy
+    }
+
+    pub fn shares(&self) -> usize {
+        self.shares
+    }
+
+    pub fn modulus(&self) -> &BigUint {
+        &self.modulus
+    }
+
+    /// Number of field elements in the secret, `t - r`.
+    pub fn secret_len(&self) -> usize {
+        self.threshold - self.privacy
+    }
+
+    /// Upper bound, in field elements, on what `count` shares reveal about
+    /// the secret: zero up to the privacy threshold, then one per share.
+    pub fn leakage(&self, count: usize) -> usize {
+        count.saturating_sub(self.privacy).min(self.secret_len())
+    }
+
+    /// Splits a secret of exactly [`RampScheme::secret_len`] field elements.
+    pub fn split_with_rng<R: RngCore + CryptoRng + ?Sized>(
+        &self,
+        secret: &[BigUint],
+        rng: &mut R,
+    ) -> Result<Vec<Share>, ShamirError> {
+        if secret.len() != self.secret_len() {
+            return Err(ShamirError::NotEnoughShares {
+                required: self.secret_len(),
+                provided: secret.len(),
+            });
+        }
+        if secret.iter().any(|piece| *piece >= self.modulus) {
+            return Err(ShamirError::SecretTooLarge);
+        }
+        let mut coefficients: Vec<BigUint> = secret.to_vec();
+        for _ in 0..self.privacy {
+            coefficients.push(rng.gen_biguint_below(&self.modulus));
+        }
+        let polynomial = Polynomial::from_coefficients(coefficients);
+        let fingerprint = Fingerprint::of(&self.modulus);
+        let set_id = ShareSetId::random(rng);
+        let xs: Vec<BigUint> = (1..=self.shares as u64).map(BigUint::from).collect();
+        let ys = polynomial.evaluate_all(&xs, &self.modulus);
+        Ok(xs
+            .into_iter()
+            .zip(ys)
+            .map(|(x, y)| Share::new(x, y, self.threshold, fingerprint).with_set_id(set_id))
+            .collect())
+    }
+
+    /// Recovers the secret from at least [`RampScheme::threshold`] shares,
+    /// checked against their [`SecretDigest::compute_all`] digest if they
+    /// carry one.
+    ///
+    /// [`SecretDigest::compute_all`]: crate::SecretDigest::compute_all
+    pub fn reconstruct(&self, shares: &[Share]) -> Result<Vec<BigUint>, ShamirError> {
+        check_share_set(shares, &self.modulus)?;
+        if shares[0].threshold() != self.threshold {
+            return Err(ShamirError::ThresholdMismatch);
+        }
+        let used = &shares[..self.threshold];
+        let rows = used
+            .iter()
+            .map(|share| {
+                let x = share.x();
+                let mut row = Vec::with_capacity(self.threshold + 1);
+                let mut power = BigUint::one();
+                for _ in 0..self.threshold {
+                    row.push(power.clone());
+                    power = (power * x) % &self.modulus;
+                }
+                row.push(share.y() % &self.modulus);
+                row
+            })
+            .collect();
+        let (mut coefficients, rank) = solve(rows, self.threshold, &self.modulus)?;
+        if rank < self.threshold {
+            return Err(ShamirError::SingularSystem);
+        }
+        coefficients[self.secret_len()..]
+            .iter_mut()
+            .for_each(zeroize_biguint);
+        coefficients.truncate(self.secret_len());
+        check_digest_all(shares, coefficients)
+    }
+}
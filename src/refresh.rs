@@ -0,0 +1,47 @@
+use alloc::vec::Vec;
+
+use num_bigint::BigUint;
+use num_traits::Zero;
+use rand::{CryptoRng, RngCore};
+
+use crate::polynomial::Polynomial;
+use crate::shamir::{check_compatible, check_same_set};
+use crate::{ShamirError, Share, ShareSetId};
+
+/// Proactively refreshes `shares` without reconstructing the secret.
+///
+/// A random polynomial with a zero constant term is added to every share, so
+/// the refreshed set still encodes the same secret while shares from before
+/// the refresh can no longer be combined with shares from after it; the
+/// refreshed shares get a new [`ShareSetId`] so such mixing is reported as
+/// [`ShamirError::SetMismatch`]. Pedersen blinding values are not carried
+/// over, since the old commitments no longer match.
+pub fn refresh_shares<R: RngCore + CryptoRng + ?Sized>(
+    shares: &[Share],
+    modulus: &BigUint,
+    rng: &mut R,
+) -> Result<Vec<Share>, ShamirError> {
+    let first = check_compatible(shares, modulus)?;
+    check_same_set(shares)?;
+    for (i, share) in shares.iter().enumerate() {
+        if shares[..i].iter().any(|other| other.x() == share.x()) {
+            return Err(ShamirError::DuplicateShareX(share.x().clone()));
+        }
+    }
+    let threshold = first.threshold();
+    let zero_poly = Polynomial::random(&BigUint::zero(), threshold - 1, modulus, rng);
+    let set_id = ShareSetId::random(rng);
+    Ok(shares
+        .iter()
+        .map(|share| {
+            let delta = zero_poly.evaluate(share.x(), modulus);
+            let y = (share.y() + delta) % modulus;
+            let refreshed = Share::new(share.x().clone(), y, threshold, share.fingerprint())
+                .with_set_id(set_id);
+            match share.digest() {
+                Some(digest) => refreshed.with_digest(*digest),
+                None => refreshed,
+            }
+        })
+        .collect())
+}
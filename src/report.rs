@@ -0,0 +1,159 @@
+//! Reconstruction with cheater detection.
+
+use alloc::vec::Vec;
+
+use num_bigint::BigUint;
+use num_traits::Zero;
+
+use crate::shamir::{check_same_set, check_share_set, lagrange_coefficient_at};
+use crate::{shamir_reconstruct, PedersenCommitments, PedersenParams, ShamirError, Share};
+
+/// Outcome of a reconstruction that cross-checked its shares.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReconstructReport {
+    /// The secret determined by the consistent shares.
+    pub secret: BigUint,
+    /// x-coordinates of shares that do not lie on the recovered polynomial
+    /// (or fail commitment verification).
+    pub suspects: Vec<BigUint>,
+}
+
+impl ReconstructReport {
+    /// `true` when every supplied share was consistent.
+    pub fn is_clean(&self) -> bool {
+        self.suspects.is_empty()
+    }
+}
+
+/// Reconstructs while checking every share against the others.
+///
+/// Threshold-sized subsets are interpolated in turn and the polynomial
+/// agreeing with the most shares wins; shares off that polynomial are
+/// reported as suspects. Cheaters can only be identified when more than
+/// `threshold` shares are supplied and the honest ones form a strict
+/// majority of the agreement; otherwise the set is rejected with
+/// [`ShamirError::InconsistentShares`]. With exactly `threshold` shares no
+/// check is possible and the report is always clean. The secret is checked
+/// against the [`SecretDigest`](crate::SecretDigest) of the consistent
+/// shares, if they carry one.
+pub fn shamir_reconstruct_report(
+    shares: &[Share],
+    modulus: &BigUint,
+) -> Result<ReconstructReport, ShamirError> {
+    check_share_set(shares, modulus)?;
+    let threshold = shares[0].threshold();
+    let n = shares.len();
+    let xs: Vec<BigUint> = shares.iter().map(|share| share.x().clone()).collect();
+
+    // Polynomials agreeing on more than (n + t) / 2 points are unique.
+    let decisive = (n + threshold) / 2 + 1;
+    let mut best: Option<(Vec<bool>, usize)> = None;
+    let mut tied = false;
+    let mut subset: Vec<usize> = (0..threshold).collect();
+    loop {
+        let agree = agreement(shares, &xs, &subset, modulus)?;
+        let support = agree.iter().filter(|&&ok| ok).count();
+        match &best {
+            Some((best_agree, best_support)) if support == *best_support => {
+                tied |= agree != *best_agree;
+            }
+            Some((_, best_support)) if support < *best_support => {}
+            _ => {
+                best = Some((agree, support));
+                tied = false;
+            }
+        }
+        if support >= decisive || !next_subset(&mut subset, n) {
+            break;
+        }
+    }
+
+    let (agree, support) = best.expect("at least one subset");
+    if n > threshold && (tied || support <= threshold) {
+        return Err(ShamirError::InconsistentShares);
+    }
+    let consistent: Vec<Share> = shares
+        .iter()
+        .zip(&agree)
+        .filter(|(_, &ok)| ok)
+        .map(|(share, _)| share.clone())
+        .collect();
+    let suspects = shares
+        .iter()
+        .zip(&agree)
+        .filter(|(_, &ok)| !ok)
+        .map(|(share, _)| share.x().clone())
+        .collect();
+    Ok(ReconstructReport {
+        secret: shamir_reconstruct(&consistent, modulus)?,
+        suspects,
+    })
+}
+
+/// Reconstructs from the shares that pass Pedersen verification, reporting
+/// the rest as suspects. As with [`shamir_reconstruct`], the secret is
+/// checked against the shares' digest, if they carry one.
+pub fn pedersen_reconstruct_report(
+    shares: &[Share],
+    commitments: &PedersenCommitments,
+    params: &PedersenParams,
+) -> Result<ReconstructReport, ShamirError> {
+    check_same_set(shares)?;
+    let mut valid = Vec::with_capacity(shares.len());
+    let mut suspects = Vec::new();
+    for share in shares {
+        match commitments.verify(share, params) {
+            Ok(()) => valid.push(share.clone()),
+            Err(ShamirError::CommitmentMismatch(x)) | Err(ShamirError::MissingBlinding(x)) => {
+                suspects.push(x)
+            }
+            Err(err) => return Err(err),
+        }
+    }
+    Ok(ReconstructReport {
+        secret: shamir_reconstruct(&valid, params.q())?,
+        suspects,
+    })
+}
+
+/// Marks which shares lie on the polynomial through `subset`.
+fn agreement(
+    shares: &[Share],
+    xs: &[BigUint],
+    subset: &[usize],
+    modulus: &BigUint,
+) -> Result<Vec<bool>, ShamirError> {
+    let subset_xs: Vec<BigUint> = subset.iter().map(|&i| xs[i].clone()).collect();
+    shares
+        .iter()
+        .enumerate()
+        .map(|(k, share)| {
+            if subset.contains(&k) {
+                return Ok(true);
+            }
+            let mut y = BigUint::zero();
+            for (i, &index) in subset.iter().enumerate() {
+                y +=
+                    lagrange_coefficient_at(&subset_xs, i, share.x(), modulus)? * shares[index].y();
+                y %= modulus;
+            }
+            Ok(y == share.y() % modulus)
+        })
+        .collect()
+}
+
+/// Advances `subset` to the next combination of `subset.len()` indices out
+/// of `n` in lexicographic order.
+fn next_subset(subset: &mut [usize], n: usize) -> bool {
+    let k = subset.len();
+    for i in (0..k).rev() {
+        if subset[i] < n - k + i {
+            subset[i] += 1;
+            for j in i + 1..k {
+                subset[j] = subset[j - 1] + 1;
+            }
+            return true;
+        }
+    }
+    false
+}
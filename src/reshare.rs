@@ -0,0 +1,157 @@
+//! Resharing an existing secret to a new threshold and shareholder set.
+//!
+//! Each old shareholder in a quorum multiplies its share by its Lagrange
+//! coefficient and deals the product as a fresh `(t', n')` sharing with
+//! [`reshare_deal`]. Every new shareholder then sums the sub-shares it
+//! received with [`reshare_combine`]. Because the Lagrange-weighted values
+//! add up to the secret, the sums form a valid new sharing of it, yet the
+//! secret never exists in one place.
+//!
+//! Each dealer's sub-shares carry the [`ShareSetId`] of its own sharing, which
+//! tells dealers apart when they are combined, and the digest of the old
+//! shares if they had one. The new shares are stamped with a set id derived
+//! from the dealers' ones, so every new shareholder arrives at the same id
+//! and the new set cannot be mixed with the old one.
+
+use alloc::vec::Vec;
+
+use num_bigint::BigUint;
+use num_traits::Zero;
+use rand::{CryptoRng, RngCore};
+use sha3::{Digest, Sha3_256};
+
+use crate::shamir::{check_compatible, check_share_set, lagrange_coefficient};
+use crate::{shamir_split_shares, zeroize_biguint, Fingerprint, ShamirError, Share, ShareSetId};
+
+/// Deals one old shareholder's contribution to a resharing.
+///
+/// `quorum` lists the x-coordinates of all old shares taking part, which
+/// must include `share` and number at least its threshold. Returns one
+/// sub-share per new shareholder, at x = 1..=`new_count`, all stamped with a
+/// fresh [`ShareSetId`] for this dealer and with `share`'s digest.
+pub fn reshare_deal<R: RngCore + CryptoRng + ?Sized>(
+    share: &Share,
+    quorum: &[BigUint],
+    modulus: &BigUint,
+    new_threshold: usize,
+    new_count: usize,
+    rng: &mut R,
+) -> Result<Vec<Share>, ShamirError> {
+    check_compatible(core::slice::from_ref(share), modulus)?;
+    if quorum.len() < share.threshold() {
+        return Err(ShamirError::NotEnoughShares {
+            required: share.threshold(),
+            provided: quorum.len(),
+        });
+    }
+    let i = quorum
+        .iter()
+        .position(|x| x == share.x())
+        .ok_or_else(|| ShamirError::InvalidParticipant(share.x().clone()))?;
+    let coefficient = lagrange_coefficient(quorum, i, modulus)?;
+    let mut weighted = (coefficient * share.y()) % modulus;
+    let sub_shares = shamir_split_shares(&weighted, new_threshold, new_count, modulus, rng);
+    zeroize_biguint(&mut weighted);
+    let sub_shares = sub_shares?;
+    Ok(match share.digest() {
+        Some(digest) => sub_shares
+            .into_iter()
+            .map(|sub_share| sub_share.with_digest(*digest))
+            .collect(),
+        None => sub_shares,
+    })
+}
+
+/// Sums the sub-shares one new shareholder received from every old
+/// shareholder in the quorum into its new share.
+///
+/// There must be one sub-share from each of at least `old_threshold`
+/// distinct dealers, told apart by their [`ShareSetId`]s, and all of them
+/// must carry the same digest, which the new share keeps.
+pub fn reshare_combine(
+    sub_shares: &[Share],
+    old_threshold: usize,
+    modulus: &BigUint,
+) -> Result<Share, ShamirError> {
+    let first = check_compatible(sub_shares, modulus)?;
+    if let Some(other) = sub_shares.iter().find(|share| share.x() != first.x()) {
+        return Err(ShamirError::InvalidParticipant(other.x().clone()));
+    }
+    if sub_shares.len() < old_threshold {
+        return Err(ShamirError::NotEnoughShares {
+            required: old_threshold,
+            provided: sub_shares.len(),
+        });
+    }
+    let mut dealers = sub_shares
+        .iter()
+        .map(Share::set_id)
+        .collect::<Option<Vec<ShareSetId>>>()
+        .ok_or(ShamirError::DuplicateDealer)?;
+    dealers.sort_unstable_by_key(|id| *id.as_bytes());
+    if dealers.windows(2).any(|pair| pair[0] == pair[1]) {
+        return Err(ShamirError::DuplicateDealer);
+    }
+    let digest = first.digest();
+    if sub_shares.iter().any(|share| share.digest() != digest) {
+        return Err(ShamirError::IntegrityFailure);
+    }
+    let mut y = BigUint::zero();
+    for share in sub_shares {
+        y += share.y();
+        y %= modulus;
+    }
+    let share = Share::new(
+        first.x().clone(),
+        y,
+        first.threshold(),
+        Fingerprint::of(modulus),
+    )
+    .with_set_id(new_set_id(&dealers));
+    Ok(match digest {
+        Some(digest) => share.with_digest(*digest),
+        None => share,
+    })
+}
+
+/// The set id of a resharing, hashed from the sorted set ids of its dealers.
+fn new_set_id(dealers: &[ShareSetId]) -> ShareSetId {
+    let mut hasher = Sha3_256::new();
+    hasher.update(b"prime-shamir/reshare-set-id/v1");
+    for dealer in dealers {
+        hasher.update(dealer.as_bytes());
+    }
+    ShareSetId::from_hash(&hasher.finalize())
+}
+
+/// Runs the whole resharing locally: every share in `old_shares` deals a
+/// contribution and the new shares are combined from them.
+///
+/// `old_shares` must come from one share set, at distinct x-coordinates,
+/// and number at least their threshold.
+///
+/// Useful when one operator holds the quorum but must never materialize the
+/// secret; distributed deployments call [`reshare_deal`] and
+/// [`reshare_combine`] on separate machines instead.
+pub fn reshare<R: RngCore + CryptoRng + ?Sized>(
+    old_shares: &[Share],
+    modulus: &BigUint,
+    new_threshold: usize,
+    new_count: usize,
+    rng: &mut R,
+) -> Result<Vec<Share>, ShamirError> {
+    check_share_set(old_shares, modulus)?;
+    let old_threshold = old_shares[0].threshold();
+    let quorum: Vec<BigUint> = old_shares.iter().map(|share| share.x().clone()).collect();
+    let mut received: Vec<Vec<Share>> = (0..new_count).map(|_| Vec::new()).collect();
+    for share in old_shares {
+        let dealt = reshare_deal(share, &quorum, modulus, new_threshold, new_count, rng)?;
+        for (slot, sub_share) in received.iter_mut().zip(dealt) {
+            slot.push(sub_share);
+        }
+    }
+    received
+        .iter()
+        .map(|sub_shares| reshare_combine(sub_shares, old_threshold, modulus))
+        .collect()
+}
@@ -0,0 +1,203 @@
+//! Error-correcting reconstruction with the Berlekamp–Welch decoder.
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+use num_bigint::BigUint;
+use num_traits::{One, Zero};
+
+use crate::linear::solve;
+use crate::shamir::{check_digest, check_share_set};
+use crate::{Fingerprint, ReconstructReport, ShamirError, Share};
+
+/// Reconstructs the secret even if up to `max_errors` of the shares are
+/// corrupted, provided at least `threshold + 2 * max_errors` shares are
+/// supplied.
+///
+/// Solves the Berlekamp–Welch key equation `Q(x_i) = y_i * E(x_i)` for an
+/// error locator `E` of degree `max_errors`, then recovers the sharing
+/// polynomial as `Q / E`. Shares that do not lie on it are reported as
+/// suspects. Fails with [`ShamirError::InconsistentShares`] when more than
+/// `max_errors` shares are wrong. If the shares carry a
+/// [`SecretDigest`](crate::SecretDigest), the secret is checked against the
+/// digest of the shares that lie on the polynomial.
+#[tracing::instrument(level = "debug", skip(shares, modulus), fields(shares = shares.len(), modulus = %Fingerprint::of(modulus)))]
+pub fn shamir_reconstruct_robust(
+    shares: &[Share],
+    modulus: &BigUint,
+    max_errors: usize,
+) -> Result<ReconstructReport, ShamirError> {
+    check_share_set(shares, modulus)?;
+    let threshold = shares[0].threshold();
+    let required = threshold + 2 * max_errors;
+    if shares.len() < required {
+        return Err(ShamirError::NotEnoughShares {
+            required,
+            provided: shares.len(),
+        });
+    }
+    let q_len = threshold + max_errors;
+    let unknowns = q_len + max_errors;
+    let mut rows = Vec::with_capacity(shares.len());
+    for share in shares {
+        let x = share.x();
+        let y = share.y() % modulus;
+        let mut row = Vec::with_capacity(unknowns + 1);
+        let mut power = BigUint::one();
+        let mut powers = Vec::with_capacity(q_len);
+        for _ in 0..q_len {
+            powers.push(power.clone());
+            power = (power * x) % modulus;
+        }
+        row.extend(powers.iter().cloned());
+        for power in powers.iter().take(max_errors) {
+            row.push((modulus - (&y * power) % modulus) % modulus);
+        }
+        row.push((&y * &powers[max_errors]) % modulus);
+        rows.push(row);
+    }
+    let (solution, _) = solve(rows, unknowns, modulus)?;
+
+    let q_poly = solution[..q_len].to_vec();
+    let mut e_poly = solution[q_len..].to_vec();
+    e_poly.push(BigUint::one());
+    let (p_poly, remainder) = divide_monic(&q_poly, &e_poly, modulus);
+    if remainder.iter().any(|c| !c.is_zero()) || p_poly.len() > threshold {
+        return Err(ShamirError::InconsistentShares);
+    }
+
+    let (honest, corrupted): (Vec<Share>, Vec<Share>) = shares
+        .iter()
+        .cloned()
+        .partition(|share| evaluate(&p_poly, share.x(), modulus) == share.y() % modulus);
+    if corrupted.len() > max_errors {
+        return Err(ShamirError::InconsistentShares);
+    }
+    let secret = p_poly.first().cloned().unwrap_or_default();
+    Ok(ReconstructReport {
+        secret: check_digest(&honest, secret)?,
+        suspects: corrupted.iter().map(|share| share.x().clone()).collect(),
+    })
+}
+
+/// Divides `numerator` by the monic `divisor`, returning quotient and
+/// remainder with coefficients lowest degree first.
+fn divide_monic(
+    numerator: &[BigUint],
+    divisor: &[BigUint],
+    modulus: &BigUint,
+) -> (Vec<BigUint>, Vec<BigUint>) {
+    let mut remainder = numerator.to_vec();
+    let d = divisor.len() - 1;
+    if remainder.len() <= d {
+        return (Vec::new(), remainder);
+    }
+    let mut quotient = vec![BigUint::zero(); remainder.len() - d];
+    for k in (0..quotient.len()).rev() {
+        let lead = remainder[k + d].clone();
+        if lead.is_zero() {
+            continue;
+        }
+        for (j, coeff) in divisor.iter().enumerate() {
+            let sub = (&lead * coeff) % modulus;
+            remainder[k + j] = (&remainder[k + j] + modulus - sub) % modulus;
+        }
+        quotient[k] = lead;
+    }
+    remainder.truncate(d);
+    while quotient.last().is_some_and(|c| c.is_zero()) {
+        quotient.pop();
+    }
+    (quotient, remainder)
+}
+
+fn evaluate(coefficients: &[BigUint], x: &BigUint, modulus: &BigUint) -> BigUint {
+    coefficients
+        .iter()
+        .rev()
+        .fold(BigUint::zero(), |acc, c| (acc * x + c) % modulus)
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha20Rng;
+
+    use super::*;
+    use crate::{shamir_split_shares, shamir_split_shares_with_digest, StandardPrime};
+
+    /// The share with `y` moved off the polynomial, keeping its metadata.
+    fn corrupt(share: &Share, modulus: &BigUint) -> Share {
+        let y = (share.y() + 1u32) % modulus;
+        let mut corrupted =
+            Share::new(share.x().clone(), y, share.threshold(), share.fingerprint());
+        if let Some(set_id) = share.set_id() {
+            corrupted = corrupted.with_set_id(set_id);
+        }
+        if let Some(digest) = share.digest() {
+            corrupted = corrupted.with_digest(*digest);
+        }
+        corrupted
+    }
+
+    #[test]
+    fn corrects_up_to_max_errors() {
+        let mut rng = ChaCha20Rng::seed_from_u64(0);
+        let modulus = StandardPrime::Modp2048.modulus();
+        let secret = BigUint::from(0xdead_beef_u32);
+        // 7 shares of a 3-of-7 split leave room for (7 - 3) / 2 = 2 errors.
+        let shares = shamir_split_shares(&secret, 3, 7, &modulus, &mut rng).unwrap();
+        for bad in [&[][..], &[4], &[0, 6], &[2, 3]] {
+            let mut supplied = shares.clone();
+            for &i in bad {
+                supplied[i] = corrupt(&shares[i], &modulus);
+            }
+            let report = shamir_reconstruct_robust(&supplied, &modulus, 2).unwrap();
+            assert_eq!(report.secret, secret);
+            let expected: Vec<BigUint> = bad.iter().map(|&i| shares[i].x().clone()).collect();
+            assert_eq!(report.suspects, expected);
+        }
+    }
+
+    #[test]
+    fn rejects_more_than_max_errors() {
+        let mut rng = ChaCha20Rng::seed_from_u64(1);
+        let modulus = StandardPrime::Modp2048.modulus();
+        let secret = BigUint::from(7u32);
+        let mut shares = shamir_split_shares(&secret, 3, 7, &modulus, &mut rng).unwrap();
+        for i in [0, 2, 5] {
+            shares[i] = corrupt(&shares[i], &modulus);
+        }
+        assert!(matches!(
+            shamir_reconstruct_robust(&shares, &modulus, 2),
+            Err(ShamirError::InconsistentShares)
+        ));
+    }
+
+    #[test]
+    fn requires_two_shares_per_error() {
+        let mut rng = ChaCha20Rng::seed_from_u64(2);
+        let modulus = StandardPrime::Modp2048.modulus();
+        let shares = shamir_split_shares(&BigUint::from(7u32), 3, 6, &modulus, &mut rng).unwrap();
+        assert!(matches!(
+            shamir_reconstruct_robust(&shares, &modulus, 2),
+            Err(ShamirError::NotEnoughShares {
+                required: 7,
+                provided: 6
+            })
+        ));
+    }
+
+    #[test]
+    fn checks_the_digest_of_the_honest_shares() {
+        let mut rng = ChaCha20Rng::seed_from_u64(3);
+        let modulus = StandardPrime::Modp2048.modulus();
+        let secret = BigUint::from(42u32);
+        let mut shares =
+            shamir_split_shares_with_digest(&secret, 2, 5, &modulus, &mut rng).unwrap();
+        shares[1] = corrupt(&shares[1], &modulus);
+        let report = shamir_reconstruct_robust(&shares, &modulus, 1).unwrap();
+        assert_eq!(report.secret, secret);
+        assert_eq!(report.suspects, [shares[1].x().clone()]);
+    }
+}
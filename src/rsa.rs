@@ -0,0 +1,356 @@
+//! RSA key generation and private key sharding.
+//!
+//! [`generate_keypair`] builds a key from the crate's FIPS 186-4 prime
+//! generation. When a key is split, only the first prime factor `p` is shared; the public key `(n, e)` is
+//! kept alongside the shares and everything else is recomputed from it on
+//! reconstruction. Shares live in the smallest [`StandardPrime`] field above
+//! `sqrt(n)` so they stay about half the size of the key, and the restored
+//! key is checked against the public key before it is returned.
+
+use alloc::vec::Vec;
+
+use num_bigint::BigUint;
+use num_integer::Integer;
+use num_traits::{One, Zero};
+use rand::{CryptoRng, RngCore};
+use zeroize::Zeroize;
+
+use crate::bigint::modpow;
+use crate::der::{self, Reader};
+use crate::primes::generate_fips186_rsa_primes;
+use crate::primes::standard::StandardPrime;
+use crate::{shamir_reconstruct, shamir_split_shares, zeroize_biguint, ShamirError, Share};
+
+/// The public exponent of generated keys, 2^16 + 1.
+const PUBLIC_EXPONENT: u32 = 65537;
+
+/// PEM label of a PKCS#8 private key.
+#[cfg(feature = "std")]
+const PKCS8_PEM_TAG: &str = "PRIVATE KEY";
+
+/// DER contents of the `rsaEncryption` object identifier, 1.2.840.113549.1.1.1.
+const RSA_ENCRYPTION: &[u8] = &[0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x01, 0x01];
+
+/// Share fields, smallest first.
+const SHARE_FIELDS: [StandardPrime; 7] = [
+    StandardPrime::Mersenne521,
+    StandardPrime::Modp1536,
+    StandardPrime::Modp2048,
+    StandardPrime::Modp3072,
+    StandardPrime::Modp4096,
+    StandardPrime::Modp6144,
+    StandardPrime::Modp8192,
+];
+
+/// The public half of a sharded RSA key, needed to reconstruct it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RsaPublicKey {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::biguint_hex"))]
+    n: BigUint,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::biguint_hex"))]
+    e: BigUint,
+}
+
+impl RsaPublicKey {
+    pub fn new(n: BigUint, e: BigUint) -> Self {
+        RsaPublicKey { n, e }
+    }
+
+    pub fn n(&self) -> &BigUint {
+        &self.n
+    }
+
+    pub fn e(&self) -> &BigUint {
+        &self.e
+    }
+
+    /// The field the shares of this key live in.
+    pub fn share_modulus(&self) -> Result<BigUint, ShamirError> {
+        let half = self.n.bits().div_ceil(2) as usize + 1;
+        SHARE_FIELDS
+            .iter()
+            .find(|field| field.bits() > half)
+            .map(|field| field.modulus())
+            .ok_or(ShamirError::InvalidKey("RSA modulus too large to shard"))
+    }
+}
+
+struct RsaPrivateKey {
+    n: BigUint,
+    e: BigUint,
+    d: BigUint,
+    p: BigUint,
+    q: BigUint,
+}
+
+impl RsaPrivateKey {
+    /// The CRT parameters `d mod (p - 1)`, `d mod (q - 1)` and
+    /// `q^-1 mod p`.
+    fn crt_parameters(&self) -> [BigUint; 3] {
+        let one = BigUint::one();
+        [
+            &self.d % (&self.p - &one),
+            &self.d % (&self.q - &one),
+            self.q.modinv(&self.p).unwrap_or_default(),
+        ]
+    }
+}
+
+impl Drop for RsaPrivateKey {
+    fn drop(&mut self) {
+        zeroize_biguint(&mut self.d);
+        zeroize_biguint(&mut self.p);
+        zeroize_biguint(&mut self.q);
+    }
+}
+
+/// An RSA key from [`generate_keypair`], with its CRT parameters. Private
+/// values are wiped on drop.
+pub struct RsaKeyPair {
+    key: RsaPrivateKey,
+    crt: [BigUint; 3],
+}
+
+impl RsaKeyPair {
+    pub fn n(&self) -> &BigUint {
+        &self.key.n
+    }
+
+    pub fn e(&self) -> &BigUint {
+        &self.key.e
+    }
+
+    /// The private exponent, `e^-1 mod lcm(p - 1, q - 1)`.
+    pub fn d(&self) -> &BigUint {
+        &self.key.d
+    }
+
+    pub fn p(&self) -> &BigUint {
+        &self.key.p
+    }
+
+    pub fn q(&self) -> &BigUint {
+        &self.key.q
+    }
+
+    /// `d mod (p - 1)`.
+    pub fn dp(&self) -> &BigUint {
+        &self.crt[0]
+    }
+
+    /// `d mod (q - 1)`.
+    pub fn dq(&self) -> &BigUint {
+        &self.crt[1]
+    }
+
+    /// `q^-1 mod p`.
+    pub fn qinv(&self) -> &BigUint {
+        &self.crt[2]
+    }
+
+    pub fn public_key(&self) -> RsaPublicKey {
+        RsaPublicKey::new(self.key.n.clone(), self.key.e.clone())
+    }
+
+    /// Encodes the key as unencrypted PKCS#8 DER, which
+    /// [`split_rsa_key`] accepts.
+    pub fn to_pkcs8_der(&self) -> Vec<u8> {
+        encode_private_key(&self.key)
+    }
+
+    /// Encodes the key as PEM with the `PRIVATE KEY` label.
+    #[cfg(feature = "std")]
+    pub fn to_pkcs8_pem(&self) -> String {
+        let pem = pem::Pem {
+            tag: PKCS8_PEM_TAG.into(),
+            contents: self.to_pkcs8_der(),
+        };
+        let encoded = pem::encode_config(
+            &pem,
+            pem::EncodeConfig {
+                line_ending: pem::LineEnding::LF,
+            },
+        );
+        let mut contents = pem.contents;
+        contents.zeroize();
+        encoded
+    }
+}
+
+impl Drop for RsaKeyPair {
+    fn drop(&mut self) {
+        for value in &mut self.crt {
+            zeroize_biguint(value);
+        }
+    }
+}
+
+/// Generates an RSA key with a `bits`-bit modulus and `e = 65537`.
+///
+/// The primes come from
+/// [`generate_fips186_rsa_primes`](crate::generate_fips186_rsa_primes), so
+/// `bits` must be 2048, 3072 or 4096. `d` is `e^-1 mod lcm(p - 1, q - 1)`,
+/// and like FIPS 186-4 B.3.1 requires, the primes are drawn again in the
+/// unlikely case that `d` is not above `2^(bits/2)`.
+#[tracing::instrument(level = "debug", skip(rng))]
+pub fn generate_keypair<R: RngCore + CryptoRng + ?Sized>(
+    bits: usize,
+    rng: &mut R,
+) -> Result<RsaKeyPair, ShamirError> {
+    let e = BigUint::from(PUBLIC_EXPONENT);
+    let floor = BigUint::one() << (bits / 2);
+    loop {
+        let primes = generate_fips186_rsa_primes(bits, &e, rng)?;
+        let (p, q) = (primes.p().clone(), primes.q().clone());
+        let one = BigUint::one();
+        let mut lambda = (&p - &one).lcm(&(&q - &one));
+        let d = e.modinv(&lambda);
+        zeroize_biguint(&mut lambda);
+        let key = RsaPrivateKey {
+            n: &p * &q,
+            e: e.clone(),
+            d: d.expect("gcd(p - 1, e) = gcd(q - 1, e) = 1 for FIPS 186 primes"),
+            p,
+            q,
+        };
+        if key.d > floor {
+            tracing::debug!("generated a {bits}-bit RSA key");
+            let crt = key.crt_parameters();
+            return Ok(RsaKeyPair { key, crt });
+        }
+    }
+}
+
+/// Splits an RSA private key given as PKCS#8 or PKCS#1 DER, returning its
+/// public key and shares of its first prime.
+pub fn split_rsa_key<R: RngCore + CryptoRng + ?Sized>(
+    der: &[u8],
+    threshold: usize,
+    shares: usize,
+    rng: &mut R,
+) -> Result<(RsaPublicKey, Vec<Share>), ShamirError> {
+    let key = parse_private_key(der)?;
+    if &key.p * &key.q != key.n || key.p <= BigUint::one() || key.q <= BigUint::one() {
+        return Err(ShamirError::InvalidKey("RSA primes do not multiply to n"));
+    }
+    let public = RsaPublicKey::new(key.n.clone(), key.e.clone());
+    let modulus = public.share_modulus()?;
+    if key.p >= modulus {
+        return Err(ShamirError::InvalidKey("RSA primes are too unbalanced"));
+    }
+    let shares = shamir_split_shares(&key.p, threshold, shares, &modulus, rng)?;
+    Ok((public, shares))
+}
+
+/// Recovers the private key as PKCS#8 DER from at least `threshold` shares.
+///
+/// The private exponent is recomputed as `e^-1 mod lcm(p - 1, q - 1)`, so the
+/// output matches the original DER byte for byte whenever its `d` was
+/// derived the same way. The key is rejected unless `p` divides `n` and a
+/// test exponentiation round-trips.
+pub fn reconstruct_rsa_key(
+    public: &RsaPublicKey,
+    shares: &[Share],
+) -> Result<Vec<u8>, ShamirError> {
+    let modulus = public.share_modulus()?;
+    let p = shamir_reconstruct(shares, &modulus)?;
+    let mismatch = ShamirError::InvalidKey("shares do not match the RSA public key");
+    if p <= BigUint::one() || !(&public.n % &p).is_zero() {
+        let mut p = p;
+        zeroize_biguint(&mut p);
+        return Err(mismatch);
+    }
+    let q = &public.n / &p;
+    let one = BigUint::one();
+    let mut lambda = (&p - &one).lcm(&(&q - &one));
+    let d = public.e.modinv(&lambda);
+    zeroize_biguint(&mut lambda);
+    let key = RsaPrivateKey {
+        n: public.n.clone(),
+        e: public.e.clone(),
+        d: d.ok_or(ShamirError::InvalidKey("e is not invertible"))?,
+        p,
+        q,
+    };
+    let probe = BigUint::from(2u32);
+    if modpow(&modpow(&probe, &key.e, &key.n), &key.d, &key.n) != probe {
+        return Err(mismatch);
+    }
+    Ok(encode_private_key(&key))
+}
+
+fn parse_private_key(input: &[u8]) -> Result<RsaPrivateKey, ShamirError> {
+    let mut outer = Reader::new(input);
+    let mut info = outer.sequence()?;
+    outer.finish()?;
+    if !info.uint()?.is_zero() {
+        return Err(ShamirError::InvalidKey("unsupported RSA key version"));
+    }
+    if info.peek_tag() != Some(der::SEQUENCE) {
+        return parse_rsa_fields(info);
+    }
+    let mut algorithm = info.sequence()?;
+    if algorithm.read(der::OID)? != RSA_ENCRYPTION {
+        return Err(ShamirError::InvalidKey("not an RSA key"));
+    }
+    if !algorithm.is_empty() {
+        algorithm.read(der::NULL)?;
+    }
+    algorithm.finish()?;
+    let mut inner = Reader::new(info.read(der::OCTET_STRING)?);
+    let mut fields = inner.sequence()?;
+    inner.finish()?;
+    if !fields.uint()?.is_zero() {
+        return Err(ShamirError::InvalidKey("unsupported RSA key version"));
+    }
+    parse_rsa_fields(fields)
+}
+
+/// Reads the RSAPrivateKey fields following the version.
+fn parse_rsa_fields(mut fields: Reader<'_>) -> Result<RsaPrivateKey, ShamirError> {
+    let key = RsaPrivateKey {
+        n: fields.uint()?,
+        e: fields.uint()?,
+        d: fields.uint()?,
+        p: fields.uint()?,
+        q: fields.uint()?,
+    };
+    for _ in 0..3 {
+        zeroize_biguint(&mut fields.uint()?);
+    }
+    fields.finish()?;
+    Ok(key)
+}
+
+fn encode_private_key(key: &RsaPrivateKey) -> Vec<u8> {
+    let mut crt = key.crt_parameters();
+    let [dp, dq, qinv] = &crt;
+
+    let mut fields = Vec::new();
+    der::write_uint(&mut fields, &BigUint::zero());
+    for value in [&key.n, &key.e, &key.d, &key.p, &key.q, dp, dq, qinv] {
+        der::write_uint(&mut fields, value);
+    }
+    let mut rsa_key = Vec::new();
+    der::write(&mut rsa_key, der::SEQUENCE, &fields);
+
+    let mut algorithm = Vec::new();
+    der::write(&mut algorithm, der::OID, RSA_ENCRYPTION);
+    der::write(&mut algorithm, der::NULL, &[]);
+
+    let mut info = Vec::new();
+    der::write_uint(&mut info, &BigUint::zero());
+    der::write(&mut info, der::SEQUENCE, &algorithm);
+    der::write(&mut info, der::OCTET_STRING, &rsa_key);
+    let mut out = Vec::new();
+    der::write(&mut out, der::SEQUENCE, &info);
+
+    fields.zeroize();
+    rsa_key.zeroize();
+    info.zeroize();
+    for value in &mut crt {
+        zeroize_biguint(value);
+    }
+    out
+}
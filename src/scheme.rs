@@ -0,0 +1,216 @@
+use alloc::vec::Vec;
+
+use num_bigint::BigUint;
+#[cfg(feature = "std")]
+use rand::SeedableRng;
+use rand::{CryptoRng, RngCore};
+#[cfg(feature = "std")]
+use rand_chacha::ChaCha20Rng;
+
+use crate::{
+    generate_large_prime, refresh_shares, shamir_reconstruct, shamir_reconstruct_ct,
+    shamir_split_shares, shamir_split_shares_deterministic, ShamirError, Share,
+};
+
+/// A configured sharing: threshold, share count and the prime modulus.
+///
+/// ```no_run
+/// # use num_bigint::BigUint;
+/// # use prime_pmpt::ShamirScheme;
+/// let scheme = ShamirScheme::builder()
+///     .threshold(6)
+///     .shares(8)
+///     .modulus_bits(1024)
+///     .build()?;
+/// let shares = scheme.split(&BigUint::from(42u32))?;
+/// assert_eq!(scheme.reconstruct(&shares[..6])?, BigUint::from(42u32));
+/// # Ok::<(), prime_pmpt::ShamirError>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(try_from = "crate::serde_support::SchemeRepr")
+)]
+pub struct ShamirScheme {
+    threshold: usize,
+    shares: usize,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::biguint_hex"))]
+    modulus: BigUint,
+}
+
+impl ShamirScheme {
+    pub fn builder() -> ShamirSchemeBuilder {
+        ShamirSchemeBuilder::default()
+    }
+
+    pub(crate) fn from_parts(
+        threshold: usize,
+        shares: usize,
+        modulus: BigUint,
+    ) -> Result<Self, ShamirError> {
+        if threshold < 2 || shares < threshold {
+            return Err(ShamirError::InvalidThreshold { threshold, shares });
+        }
+        if modulus <= BigUint::from(shares as u64) {
+            return Err(ShamirError::ModulusTooSmall { modulus, shares });
+        }
+        Ok(ShamirScheme {
+            threshold,
+            shares,
+            modulus,
+        })
+    }
+
+    pub fn threshold(&self) -> usize {
+        self.threshold
+    }
+
+    pub fn shares(&self) -> usize {
+        self.shares
+    }
+
+    pub fn modulus(&self) -> &BigUint {
+        &self.modulus
+    }
+
+    /// Splits `secret` into [`ShamirScheme::shares`] shares using a
+    /// `ChaCha20Rng` seeded from OS entropy.
+    #[cfg(feature = "std")]
+    pub fn split(&self, secret: &BigUint) -> Result<Vec<Share>, ShamirError> {
+        self.split_with_rng(secret, &mut ChaCha20Rng::from_entropy())
+    }
+
+    /// Like [`ShamirScheme::split`], drawing coefficients from `rng`.
+    pub fn split_with_rng<R: RngCore + CryptoRng + ?Sized>(
+        &self,
+        secret: &BigUint,
+        rng: &mut R,
+    ) -> Result<Vec<Share>, ShamirError> {
+        shamir_split_shares(secret, self.threshold, self.shares, &self.modulus, rng)
+    }
+
+    /// Splits `secret` reproducibly from a 32-byte `seed`; see
+    /// [`shamir_split_shares_deterministic`].
+    pub fn split_deterministic(
+        &self,
+        secret: &BigUint,
+        seed: &[u8; 32],
+    ) -> Result<Vec<Share>, ShamirError> {
+        shamir_split_shares_deterministic(secret, self.threshold, self.shares, &self.modulus, seed)
+    }
+
+    /// Rotates every share value while keeping the secret; see
+    /// [`refresh_shares`].
+    #[cfg(feature = "std")]
+    pub fn refresh(&self, shares: &[Share]) -> Result<Vec<Share>, ShamirError> {
+        self.refresh_with_rng(shares, &mut ChaCha20Rng::from_entropy())
+    }
+
+    /// Like [`ShamirScheme::refresh`], drawing the zero polynomial from `rng`.
+    pub fn refresh_with_rng<R: RngCore + CryptoRng + ?Sized>(
+        &self,
+        shares: &[Share],
+        rng: &mut R,
+    ) -> Result<Vec<Share>, ShamirError> {
+        if shares
+            .iter()
+            .any(|share| share.threshold() != self.threshold)
+        {
+            return Err(ShamirError::ThresholdMismatch);
+        }
+        refresh_shares(shares, &self.modulus, rng)
+    }
+
+    /// Recovers the secret from at least [`ShamirScheme::threshold`] shares.
+    pub fn reconstruct(&self, shares: &[Share]) -> Result<BigUint, ShamirError> {
+        self.check_shares(shares)?;
+        shamir_reconstruct(shares, &self.modulus)
+    }
+
+    /// Constant-time counterpart of [`ShamirScheme::reconstruct`]; see
+    /// [`shamir_reconstruct_ct`].
+    pub fn reconstruct_ct(&self, shares: &[Share]) -> Result<BigUint, ShamirError> {
+        self.check_shares(shares)?;
+        shamir_reconstruct_ct(shares, &self.modulus)
+    }
+
+    fn check_shares(&self, shares: &[Share]) -> Result<(), ShamirError> {
+        if shares.len() < self.threshold {
+            return Err(ShamirError::NotEnoughShares {
+                required: self.threshold,
+                provided: shares.len(),
+            });
+        }
+        if shares
+            .iter()
+            .any(|share| share.threshold() != self.threshold)
+        {
+            return Err(ShamirError::ThresholdMismatch);
+        }
+        Ok(())
+    }
+}
+
+/// Builder for [`ShamirScheme`].
+///
+/// Either an explicit [`modulus`](ShamirSchemeBuilder::modulus) or a
+/// [`modulus_bits`](ShamirSchemeBuilder::modulus_bits) size for a freshly
+/// generated prime must be supplied.
+#[derive(Debug, Clone, Default)]
+pub struct ShamirSchemeBuilder {
+    threshold: Option<usize>,
+    shares: Option<usize>,
+    modulus: Option<BigUint>,
+    modulus_bits: Option<usize>,
+}
+
+impl ShamirSchemeBuilder {
+    pub fn threshold(mut self, threshold: usize) -> Self {
+        self.threshold = Some(threshold);
+        self
+    }
+
+    pub fn shares(mut self, shares: usize) -> Self {
+        self.shares = Some(shares);
+        self
+    }
+
+    /// Uses `modulus` as the field prime. Takes precedence over `modulus_bits`.
+    pub fn modulus(mut self, modulus: BigUint) -> Self {
+        self.modulus = Some(modulus);
+        self
+    }
+
+    /// Generates a fresh prime of `bits` bits as the modulus.
+    pub fn modulus_bits(mut self, bits: usize) -> Self {
+        self.modulus_bits = Some(bits);
+        self
+    }
+
+    /// Builds the scheme, generating the modulus from OS entropy if needed.
+    #[cfg(feature = "std")]
+    pub fn build(self) -> Result<ShamirScheme, ShamirError> {
+        self.build_with_rng(&mut ChaCha20Rng::from_entropy())
+    }
+
+    /// Builds the scheme, generating the modulus from `rng` if needed.
+    pub fn build_with_rng<R: RngCore + CryptoRng + ?Sized>(
+        self,
+        rng: &mut R,
+    ) -> Result<ShamirScheme, ShamirError> {
+        let threshold = self
+            .threshold
+            .ok_or(ShamirError::MissingParameter("threshold"))?;
+        let shares = self.shares.ok_or(ShamirError::MissingParameter("shares"))?;
+        if threshold < 2 || shares < threshold {
+            return Err(ShamirError::InvalidThreshold { threshold, shares });
+        }
+        let modulus = match (self.modulus, self.modulus_bits) {
+            (Some(modulus), _) => modulus,
+            (None, Some(bits)) => generate_large_prime(bits, rng)?,
+            (None, None) => return Err(ShamirError::MissingParameter("modulus")),
+        };
+        ShamirScheme::from_parts(threshold, shares, modulus)
+    }
+}
@@ -0,0 +1,49 @@
+//! secp256k1 private keys shared over the curve order.
+//!
+//! Keys are the 32-byte big-endian scalars used by the `k256` and
+//! `secp256k1` crates. Sharing happens modulo the group order `n`, so every
+//! t-of-n reconstruction lands on the original scalar and can be loaded
+//! straight back into a signing key.
+
+use alloc::vec::Vec;
+
+use num_bigint::BigUint;
+use num_traits::Zero;
+use rand::{CryptoRng, RngCore};
+use zeroize::Zeroize;
+
+use crate::primes::standard::StandardPrime;
+use crate::{shamir_reconstruct, shamir_split_shares, zeroize_biguint, ShamirError, Share};
+
+/// Splits a secp256k1 private key, which must lie in `1..n`.
+pub fn split_secp256k1_key<R: RngCore + CryptoRng + ?Sized>(
+    key: &[u8; 32],
+    threshold: usize,
+    shares: usize,
+    rng: &mut R,
+) -> Result<Vec<Share>, ShamirError> {
+    let order = StandardPrime::Secp256k1Order.modulus();
+    let mut scalar = BigUint::from_bytes_be(key);
+    if scalar.is_zero() || scalar >= order {
+        zeroize_biguint(&mut scalar);
+        return Err(ShamirError::InvalidKey("secp256k1 keys must lie in 1..n"));
+    }
+    let result = shamir_split_shares(&scalar, threshold, shares, &order, rng);
+    zeroize_biguint(&mut scalar);
+    result
+}
+
+/// Recovers a secp256k1 private key as 32 big-endian bytes.
+pub fn reconstruct_secp256k1_key(shares: &[Share]) -> Result<[u8; 32], ShamirError> {
+    let order = StandardPrime::Secp256k1Order.modulus();
+    let mut scalar = shamir_reconstruct(shares, &order)?;
+    if scalar.is_zero() {
+        return Err(ShamirError::InvalidKey("reconstructed key is zero"));
+    }
+    let mut key = [0u8; 32];
+    let mut bytes = scalar.to_bytes_be();
+    key[32 - bytes.len()..].copy_from_slice(&bytes);
+    bytes.zeroize();
+    zeroize_biguint(&mut scalar);
+    Ok(key)
+}
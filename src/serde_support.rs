@@ -0,0 +1,376 @@
+//! Serde impls with a canonical layout: big integers and fingerprints are
+//! lowercase hex strings, so the same document reads identically in JSON,
+//! TOML or CBOR.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use num_bigint::BigUint;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{
+    ByteShare, DealerSignature, DhParams, Fingerprint, GroupParams, PedersenParams, SecretDigest,
+    ShamirError, ShamirScheme, Share, ShareMac, ShareSetId,
+};
+
+pub(crate) mod biguint_hex {
+    use super::*;
+
+    pub(crate) fn serialize<S: Serializer>(
+        value: &BigUint,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&value.to_str_radix(16))
+    }
+
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<BigUint, D::Error> {
+        let hex = String::deserialize(deserializer)?;
+        BigUint::parse_bytes(hex.as_bytes(), 16)
+            .ok_or_else(|| serde::de::Error::custom("invalid hex integer"))
+    }
+}
+
+pub(crate) mod option_biguint_hex {
+    use super::*;
+
+    pub(crate) fn serialize<S: Serializer>(
+        value: &Option<BigUint>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match value {
+            Some(value) => serializer.serialize_some(&value.to_str_radix(16)),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<BigUint>, D::Error> {
+        Option::<String>::deserialize(deserializer)?
+            .map(|hex| {
+                BigUint::parse_bytes(hex.as_bytes(), 16)
+                    .ok_or_else(|| serde::de::Error::custom("invalid hex integer"))
+            })
+            .transpose()
+    }
+}
+
+pub(crate) mod biguint_hex_seq {
+    use super::*;
+    use alloc::vec::Vec;
+    use serde::ser::SerializeSeq;
+
+    pub(crate) fn serialize<S: Serializer>(
+        values: &[BigUint],
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let mut seq = serializer.serialize_seq(Some(values.len()))?;
+        for value in values {
+            seq.serialize_element(&value.to_str_radix(16))?;
+        }
+        seq.end()
+    }
+
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<BigUint>, D::Error> {
+        Vec::<String>::deserialize(deserializer)?
+            .iter()
+            .map(|hex| {
+                BigUint::parse_bytes(hex.as_bytes(), 16)
+                    .ok_or_else(|| serde::de::Error::custom("invalid hex integer"))
+            })
+            .collect()
+    }
+}
+
+/// A share's x-coordinate: a plain integer while it fits in a `u64`, as
+/// before x-coordinates could be field elements, and a hex string beyond.
+pub(crate) mod share_x {
+    use super::*;
+    use core::fmt;
+    use serde::de::{self, Visitor};
+
+    pub(crate) fn serialize<S: Serializer>(
+        value: &BigUint,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match u64::try_from(value) {
+            Ok(x) => serializer.serialize_u64(x),
+            Err(_) => serializer.serialize_str(&value.to_str_radix(16)),
+        }
+    }
+
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<BigUint, D::Error> {
+        struct XVisitor;
+
+        impl Visitor<'_> for XVisitor {
+            type Value = BigUint;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("an integer or a hex string")
+            }
+
+            fn visit_u64<E: de::Error>(self, x: u64) -> Result<BigUint, E> {
+                Ok(BigUint::from(x))
+            }
+
+            fn visit_str<E: de::Error>(self, hex: &str) -> Result<BigUint, E> {
+                BigUint::parse_bytes(hex.as_bytes(), 16)
+                    .ok_or_else(|| E::custom("invalid hex integer"))
+            }
+        }
+
+        deserializer.deserialize_any(XVisitor)
+    }
+}
+
+pub(crate) mod bytes_hex {
+    use super::*;
+    use alloc::vec::Vec;
+
+    pub(crate) fn serialize<S: Serializer>(value: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&hex::encode(value))
+    }
+
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<u8>, D::Error> {
+        let encoded = String::deserialize(deserializer)?;
+        hex::decode(encoded).map_err(|_| serde::de::Error::custom("invalid hex bytes"))
+    }
+}
+
+pub(crate) mod array_hex {
+    use super::*;
+
+    pub(crate) fn serialize<S: Serializer, const N: usize>(
+        value: &[u8; N],
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&hex::encode(value))
+    }
+
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>, const N: usize>(
+        deserializer: D,
+    ) -> Result<[u8; N], D::Error> {
+        let encoded = String::deserialize(deserializer)?;
+        let mut value = [0u8; N];
+        hex::decode_to_slice(encoded, &mut value)
+            .map_err(|_| serde::de::Error::custom("invalid hex bytes"))?;
+        Ok(value)
+    }
+}
+
+impl Serialize for Fingerprint {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Fingerprint {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+impl Serialize for ShareSetId {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for ShareSetId {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+impl Serialize for ShareMac {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for ShareMac {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+impl Serialize for DealerSignature {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for DealerSignature {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+impl Serialize for SecretDigest {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for SecretDigest {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+#[derive(Deserialize)]
+pub(crate) struct ShareRepr {
+    #[serde(with = "share_x")]
+    x: BigUint,
+    #[serde(with = "biguint_hex")]
+    y: BigUint,
+    threshold: usize,
+    fingerprint: Fingerprint,
+    #[serde(default, with = "option_biguint_hex")]
+    blinding: Option<BigUint>,
+    #[serde(default)]
+    digest: Option<SecretDigest>,
+    #[serde(default)]
+    set_id: Option<ShareSetId>,
+    #[serde(default)]
+    mac: Option<ShareMac>,
+    #[serde(default)]
+    signature: Option<DealerSignature>,
+}
+
+impl TryFrom<ShareRepr> for Share {
+    type Error = ShamirError;
+
+    fn try_from(repr: ShareRepr) -> Result<Self, Self::Error> {
+        if repr.x.bits() == 0 {
+            return Err(ShamirError::MalformedShare("x must be nonzero"));
+        }
+        if repr.threshold < 2 {
+            return Err(ShamirError::MalformedShare("threshold must be at least 2"));
+        }
+        let mut share = Share::new(repr.x, repr.y, repr.threshold, repr.fingerprint);
+        if let Some(blinding) = repr.blinding {
+            share = share.with_blinding(blinding);
+        }
+        if let Some(digest) = repr.digest {
+            share = share.with_digest(digest);
+        }
+        if let Some(set_id) = repr.set_id {
+            share = share.with_set_id(set_id);
+        }
+        if let Some(mac) = repr.mac {
+            share = share.with_mac(mac);
+        }
+        if let Some(signature) = repr.signature {
+            share = share.with_signature(signature);
+        }
+        Ok(share)
+    }
+}
+
+#[derive(Deserialize)]
+pub(crate) struct ByteShareRepr {
+    x: u8,
+    threshold: usize,
+    #[serde(with = "bytes_hex")]
+    data: Vec<u8>,
+}
+
+impl TryFrom<ByteShareRepr> for ByteShare {
+    type Error = ShamirError;
+
+    fn try_from(repr: ByteShareRepr) -> Result<Self, Self::Error> {
+        if repr.x == 0 {
+            return Err(ShamirError::MalformedShare("x must be nonzero"));
+        }
+        Ok(ByteShare::new(repr.x, repr.threshold, repr.data))
+    }
+}
+
+#[derive(Deserialize)]
+pub(crate) struct SchemeRepr {
+    threshold: usize,
+    shares: usize,
+    #[serde(with = "biguint_hex")]
+    modulus: BigUint,
+}
+
+impl TryFrom<SchemeRepr> for ShamirScheme {
+    type Error = ShamirError;
+
+    fn try_from(repr: SchemeRepr) -> Result<Self, Self::Error> {
+        ShamirScheme::from_parts(repr.threshold, repr.shares, repr.modulus)
+    }
+}
+
+#[derive(Deserialize)]
+pub(crate) struct PedersenParamsRepr {
+    #[serde(with = "biguint_hex")]
+    p: BigUint,
+    #[serde(with = "biguint_hex")]
+    q: BigUint,
+    #[serde(with = "biguint_hex")]
+    g: BigUint,
+    #[serde(with = "biguint_hex")]
+    h: BigUint,
+}
+
+impl TryFrom<PedersenParamsRepr> for PedersenParams {
+    type Error = ShamirError;
+
+    fn try_from(repr: PedersenParamsRepr) -> Result<Self, Self::Error> {
+        PedersenParams::new(repr.p, repr.q, repr.g, repr.h)
+    }
+}
+
+#[derive(Deserialize)]
+pub(crate) struct DhParamsRepr {
+    #[serde(with = "biguint_hex")]
+    p: BigUint,
+    #[serde(with = "biguint_hex")]
+    g: BigUint,
+}
+
+impl TryFrom<DhParamsRepr> for DhParams {
+    type Error = ShamirError;
+
+    fn try_from(repr: DhParamsRepr) -> Result<Self, Self::Error> {
+        DhParams::new(repr.p, repr.g)
+    }
+}
+
+#[derive(Deserialize)]
+pub(crate) struct GroupParamsRepr {
+    #[serde(with = "biguint_hex")]
+    p: BigUint,
+    #[serde(with = "biguint_hex")]
+    q: BigUint,
+    #[serde(with = "biguint_hex")]
+    g: BigUint,
+}
+
+impl TryFrom<GroupParamsRepr> for GroupParams {
+    type Error = ShamirError;
+
+    fn try_from(repr: GroupParamsRepr) -> Result<Self, Self::Error> {
+        GroupParams::new(repr.p, repr.q, repr.g)
+    }
+}
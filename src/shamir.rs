@@ -0,0 +1,612 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+use num_bigint::{BigUint, RandBigInt};
+use num_integer::Integer;
+use num_traits::{One, Zero};
+use rand::{CryptoRng, RngCore, SeedableRng};
+use rand_chacha::ChaCha20Rng;
+use sha3::{Digest, Sha3_256};
+use zeroize::Zeroize;
+
+use crate::montgomery::MontgomeryModulus;
+use crate::polynomial::Polynomial;
+use crate::primes::{is_accepted_prime, is_baillie_psw_prime};
+use crate::{zeroize_biguint, Fingerprint, SecretDigest, ShamirError, Share, ShareSetId};
+
+/// Splits `secret` into `shares` points on a random polynomial of degree
+/// `threshold - 1` over the prime field defined by `modulus`, drawing the
+/// coefficients from `rng`.
+///
+/// Shares are numbered with `x` running from `1` to `shares` and stamped
+/// with a fresh [`ShareSetId`].
+/// The threshold must satisfy `2 <= threshold <= shares`, the secret must be
+/// reduced modulo `modulus`, and the modulus must exceed `shares` so every
+/// x-coordinate is a distinct nonzero field element.
+#[tracing::instrument(level = "debug", skip(secret, modulus, rng), fields(modulus = %Fingerprint::of(modulus)))]
+pub fn shamir_split_shares<R: RngCore + CryptoRng + ?Sized>(
+    secret: &BigUint,
+    threshold: usize,
+    shares: usize,
+    modulus: &BigUint,
+    rng: &mut R,
+) -> Result<Vec<Share>, ShamirError> {
+    check_split_params(secret, threshold, shares, modulus)?;
+    let xs: Vec<BigUint> = (1..=shares).map(BigUint::from).collect();
+    Ok(split_at(secret, threshold, &xs, modulus, rng))
+}
+
+/// Like [`shamir_split_shares`], with one share at each of the caller's
+/// x-coordinates instead of `1..=shares`, such as stable participant IDs
+/// or hashes of names reduced into the field.
+///
+/// The x-coordinates must be nonzero, distinct, and below `modulus` so that
+/// no two are the same field element; otherwise the split fails with
+/// [`ShamirError::InvalidShareX`] or [`ShamirError::DuplicateShareX`].
+#[tracing::instrument(level = "debug", skip(secret, xs, modulus, rng), fields(shares = xs.len(), modulus = %Fingerprint::of(modulus)))]
+pub fn shamir_split_shares_at<R: RngCore + CryptoRng + ?Sized>(
+    secret: &BigUint,
+    threshold: usize,
+    xs: &[BigUint],
+    modulus: &BigUint,
+    rng: &mut R,
+) -> Result<Vec<Share>, ShamirError> {
+    check_split_params(secret, threshold, xs.len(), modulus)?;
+    check_xs(xs, modulus)?;
+    Ok(split_at(secret, threshold, xs, modulus, rng))
+}
+
+/// Rejects x-coordinates that are zero, not below `modulus` or repeated.
+fn check_xs(xs: &[BigUint], modulus: &BigUint) -> Result<(), ShamirError> {
+    for (i, x) in xs.iter().enumerate() {
+        if x.is_zero() || x >= modulus {
+            return Err(ShamirError::InvalidShareX(x.clone()));
+        }
+        if xs[..i].contains(x) {
+            return Err(ShamirError::DuplicateShareX(x.clone()));
+        }
+    }
+    Ok(())
+}
+
+/// Like [`shamir_split_shares`], with distinct x-coordinates drawn uniformly
+/// from the nonzero field elements.
+///
+/// Numbering from 1 tells every holder their place in the order and lets
+/// anyone match up the shares of different secrets split among the same
+/// people; random coordinates reveal neither.
+#[tracing::instrument(level = "debug", skip(secret, modulus, rng), fields(modulus = %Fingerprint::of(modulus)))]
+pub fn shamir_split_shares_random_x<R: RngCore + CryptoRng + ?Sized>(
+    secret: &BigUint,
+    threshold: usize,
+    shares: usize,
+    modulus: &BigUint,
+    rng: &mut R,
+) -> Result<Vec<Share>, ShamirError> {
+    check_split_params(secret, threshold, shares, modulus)?;
+    let mut xs = Vec::with_capacity(shares);
+    while xs.len() < shares {
+        let x = rng.gen_biguint_range(&BigUint::one(), modulus);
+        if !xs.contains(&x) {
+            xs.push(x);
+        }
+    }
+    Ok(split_at(secret, threshold, &xs, modulus, rng))
+}
+
+/// Shares `secret` at the already validated `xs`.
+fn split_at<R: RngCore + CryptoRng + ?Sized>(
+    secret: &BigUint,
+    threshold: usize,
+    xs: &[BigUint],
+    modulus: &BigUint,
+    rng: &mut R,
+) -> Vec<Share> {
+    let polynomial = Polynomial::random(secret, threshold - 1, modulus, rng);
+    let fingerprint = Fingerprint::of(modulus);
+    let set_id = ShareSetId::random(rng);
+    let result = xs
+        .iter()
+        .zip(polynomial.evaluate_all(xs, modulus))
+        .map(|(x, y)| Share::new(x.clone(), y, threshold, fingerprint).with_set_id(set_id))
+        .collect();
+    tracing::debug!(
+        "split into {} shares with threshold {threshold} under modulus {fingerprint}, set {set_id}",
+        xs.len()
+    );
+    result
+}
+
+/// Like [`shamir_split_shares`], stamping every share with a salted
+/// [`SecretDigest`] of the secret so reconstruction from bad shares fails
+/// with [`ShamirError::IntegrityFailure`] instead of returning a wrong value.
+///
+/// The digest lets a single shareholder confirm guesses of the secret; use
+/// it only for high-entropy secrets.
+pub fn shamir_split_shares_with_digest<R: RngCore + CryptoRng + ?Sized>(
+    secret: &BigUint,
+    threshold: usize,
+    shares: usize,
+    modulus: &BigUint,
+    rng: &mut R,
+) -> Result<Vec<Share>, ShamirError> {
+    let shares = shamir_split_shares(secret, threshold, shares, modulus, rng)?;
+    let mut salt = [0u8; 16];
+    rng.fill_bytes(&mut salt);
+    let digest = SecretDigest::compute(secret, salt);
+    Ok(shares
+        .into_iter()
+        .map(|share| share.with_digest(digest))
+        .collect())
+}
+
+/// Like [`shamir_split_shares`], but every share's `y` value is a probable
+/// prime, for protocols that need prime-valued shares.
+///
+/// The x-coordinates are no longer `1..=shares`: `x` counts up from 1 and
+/// each value where the polynomial is prime becomes the next share, so
+/// about `ln(modulus)` values are tried per share. Each `y` must pass the
+/// same tests as [`generate_large_prime`](crate::generate_large_prime).
+/// After `max_candidates` values of `x`, or when `x` reaches the modulus,
+/// the split fails with [`ShamirError::SearchExhausted`].
+///
+/// Knowing that the other shares are prime tells anyone holding
+/// `threshold - 1` shares about `log2(ln(modulus))` bits of the secret per
+/// other share's `x`. Keep the modulus well above the secret's own
+/// entropy.
+#[tracing::instrument(level = "debug", skip(secret, modulus, rng), fields(modulus = %Fingerprint::of(modulus)))]
+pub fn shamir_split_prime_shares<R: RngCore + CryptoRng + ?Sized>(
+    secret: &BigUint,
+    threshold: usize,
+    shares: usize,
+    modulus: &BigUint,
+    max_candidates: u64,
+    rng: &mut R,
+) -> Result<Vec<Share>, ShamirError> {
+    check_split_params(secret, threshold, shares, modulus)?;
+    let polynomial = Polynomial::random(secret, threshold - 1, modulus, rng);
+    let fingerprint = Fingerprint::of(modulus);
+    let set_id = ShareSetId::random(rng);
+    let mut result = Vec::with_capacity(shares);
+    let mut candidates = 0u64;
+    while result.len() < shares {
+        let x = BigUint::from(candidates + 1);
+        if candidates == max_candidates || x >= *modulus {
+            tracing::debug!(
+                "gave up on prime shares after {candidates} candidates, {} found",
+                result.len()
+            );
+            return Err(ShamirError::SearchExhausted(candidates));
+        }
+        candidates += 1;
+        let mut y = polynomial.evaluate(&x, modulus);
+        if is_accepted_prime(&y, rng) {
+            result.push(Share::new(x, y, threshold, fingerprint).with_set_id(set_id));
+        } else {
+            zeroize_biguint(&mut y);
+        }
+    }
+    tracing::debug!(
+        "split into {shares} prime shares with threshold {threshold} after {candidates} candidates, set {set_id}"
+    );
+    Ok(result)
+}
+
+/// Checks that `modulus` can carry `secret` split into `shares` shares with
+/// the given threshold, before anything is split.
+///
+/// The splitting functions check the threshold and sizes but accept any
+/// modulus; over a composite one, reconstruction fails for any two
+/// x-coordinates that differ by a multiple of one of its factors.
+/// Primality is decided by
+/// [`is_baillie_psw_prime`](crate::is_baillie_psw_prime), so no randomness
+/// is needed. The modulus must also exceed the secret and the largest
+/// x-coordinate, `shares`.
+pub fn validate_modulus(
+    modulus: &BigUint,
+    secret: &BigUint,
+    threshold: usize,
+    shares: usize,
+) -> Result<(), ShamirError> {
+    if !is_baillie_psw_prime(modulus) {
+        tracing::debug!("rejected a composite {}-bit modulus", modulus.bits());
+        return Err(ShamirError::InvalidModulus("modulus is not prime"));
+    }
+    check_split_params(secret, threshold, shares, modulus)
+}
+
+pub(crate) fn check_split_params(
+    secret: &BigUint,
+    threshold: usize,
+    shares: usize,
+    modulus: &BigUint,
+) -> Result<(), ShamirError> {
+    if threshold < 2 || shares < threshold {
+        return Err(ShamirError::InvalidThreshold { threshold, shares });
+    }
+    if *modulus <= BigUint::from(shares as u64) {
+        return Err(ShamirError::ModulusTooSmall {
+            modulus: modulus.clone(),
+            shares,
+        });
+    }
+    if secret >= modulus {
+        return Err(ShamirError::SecretTooLarge);
+    }
+    Ok(())
+}
+
+/// Deterministic variant of [`shamir_split_shares`]: the same secret, seed
+/// and parameters always yield the same shares.
+///
+/// The coefficients come from a `ChaCha20Rng` keyed with a SHA3-256 digest
+/// of the seed, secret, threshold, share count and modulus, so reusing a seed
+/// for a different secret still produces an unrelated polynomial. The seed
+/// must be kept as secret as the shares themselves.
+pub fn shamir_split_shares_deterministic(
+    secret: &BigUint,
+    threshold: usize,
+    shares: usize,
+    modulus: &BigUint,
+    seed: &[u8; 32],
+) -> Result<Vec<Share>, ShamirError> {
+    let mut hasher = Sha3_256::new();
+    hasher.update(b"prime-shamir/deterministic-split/v1");
+    hasher.update(seed);
+    for value in [secret, modulus] {
+        let bytes = value.to_bytes_be();
+        hasher.update((bytes.len() as u64).to_be_bytes());
+        hasher.update(&bytes);
+    }
+    hasher.update((threshold as u64).to_be_bytes());
+    hasher.update((shares as u64).to_be_bytes());
+    let mut key: [u8; 32] = hasher.finalize().into();
+    let mut rng = ChaCha20Rng::from_seed(key);
+    key.zeroize();
+    shamir_split_shares(secret, threshold, shares, modulus, &mut rng)
+}
+
+/// Recovers the secret from at least `threshold` shares by Lagrange
+/// interpolation at zero.
+///
+/// `modulus` must be the one used when splitting; shares carrying another
+/// modulus fingerprint or a different threshold are rejected, as are sets
+/// that repeat an x-coordinate, with [`ShamirError::DuplicateShareX`] before
+/// any interpolation. The modulus need not be prime: inverses come from the
+/// extended Euclidean algorithm, so a composite such as a CRT product of
+/// primes works whenever the differences between the x-coordinates are
+/// coprime to it, and fails with [`ShamirError::NonInvertibleDenominator`],
+/// naming the offending pair, otherwise. If the shares carry a
+/// [`SecretDigest`], the result is checked against it. Intermediate products
+/// are wiped; the returned secret is the caller's to wipe with
+/// [`zeroize_biguint`].
+#[tracing::instrument(level = "debug", skip_all, fields(shares = shares.len(), modulus = %Fingerprint::of(modulus)))]
+pub fn shamir_reconstruct(shares: &[Share], modulus: &BigUint) -> Result<BigUint, ShamirError> {
+    log_reconstruct(shares, modulus);
+    check_share_set(shares, modulus)?;
+    let xs: Vec<BigUint> = shares.iter().map(|share| share.x().clone()).collect();
+    let mut reconstructed = BigUint::zero();
+    for (i, share) in shares.iter().enumerate() {
+        let lagrange_coeff = lagrange_coefficient(&xs, i, modulus)?;
+        let mut term = lagrange_coeff * share.y();
+        reconstructed += &term;
+        reconstructed %= modulus;
+        zeroize_biguint(&mut term);
+    }
+    check_digest(shares, reconstructed)
+}
+
+/// Constant-time variant of [`shamir_reconstruct`] for hosts where timing
+/// may be observed.
+///
+/// Requires an odd modulus, and share values below it: a share whose `y`
+/// is not reduced is rejected as malformed rather than reduced, since
+/// dividing by the modulus would take time that depends on `y`.
+///
+/// Constant-time: loading each `y` into fixed-width Montgomery limbs and
+/// the check that it is below the modulus, and the sum of
+/// `coefficient * y`, whose timing depends only on the modulus size. The
+/// one exception is the limb count of each `y`, which `BigUint` stores.
+///
+/// Not constant-time: the share-set checks and the Lagrange coefficients,
+/// which depend only on public metadata and x-coordinates, and turning the
+/// sum back into a `BigUint` and checking it against a [`SecretDigest`],
+/// whose timing depends on the secret's length.
+#[tracing::instrument(level = "debug", skip_all, fields(shares = shares.len(), modulus = %Fingerprint::of(modulus)))]
+pub fn shamir_reconstruct_ct(shares: &[Share], modulus: &BigUint) -> Result<BigUint, ShamirError> {
+    log_reconstruct(shares, modulus);
+    check_share_set(shares, modulus)?;
+    let field = MontgomeryModulus::new(modulus).ok_or(ShamirError::InvalidModulus(
+        "constant-time reconstruction needs an odd modulus",
+    ))?;
+    let xs: Vec<BigUint> = shares.iter().map(|share| share.x().clone()).collect();
+    let mut acc = vec![0u64; field.width()];
+    for (i, share) in shares.iter().enumerate() {
+        let coeff = field.to_montgomery(&lagrange_coefficient(&xs, i, modulus)?);
+        let mut y = field.load(share.y()).ok_or(ShamirError::MalformedShare(
+            "share value is not below the modulus",
+        ))?;
+        let mut term = field.mul(&coeff, &y);
+        let mut sum = field.add(&acc, &term);
+        core::mem::swap(&mut acc, &mut sum);
+        y.zeroize();
+        term.zeroize();
+        sum.zeroize();
+    }
+    let reconstructed = MontgomeryModulus::to_biguint(&acc);
+    acc.zeroize();
+    check_digest(shares, reconstructed)
+}
+
+/// The Lagrange coefficients at zero for a fixed list of x-coordinates.
+///
+/// They depend only on the x-coordinates, so when many secrets are shared
+/// at the same points, such as the blocks of a chunked secret, computing
+/// them once saves an inversion per share in every later
+/// [`reconstruct_with_basis`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LagrangeBasis {
+    xs: Vec<BigUint>,
+    coefficients: Vec<BigUint>,
+    modulus: BigUint,
+}
+
+impl LagrangeBasis {
+    /// Computes the coefficients for `xs`, which must be nonzero, distinct
+    /// and below `modulus`. Like [`shamir_reconstruct`], fails with
+    /// [`ShamirError::NonInvertibleDenominator`] when two points differ by
+    /// a non-unit of a composite modulus.
+    pub fn new(xs: &[BigUint], modulus: &BigUint) -> Result<Self, ShamirError> {
+        if xs.is_empty() {
+            return Err(ShamirError::NotEnoughShares {
+                required: 1,
+                provided: 0,
+            });
+        }
+        check_xs(xs, modulus)?;
+        let coefficients = (0..xs.len())
+            .map(|i| lagrange_coefficient(xs, i, modulus))
+            .collect::<Result<_, _>>()?;
+        Ok(LagrangeBasis {
+            xs: xs.to_vec(),
+            coefficients,
+            modulus: modulus.clone(),
+        })
+    }
+
+    pub fn xs(&self) -> &[BigUint] {
+        &self.xs
+    }
+
+    /// The coefficient of each point, in the order of [`LagrangeBasis::xs`].
+    pub fn coefficients(&self) -> &[BigUint] {
+        &self.coefficients
+    }
+
+    pub fn modulus(&self) -> &BigUint {
+        &self.modulus
+    }
+}
+
+/// Like [`shamir_reconstruct`], with the Lagrange coefficients taken from
+/// `basis` instead of computed afresh.
+///
+/// `shares` must lie at the basis x-coordinates, in the same order, or the
+/// call fails with [`ShamirError::BasisMismatch`]. All the checks of
+/// [`shamir_reconstruct`] still apply, so the share set must also meet its
+/// threshold.
+pub fn reconstruct_with_basis(
+    shares: &[Share],
+    basis: &LagrangeBasis,
+) -> Result<BigUint, ShamirError> {
+    let modulus = &basis.modulus;
+    log_reconstruct(shares, modulus);
+    check_share_set(shares, modulus)?;
+    if shares.len() != basis.xs.len()
+        || shares
+            .iter()
+            .zip(&basis.xs)
+            .any(|(share, x)| share.x() != x)
+    {
+        return Err(ShamirError::BasisMismatch);
+    }
+    let mut reconstructed = BigUint::zero();
+    for (share, coefficient) in shares.iter().zip(&basis.coefficients) {
+        let mut term = coefficient * share.y();
+        reconstructed += &term;
+        reconstructed %= modulus;
+        zeroize_biguint(&mut term);
+    }
+    check_digest(shares, reconstructed)
+}
+
+/// Reconstructs one secret from each share set, where every set lies at
+/// the same x-coordinates in the same order, such as the blocks of a large
+/// secret or keys split for the same holders.
+///
+/// The Lagrange coefficients are computed once, from the first set, and
+/// every secret is a dot product with them; a set at other points fails
+/// with [`ShamirError::BasisMismatch`]. If any set fails, the secrets
+/// already recovered are wiped before the error is returned.
+pub fn reconstruct_batch(
+    sets: &[Vec<Share>],
+    modulus: &BigUint,
+) -> Result<Vec<BigUint>, ShamirError> {
+    let Some(first) = sets.first() else {
+        return Ok(Vec::new());
+    };
+    check_share_set(first, modulus)?;
+    let xs: Vec<BigUint> = first.iter().map(|share| share.x().clone()).collect();
+    let basis = LagrangeBasis::new(&xs, modulus)?;
+    let mut secrets = Vec::with_capacity(sets.len());
+    for shares in sets {
+        match reconstruct_with_basis(shares, &basis) {
+            Ok(secret) => secrets.push(secret),
+            Err(err) => {
+                secrets.iter_mut().for_each(zeroize_biguint);
+                return Err(err);
+            }
+        }
+    }
+    Ok(secrets)
+}
+
+fn log_reconstruct(shares: &[Share], modulus: &BigUint) {
+    let xs: Vec<BigUint> = shares.iter().map(|share| share.x().clone()).collect();
+    tracing::debug!(
+        "reconstructing from shares at x = {xs:?} under modulus {}",
+        Fingerprint::of(modulus)
+    );
+}
+
+/// Checks a reconstructed secret against the shares' digests. Shares that
+/// disagree on the digest, or where only some carry one, fail as well.
+pub(crate) fn check_digest(shares: &[Share], mut secret: BigUint) -> Result<BigUint, ShamirError> {
+    let digest = shares.first().and_then(Share::digest);
+    let consistent = shares.iter().all(|share| share.digest() == digest);
+    if consistent && digest.is_none_or(|digest| digest.matches(&secret)) {
+        return Ok(secret);
+    }
+    zeroize_biguint(&mut secret);
+    tracing::warn!("reconstructed secret does not match the recorded digest");
+    Err(ShamirError::IntegrityFailure)
+}
+
+/// [`check_digest`] for schemes that recover several values, whose digest
+/// comes from [`SecretDigest::compute_all`].
+pub(crate) fn check_digest_all(
+    shares: &[Share],
+    mut values: Vec<BigUint>,
+) -> Result<Vec<BigUint>, ShamirError> {
+    let digest = shares.first().and_then(Share::digest);
+    let consistent = shares.iter().all(|share| share.digest() == digest);
+    if consistent && digest.is_none_or(|digest| digest.matches_all(&values)) {
+        return Ok(values);
+    }
+    values.iter_mut().for_each(zeroize_biguint);
+    tracing::warn!("recovered values do not match the recorded digest");
+    Err(ShamirError::IntegrityFailure)
+}
+
+/// Rejects share sets that are empty, below their threshold, repeat an
+/// x-coordinate, or mix moduli, thresholds or share sets.
+pub(crate) fn check_share_set(shares: &[Share], modulus: &BigUint) -> Result<(), ShamirError> {
+    let first = check_compatible(shares, modulus)?;
+    check_same_set(shares)?;
+    // A share passed twice would otherwise count towards the threshold.
+    for (i, share) in shares.iter().enumerate() {
+        if shares[..i].iter().any(|other| other.x() == share.x()) {
+            return Err(ShamirError::DuplicateShareX(share.x().clone()));
+        }
+    }
+    if shares.len() < first.threshold() {
+        return Err(ShamirError::NotEnoughShares {
+            required: first.threshold(),
+            provided: shares.len(),
+        });
+    }
+    Ok(())
+}
+
+/// Rejects shares stamped with different [`ShareSetId`]s, or where only some
+/// are stamped.
+pub(crate) fn check_same_set(shares: &[Share]) -> Result<(), ShamirError> {
+    let set_id = shares.first().and_then(Share::set_id);
+    if shares.iter().any(|share| share.set_id() != set_id) {
+        return Err(ShamirError::SetMismatch);
+    }
+    Ok(())
+}
+
+/// Rejects empty share sets, thresholds below 2, shares from another
+/// modulus or threshold and x-coordinates that are zero or not reduced
+/// modulo the field, returning the first share. A share at zero, or a
+/// single share of threshold 1, would hand back its value as the secret.
+pub(crate) fn check_compatible<'a>(
+    shares: &'a [Share],
+    modulus: &BigUint,
+) -> Result<&'a Share, ShamirError> {
+    let first = shares.first().ok_or(ShamirError::NotEnoughShares {
+        required: 1,
+        provided: 0,
+    })?;
+    if first.threshold() < 2 {
+        return Err(ShamirError::InvalidThreshold {
+            threshold: first.threshold(),
+            shares: shares.len(),
+        });
+    }
+    let fingerprint = Fingerprint::of(modulus);
+    for share in shares {
+        if share.fingerprint() != fingerprint {
+            return Err(ShamirError::ModulusMismatch);
+        }
+        if share.threshold() != first.threshold() {
+            return Err(ShamirError::ThresholdMismatch);
+        }
+        if share.x().is_zero() || share.x() >= modulus {
+            return Err(ShamirError::InvalidShareX(share.x().clone()));
+        }
+    }
+    Ok(first)
+}
+
+/// Lagrange basis polynomial for `xs[i]` over the points `xs`, evaluated at
+/// zero.
+pub(crate) fn lagrange_coefficient(
+    xs: &[BigUint],
+    i: usize,
+    modulus: &BigUint,
+) -> Result<BigUint, ShamirError> {
+    lagrange_coefficient_at(xs, i, &BigUint::zero(), modulus)
+}
+
+/// Lagrange basis polynomial for `xs[i]` over the points `xs`, evaluated at
+/// `at`.
+pub(crate) fn lagrange_coefficient_at(
+    xs: &[BigUint],
+    i: usize,
+    at: &BigUint,
+    modulus: &BigUint,
+) -> Result<BigUint, ShamirError> {
+    let xi = &xs[i];
+    let xi_reduced = xi % modulus;
+    let at = at % modulus;
+    let mut numerator = BigUint::one();
+    let mut denominator = BigUint::one();
+    for (j, xj) in xs.iter().enumerate() {
+        if i != j {
+            if xi == xj {
+                return Err(ShamirError::DuplicateShareX(xi.clone()));
+            }
+            let xj = xj % modulus;
+            let diff = (&xj + modulus - &xi_reduced) % modulus;
+            numerator = (numerator * ((xj + modulus - &at) % modulus)) % modulus;
+            denominator = (denominator * diff) % modulus;
+        }
+    }
+    // Extended Euclid rather than Fermat, which would give a wrong inverse
+    // for a composite modulus.
+    match denominator.modinv(modulus) {
+        Some(denominator_inv) => Ok((numerator * denominator_inv) % modulus),
+        None => Err(non_invertible_pair(xs, i, modulus)),
+    }
+}
+
+/// The error for a non-invertible denominator of `xs[i]`, found by a gcd
+/// of each difference `xs[i] - xs[j]` with `modulus` and naming the first
+/// point whose difference is not a unit. The denominator is invertible
+/// exactly when every difference is, so such a point should always exist;
+/// if none turns up, the error is [`ShamirError::SingularSystem`].
+fn non_invertible_pair(xs: &[BigUint], i: usize, modulus: &BigUint) -> ShamirError {
+    let xi = &xs[i];
+    xs.iter()
+        .enumerate()
+        .find(|&(j, xj)| {
+            let diff = if xi > xj { xi - xj } else { xj - xi };
+            j != i && !diff.gcd(modulus).is_one()
+        })
+        .map_or(ShamirError::SingularSystem, |(_, xj)| {
+            ShamirError::NonInvertibleDenominator(xi.clone(), xj.clone())
+        })
+}
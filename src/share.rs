@@ -0,0 +1,708 @@
+use alloc::vec::Vec;
+use core::fmt;
+use core::str::FromStr;
+
+use num_bigint::BigUint;
+use num_traits::Zero;
+use rand::{CryptoRng, RngCore};
+use sha2::{Digest as _, Sha256};
+use sha3::{Digest, Sha3_256};
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+use crate::{zeroize_biguint, ShamirError};
+
+/// Short SHA3-256 digest identifying the modulus a share was produced under.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Fingerprint([u8; 8]);
+
+impl Fingerprint {
+    /// Computes the fingerprint of `modulus`.
+    pub fn of(modulus: &BigUint) -> Self {
+        let digest = Sha3_256::digest(modulus.to_bytes_be());
+        let mut bytes = [0u8; 8];
+        bytes.copy_from_slice(&digest[..8]);
+        Fingerprint(bytes)
+    }
+
+    pub fn from_bytes(bytes: [u8; 8]) -> Self {
+        Fingerprint(bytes)
+    }
+
+    pub fn as_bytes(&self) -> &[u8; 8] {
+        &self.0
+    }
+}
+
+impl fmt::Display for Fingerprint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&hex::encode(self.0))
+    }
+}
+
+impl FromStr for Fingerprint {
+    type Err = ShamirError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut bytes = [0u8; 8];
+        hex::decode_to_slice(s, &mut bytes)
+            .map_err(|_| ShamirError::MalformedShare("invalid fingerprint"))?;
+        Ok(Fingerprint(bytes))
+    }
+}
+
+/// Random 128-bit identifier shared by every share of one split, so shares
+/// from different splits are not combined by mistake.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ShareSetId([u8; 16]);
+
+impl ShareSetId {
+    /// Draws a fresh identifier from `rng`.
+    pub fn random<R: RngCore + CryptoRng + ?Sized>(rng: &mut R) -> Self {
+        let mut bytes = [0u8; 16];
+        rng.fill_bytes(&mut bytes);
+        ShareSetId(bytes)
+    }
+
+    pub fn from_bytes(bytes: [u8; 16]) -> Self {
+        ShareSetId(bytes)
+    }
+
+    /// An identifier taken from the start of `hash`, for share sets whose
+    /// holders must agree on it without a dealer to draw one.
+    pub(crate) fn from_hash(hash: &[u8]) -> Self {
+        let mut bytes = [0u8; 16];
+        bytes.copy_from_slice(&hash[..16]);
+        ShareSetId(bytes)
+    }
+
+    pub fn as_bytes(&self) -> &[u8; 16] {
+        &self.0
+    }
+}
+
+impl fmt::Display for ShareSetId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&hex::encode(self.0))
+    }
+}
+
+impl FromStr for ShareSetId {
+    type Err = ShamirError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut bytes = [0u8; 16];
+        hex::decode_to_slice(s, &mut bytes)
+            .map_err(|_| ShamirError::MalformedShare("invalid share set id"))?;
+        Ok(ShareSetId(bytes))
+    }
+}
+
+/// HMAC-SHA256 tag over a share, checked against the share set's
+/// [`VerificationKey`](crate::VerificationKey) to detect corruption in storage.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ShareMac([u8; 32]);
+
+impl ShareMac {
+    pub fn from_bytes(bytes: [u8; 32]) -> Self {
+        ShareMac(bytes)
+    }
+
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        &self.0
+    }
+}
+
+impl fmt::Display for ShareMac {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&hex::encode(self.0))
+    }
+}
+
+impl FromStr for ShareMac {
+    type Err = ShamirError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut bytes = [0u8; 32];
+        hex::decode_to_slice(s, &mut bytes)
+            .map_err(|_| ShamirError::MalformedShare("invalid share MAC"))?;
+        Ok(ShareMac(bytes))
+    }
+}
+
+/// A dealer's Ed25519 signature over a share, with the first eight bytes of
+/// the SHA3-256 digest of the dealer's public key identifying the signer.
+/// Written as 144 hex digits, key ID first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DealerSignature {
+    key_id: [u8; 8],
+    signature: [u8; 64],
+}
+
+impl DealerSignature {
+    pub fn from_parts(key_id: [u8; 8], signature: [u8; 64]) -> Self {
+        DealerSignature { key_id, signature }
+    }
+
+    pub fn key_id(&self) -> &[u8; 8] {
+        &self.key_id
+    }
+
+    pub fn signature(&self) -> &[u8; 64] {
+        &self.signature
+    }
+
+    pub(crate) fn to_bytes(self) -> [u8; 72] {
+        let mut bytes = [0u8; 72];
+        bytes[..8].copy_from_slice(&self.key_id);
+        bytes[8..].copy_from_slice(&self.signature);
+        bytes
+    }
+
+    pub(crate) fn from_bytes(bytes: &[u8; 72]) -> Self {
+        DealerSignature {
+            key_id: bytes[..8].try_into().expect("8 bytes"),
+            signature: bytes[8..].try_into().expect("64 bytes"),
+        }
+    }
+}
+
+impl fmt::Display for DealerSignature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}{}",
+            hex::encode(self.key_id),
+            hex::encode(self.signature)
+        )
+    }
+}
+
+impl FromStr for DealerSignature {
+    type Err = ShamirError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut bytes = [0u8; 72];
+        hex::decode_to_slice(s, &mut bytes)
+            .map_err(|_| ShamirError::MalformedShare("invalid dealer signature"))?;
+        Ok(DealerSignature::from_bytes(&bytes))
+    }
+}
+
+/// Salted SHA-256 digest of a secret, carried in share metadata so
+/// reconstruction can tell a wrong result from the right one.
+///
+/// Anyone holding a single share can test guesses against the digest, so it
+/// should only be attached to secrets with enough entropy to resist
+/// guessing, such as keys. Written as 96 hex digits, salt first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SecretDigest {
+    salt: [u8; 16],
+    hash: [u8; 32],
+}
+
+impl SecretDigest {
+    /// Digests `secret` under `salt`.
+    pub fn compute(secret: &BigUint, salt: [u8; 16]) -> Self {
+        let mut hasher = Sha256::new();
+        hasher.update(b"prime-shamir/secret-digest/v2");
+        hasher.update(salt);
+        let mut bytes = secret.to_bytes_be();
+        hasher.update((bytes.len() as u64).to_be_bytes());
+        hasher.update(&bytes);
+        bytes.zeroize();
+        SecretDigest {
+            salt,
+            hash: hasher.finalize().into(),
+        }
+    }
+
+    pub fn from_parts(salt: [u8; 16], hash: [u8; 32]) -> Self {
+        SecretDigest { salt, hash }
+    }
+
+    pub fn salt(&self) -> &[u8; 16] {
+        &self.salt
+    }
+
+    pub fn hash(&self) -> &[u8; 32] {
+        &self.hash
+    }
+
+    /// Digests a list of values under `salt`, for the packed, ramp and
+    /// erasure schemes, which recover several values from one share set.
+    pub fn compute_all(values: &[BigUint], salt: [u8; 16]) -> Self {
+        let mut hasher = Sha256::new();
+        hasher.update(b"prime-shamir/secret-list-digest/v2");
+        hasher.update(salt);
+        hasher.update((values.len() as u64).to_be_bytes());
+        for value in values {
+            let mut bytes = value.to_bytes_be();
+            hasher.update((bytes.len() as u64).to_be_bytes());
+            hasher.update(&bytes);
+            bytes.zeroize();
+        }
+        SecretDigest {
+            salt,
+            hash: hasher.finalize().into(),
+        }
+    }
+
+    /// Whether `secret` is the value this digest was computed from.
+    pub fn matches(&self, secret: &BigUint) -> bool {
+        SecretDigest::compute(secret, self.salt) == *self
+    }
+
+    /// Whether `values` are the ones this digest was computed from with
+    /// [`SecretDigest::compute_all`].
+    pub fn matches_all(&self, values: &[BigUint]) -> bool {
+        SecretDigest::compute_all(values, self.salt) == *self
+    }
+}
+
+impl fmt::Display for SecretDigest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", hex::encode(self.salt), hex::encode(self.hash))
+    }
+}
+
+impl FromStr for SecretDigest {
+    type Err = ShamirError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut bytes = [0u8; 48];
+        hex::decode_to_slice(s, &mut bytes)
+            .map_err(|_| ShamirError::MalformedShare("invalid secret digest"))?;
+        let mut salt = [0u8; 16];
+        let mut hash = [0u8; 32];
+        salt.copy_from_slice(&bytes[..16]);
+        hash.copy_from_slice(&bytes[16..]);
+        Ok(SecretDigest { salt, hash })
+    }
+}
+
+/// A single point on the sharing polynomial together with the parameters
+/// needed to combine it with its siblings.
+///
+/// The textual form produced by `Display` and accepted by `FromStr` is
+/// `x-threshold-fingerprint-y[-blinding][+digest][@set][#mac][~signature]`,
+/// with the fingerprint, `y`, the optional Pedersen blinding value, and the
+/// optional [`SecretDigest`], [`ShareSetId`], [`ShareMac`] and
+/// [`DealerSignature`] in lowercase hex.
+///
+/// The share value is wiped when the share is dropped.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "crate::serde_support::ShareRepr"))]
+pub struct Share {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::share_x"))]
+    x: BigUint,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::biguint_hex"))]
+    y: BigUint,
+    threshold: usize,
+    fingerprint: Fingerprint,
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            with = "crate::serde_support::option_biguint_hex",
+            skip_serializing_if = "Option::is_none"
+        )
+    )]
+    blinding: Option<BigUint>,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    digest: Option<SecretDigest>,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    set_id: Option<ShareSetId>,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    mac: Option<ShareMac>,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    signature: Option<DealerSignature>,
+}
+
+impl Share {
+    /// A share with no optional metadata. Nothing is checked here; `x` is
+    /// validated against the modulus when the share is reconstructed.
+    pub fn new(x: BigUint, y: BigUint, threshold: usize, fingerprint: Fingerprint) -> Self {
+        Share {
+            x,
+            y,
+            threshold,
+            fingerprint,
+            blinding: None,
+            digest: None,
+            set_id: None,
+            mac: None,
+            signature: None,
+        }
+    }
+
+    /// Attaches the value of the Pedersen blinding polynomial at `x`.
+    pub fn with_blinding(mut self, blinding: BigUint) -> Self {
+        self.blinding = Some(blinding);
+        self
+    }
+
+    /// Attaches a digest of the secret for checking reconstructions.
+    pub fn with_digest(mut self, digest: SecretDigest) -> Self {
+        self.digest = Some(digest);
+        self
+    }
+
+    /// Marks the share as part of the share set `set_id`.
+    pub fn with_set_id(mut self, set_id: ShareSetId) -> Self {
+        self.set_id = Some(set_id);
+        self
+    }
+
+    /// Attaches a MAC computed with [`ShareMac::compute`].
+    pub fn with_mac(mut self, mac: ShareMac) -> Self {
+        self.mac = Some(mac);
+        self
+    }
+
+    /// Attaches a dealer signature over the share.
+    pub fn with_signature(mut self, signature: DealerSignature) -> Self {
+        self.signature = Some(signature);
+        self
+    }
+
+    /// Copy of the share without its MAC and dealer signature, the form
+    /// both are computed over.
+    pub(crate) fn untagged(&self) -> Share {
+        let mut share = self.clone();
+        share.mac = None;
+        share.signature = None;
+        share
+    }
+
+    /// The evaluation point, a field element. Splitting and every parser
+    /// only produce nonzero ones, and reconstruction rejects a share whose
+    /// x is zero modulo the field.
+    pub fn x(&self) -> &BigUint {
+        &self.x
+    }
+
+    /// The polynomial value at [`Share::x`].
+    pub fn y(&self) -> &BigUint {
+        &self.y
+    }
+
+    /// Number of shares required to reconstruct the secret.
+    pub fn threshold(&self) -> usize {
+        self.threshold
+    }
+
+    /// Fingerprint of the modulus the share was produced under.
+    pub fn fingerprint(&self) -> Fingerprint {
+        self.fingerprint
+    }
+
+    /// Blinding share for Pedersen verification, if the share has one.
+    pub fn blinding(&self) -> Option<&BigUint> {
+        self.blinding.as_ref()
+    }
+
+    /// Digest of the secret, if one was attached when splitting.
+    pub fn digest(&self) -> Option<&SecretDigest> {
+        self.digest.as_ref()
+    }
+
+    /// Identifier of the split this share belongs to, if it was stamped.
+    pub fn set_id(&self) -> Option<ShareSetId> {
+        self.set_id
+    }
+
+    /// MAC under the share set's verification key, if one was attached.
+    pub fn mac(&self) -> Option<&ShareMac> {
+        self.mac.as_ref()
+    }
+
+    /// The dealer's signature, if the share was signed.
+    pub fn signature(&self) -> Option<&DealerSignature> {
+        self.signature.as_ref()
+    }
+}
+
+/// Leading bytes of the binary share encoding.
+const SHARE_MAGIC: &[u8; 4] = b"PSHR";
+/// Binary share format version with a 64-bit x-coordinate, still written
+/// for every share whose x fits.
+const SHARE_VERSION: u8 = 1;
+/// Binary share format version with a field-width x-coordinate.
+const SHARE_VERSION_WIDE_X: u8 = 2;
+
+const FLAG_BLINDING: u8 = 1;
+const FLAG_DIGEST: u8 = 2;
+const FLAG_SET_ID: u8 = 4;
+const FLAG_MAC: u8 = 8;
+const FLAG_SIGNATURE: u8 = 16;
+
+impl Share {
+    /// Encodes the share in the versioned binary format:
+    ///
+    /// ```text
+    /// "PSHR" | version u8 | flags u8 | x u64 | threshold u32 | fingerprint [8]
+    ///        | width u16 | y [width] | blinding [width]? | digest [48]? | set id [16]?
+    ///        | mac [32]? | signature [72]? | checksum [4]
+    /// ```
+    ///
+    /// Integers are big-endian, `width` is the byte length of `modulus`, so
+    /// every share of a field has the same size, and the checksum is the
+    /// first four bytes of the SHA3-256 digest of everything before it. An x
+    /// too large for the `u64` is written in version 2 of the format instead,
+    /// where the `x u64` field is dropped and x follows `width` as another
+    /// `[width]` value.
+    pub fn to_bytes(&self, modulus: &BigUint) -> Result<Vec<u8>, ShamirError> {
+        if Fingerprint::of(modulus) != self.fingerprint {
+            return Err(ShamirError::ModulusMismatch);
+        }
+        let width = modulus.to_bytes_be().len();
+        let width = u16::try_from(width).map_err(|_| ShamirError::InvalidModulus("too large"))?;
+        let threshold = u32::try_from(self.threshold)
+            .map_err(|_| ShamirError::MalformedShare("threshold does not fit the format"))?;
+        let flags = [
+            (self.blinding.is_some(), FLAG_BLINDING),
+            (self.digest.is_some(), FLAG_DIGEST),
+            (self.set_id.is_some(), FLAG_SET_ID),
+            (self.mac.is_some(), FLAG_MAC),
+            (self.signature.is_some(), FLAG_SIGNATURE),
+        ]
+        .iter()
+        .filter(|(present, _)| *present)
+        .fold(0, |flags, (_, flag)| flags | flag);
+
+        let mut out = Vec::with_capacity(32 + 2 * width as usize + 48 + 16 + 32 + 72);
+        let narrow_x = u64::try_from(&self.x).ok();
+        let version = match narrow_x {
+            Some(_) => SHARE_VERSION,
+            None => SHARE_VERSION_WIDE_X,
+        };
+        out.extend_from_slice(SHARE_MAGIC);
+        out.extend_from_slice(&[version, flags]);
+        if let Some(x) = narrow_x {
+            out.extend_from_slice(&x.to_be_bytes());
+        }
+        out.extend_from_slice(&threshold.to_be_bytes());
+        out.extend_from_slice(self.fingerprint.as_bytes());
+        out.extend_from_slice(&width.to_be_bytes());
+        let wide_x = narrow_x.is_none().then_some(&self.x);
+        for value in wide_x.into_iter().chain([&self.y]).chain(&self.blinding) {
+            let mut bytes = value.to_bytes_be();
+            if bytes.len() > width as usize {
+                bytes.zeroize();
+                out.zeroize();
+                return Err(ShamirError::MalformedShare(
+                    "value is not reduced modulo the field",
+                ));
+            }
+            out.resize(out.len() + width as usize - bytes.len(), 0);
+            out.extend_from_slice(&bytes);
+            bytes.zeroize();
+        }
+        if let Some(digest) = &self.digest {
+            out.extend_from_slice(digest.salt());
+            out.extend_from_slice(digest.hash());
+        }
+        if let Some(set_id) = &self.set_id {
+            out.extend_from_slice(set_id.as_bytes());
+        }
+        if let Some(mac) = &self.mac {
+            out.extend_from_slice(mac.as_bytes());
+        }
+        if let Some(signature) = &self.signature {
+            out.extend_from_slice(&signature.to_bytes());
+        }
+        let checksum = Sha3_256::digest(&out);
+        out.extend_from_slice(&checksum[..4]);
+        Ok(out)
+    }
+
+    /// Decodes a share written by [`Share::to_bytes`].
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ShamirError> {
+        let (body, checksum) = bytes
+            .split_last_chunk::<4>()
+            .ok_or(ShamirError::MalformedShare("truncated share"))?;
+        if Sha3_256::digest(body)[..4] != checksum[..] {
+            return Err(ShamirError::MalformedShare("checksum mismatch"));
+        }
+        let mut rest = body;
+        let mut take = |len: usize| {
+            if rest.len() < len {
+                return Err(ShamirError::MalformedShare("truncated share"));
+            }
+            let (head, tail) = rest.split_at(len);
+            rest = tail;
+            Ok(head)
+        };
+        if take(4)? != SHARE_MAGIC {
+            return Err(ShamirError::MalformedShare("not a binary share"));
+        }
+        let header = take(2)?;
+        let wide_x = match header[0] {
+            SHARE_VERSION => false,
+            SHARE_VERSION_WIDE_X => true,
+            _ => {
+                return Err(ShamirError::MalformedShare(
+                    "unsupported share format version",
+                ))
+            }
+        };
+        let flags = header[1];
+        if flags & !(FLAG_BLINDING | FLAG_DIGEST | FLAG_SET_ID | FLAG_MAC | FLAG_SIGNATURE) != 0 {
+            return Err(ShamirError::MalformedShare("unknown share flags"));
+        }
+        let narrow_x = if wide_x {
+            None
+        } else {
+            Some(u64::from_be_bytes(take(8)?.try_into().expect("8 bytes")))
+        };
+        let threshold = u32::from_be_bytes(take(4)?.try_into().expect("4 bytes")) as usize;
+        let fingerprint = Fingerprint::from_bytes(take(8)?.try_into().expect("8 bytes"));
+        let width = u16::from_be_bytes(take(2)?.try_into().expect("2 bytes")) as usize;
+        let x = match narrow_x {
+            Some(x) => BigUint::from(x),
+            None => BigUint::from_bytes_be(take(width)?),
+        };
+        if x.is_zero() {
+            return Err(ShamirError::MalformedShare("x must be nonzero"));
+        }
+        if threshold < 2 {
+            return Err(ShamirError::MalformedShare("threshold must be at least 2"));
+        }
+
+        let mut share = Share::new(
+            x,
+            BigUint::from_bytes_be(take(width)?),
+            threshold,
+            fingerprint,
+        );
+        if flags & FLAG_BLINDING != 0 {
+            share.blinding = Some(BigUint::from_bytes_be(take(width)?));
+        }
+        if flags & FLAG_DIGEST != 0 {
+            let salt = take(16)?.try_into().expect("16 bytes");
+            let hash = take(32)?.try_into().expect("32 bytes");
+            share.digest = Some(SecretDigest::from_parts(salt, hash));
+        }
+        if flags & FLAG_SET_ID != 0 {
+            share.set_id = Some(ShareSetId::from_bytes(
+                take(16)?.try_into().expect("16 bytes"),
+            ));
+        }
+        if flags & FLAG_MAC != 0 {
+            share.mac = Some(ShareMac::from_bytes(
+                take(32)?.try_into().expect("32 bytes"),
+            ));
+        }
+        if flags & FLAG_SIGNATURE != 0 {
+            share.signature = Some(DealerSignature::from_bytes(
+                take(72)?.try_into().expect("72 bytes"),
+            ));
+        }
+        if !rest.is_empty() {
+            return Err(ShamirError::MalformedShare("trailing data"));
+        }
+        Ok(share)
+    }
+}
+
+impl Zeroize for Share {
+    fn zeroize(&mut self) {
+        zeroize_biguint(&mut self.x);
+        zeroize_biguint(&mut self.y);
+        self.threshold.zeroize();
+        if let Some(blinding) = &mut self.blinding {
+            zeroize_biguint(blinding);
+        }
+    }
+}
+
+impl Drop for Share {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl ZeroizeOnDrop for Share {}
+
+impl fmt::Display for Share {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}-{}-{}-{:x}",
+            self.x, self.threshold, self.fingerprint, self.y
+        )?;
+        if let Some(blinding) = &self.blinding {
+            write!(f, "-{:x}", blinding)?;
+        }
+        if let Some(digest) = &self.digest {
+            write!(f, "+{}", digest)?;
+        }
+        if let Some(set_id) = &self.set_id {
+            write!(f, "@{}", set_id)?;
+        }
+        if let Some(mac) = &self.mac {
+            write!(f, "#{}", mac)?;
+        }
+        if let Some(signature) = &self.signature {
+            write!(f, "~{}", signature)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Share {
+    type Err = ShamirError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (s, signature) = match s.trim().split_once('~') {
+            Some((s, signature)) => (s, Some(signature.parse::<DealerSignature>()?)),
+            None => (s.trim(), None),
+        };
+        let (s, mac) = match s.split_once('#') {
+            Some((s, mac)) => (s, Some(mac.parse::<ShareMac>()?)),
+            None => (s, None),
+        };
+        let (s, set_id) = match s.split_once('@') {
+            Some((s, set_id)) => (s, Some(set_id.parse::<ShareSetId>()?)),
+            None => (s, None),
+        };
+        let (s, digest) = match s.split_once('+') {
+            Some((s, digest)) => (s, Some(digest.parse::<SecretDigest>()?)),
+            None => (s, None),
+        };
+        let mut parts = s.split('-');
+        let mut next = |what: &'static str| parts.next().ok_or(ShamirError::MalformedShare(what));
+        let x: BigUint = next("missing x")?
+            .parse()
+            .map_err(|_| ShamirError::MalformedShare("invalid x"))?;
+        let threshold = next("missing threshold")?
+            .parse()
+            .map_err(|_| ShamirError::MalformedShare("invalid threshold"))?;
+        let fingerprint = next("missing fingerprint")?.parse()?;
+        let y = BigUint::parse_bytes(next("missing y")?.as_bytes(), 16)
+            .ok_or(ShamirError::MalformedShare("invalid y"))?;
+        let blinding = parts
+            .next()
+            .map(|part| {
+                BigUint::parse_bytes(part.as_bytes(), 16)
+                    .ok_or(ShamirError::MalformedShare("invalid blinding"))
+            })
+            .transpose()?;
+        if parts.next().is_some() {
+            return Err(ShamirError::MalformedShare("trailing data"));
+        }
+        if x.is_zero() {
+            return Err(ShamirError::MalformedShare("x must be nonzero"));
+        }
+        if threshold < 2 {
+            return Err(ShamirError::MalformedShare("threshold must be at least 2"));
+        }
+        let mut share = Share::new(x, y, threshold, fingerprint);
+        share.blinding = blinding;
+        share.digest = digest;
+        share.set_id = set_id;
+        share.mac = mac;
+        share.signature = signature;
+        Ok(share)
+    }
+}
@@ -0,0 +1,84 @@
+//! Dealer signatures over issued shares.
+//!
+//! The dealer signs the DER encoding of each share, without its MAC or
+//! signature, under a domain-separation prefix with an Ed25519 key, and the
+//! share carries the signature together with an eight-byte ID of the
+//! dealer's public key. A shareholder who knows the ceremony's public key
+//! can then check that their share really came from that dealer. Keys are
+//! passed as the raw 32-byte seed and public key accepted by
+//! `ed25519_dalek::SigningKey::from_bytes` and `VerifyingKey::from_bytes`.
+
+use alloc::vec::Vec;
+
+use ed25519_dalek::{Signature, Signer, SigningKey, VerifyingKey};
+use sha3::{Digest, Sha3_256};
+use zeroize::Zeroize;
+
+use crate::{DealerSignature, ShamirError, Share};
+
+const DOMAIN: &[u8] = b"prime-shamir/dealer-signature/v1";
+
+/// Public key belonging to the dealer's 32-byte signing seed.
+pub fn dealer_public_key(seed: &[u8; 32]) -> [u8; 32] {
+    SigningKey::from_bytes(seed).verifying_key().to_bytes()
+}
+
+/// The key ID embedded in dealer signatures: the first eight bytes of the
+/// SHA3-256 digest of the public key.
+pub fn dealer_key_id(public_key: &[u8; 32]) -> [u8; 8] {
+    let digest = Sha3_256::digest(public_key);
+    let mut id = [0u8; 8];
+    id.copy_from_slice(&digest[..8]);
+    id
+}
+
+impl Share {
+    fn signed_message(&self) -> Vec<u8> {
+        let mut der = self.untagged().to_der();
+        let mut message = Vec::with_capacity(DOMAIN.len() + der.len());
+        message.extend_from_slice(DOMAIN);
+        message.extend_from_slice(&der);
+        der.zeroize();
+        message
+    }
+
+    /// Signs the share with the dealer's Ed25519 seed, replacing any
+    /// earlier signature.
+    pub fn sign(self, seed: &[u8; 32]) -> Self {
+        let key = SigningKey::from_bytes(seed);
+        let mut message = self.signed_message();
+        let signature = key.sign(&message);
+        message.zeroize();
+        let key_id = dealer_key_id(&key.verifying_key().to_bytes());
+        self.with_signature(DealerSignature::from_parts(key_id, signature.to_bytes()))
+    }
+
+    /// Checks that the share was signed by the holder of `public_key`.
+    #[tracing::instrument(level = "debug", skip_all, fields(x = %self.x()))]
+    pub fn verify_signature(&self, public_key: &[u8; 32]) -> Result<(), ShamirError> {
+        let signature = self
+            .signature()
+            .ok_or(ShamirError::MissingSignature(self.x().clone()))?;
+        if *signature.key_id() != dealer_key_id(public_key) {
+            return Err(ShamirError::UnknownDealer(self.x().clone()));
+        }
+        let key = VerifyingKey::from_bytes(public_key)
+            .map_err(|_| ShamirError::InvalidKey("invalid Ed25519 public key"))?;
+        let mut message = self.signed_message();
+        let result = key.verify_strict(&message, &Signature::from_bytes(signature.signature()));
+        message.zeroize();
+        match result {
+            Ok(()) => {
+                tracing::debug!(
+                    "dealer signature verified for the share at x = {}",
+                    self.x()
+                );
+                Ok(())
+            }
+            Err(_) => {
+                tracing::warn!("invalid dealer signature on the share at x = {}", self.x());
+                Err(ShamirError::SignatureMismatch(self.x().clone()))
+            }
+        }
+    }
+}
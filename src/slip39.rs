@@ -0,0 +1,635 @@
+//! SLIP-0039 Shamir mnemonics.
+//!
+//! Interoperates with Trezor and other wallets: the master secret is
+//! encrypted with a four-round PBKDF2-SHA256 Feistel cipher under an
+//! optional passphrase, split into groups over GF(2^8) and each group
+//! secret split again among its members. Every member share is written as a
+//! mnemonic of words from the SLIP-0039 wordlist protected by an RS1024
+//! checksum. Secrets split at thresholds above one carry a digest share, so
+//! combining mismatched shares fails with [`ShamirError::IntegrityFailure`].
+
+mod wordlist;
+
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+use core::str::FromStr;
+
+use hmac::Hmac;
+use rand::{CryptoRng, RngCore};
+use sha2::Sha256;
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+use crate::grouped::{combine_groups, split_groups, GroupMember};
+use crate::ShamirError;
+use wordlist::WORDS;
+
+const MIN_SECRET_LEN: usize = 16;
+const MIN_WORDS: usize = 20;
+const BASE_ITERATIONS: u32 = 10_000;
+const ROUNDS: u8 = 4;
+
+/// One member share of a SLIP-0039 split.
+///
+/// `Display` writes the mnemonic and `FromStr` parses one, verifying its
+/// checksum. The share value is wiped when the share is dropped.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Slip39Share {
+    identifier: u16,
+    extendable: bool,
+    iteration_exponent: u8,
+    group_index: u8,
+    group_threshold: u8,
+    group_count: u8,
+    member_index: u8,
+    member_threshold: u8,
+    value: Vec<u8>,
+}
+
+impl Slip39Share {
+    /// Random 15-bit identifier shared by all shares of one split.
+    pub fn identifier(&self) -> u16 {
+        self.identifier
+    }
+
+    pub fn extendable(&self) -> bool {
+        self.extendable
+    }
+
+    pub fn iteration_exponent(&self) -> u8 {
+        self.iteration_exponent
+    }
+
+    pub fn group_index(&self) -> u8 {
+        self.group_index
+    }
+
+    pub fn group_threshold(&self) -> u8 {
+        self.group_threshold
+    }
+
+    pub fn group_count(&self) -> u8 {
+        self.group_count
+    }
+
+    pub fn member_index(&self) -> u8 {
+        self.member_index
+    }
+
+    pub fn member_threshold(&self) -> u8 {
+        self.member_threshold
+    }
+
+    fn customization(&self) -> &'static [u8] {
+        if self.extendable {
+            b"shamir_extendable"
+        } else {
+            b"shamir"
+        }
+    }
+
+    /// Whether `other` comes from the same split, so the two may combine.
+    fn same_set(&self, other: &Slip39Share) -> bool {
+        self.identifier == other.identifier
+            && self.extendable == other.extendable
+            && self.iteration_exponent == other.iteration_exponent
+            && self.group_threshold == other.group_threshold
+            && self.group_count == other.group_count
+            && self.value.len() == other.value.len()
+    }
+
+    fn words(&self) -> Vec<u16> {
+        let header = (self.identifier as u64) << 25
+            | (self.extendable as u64) << 24
+            | (self.iteration_exponent as u64) << 20
+            | (self.group_index as u64) << 16
+            | ((self.group_threshold - 1) as u64) << 12
+            | ((self.group_count - 1) as u64) << 8
+            | (self.member_index as u64) << 4
+            | (self.member_threshold - 1) as u64;
+        let mut words: Vec<u16> = (0..4)
+            .rev()
+            .map(|i| ((header >> (10 * i)) & 0x3ff) as u16)
+            .collect();
+
+        let bits = 8 * self.value.len();
+        let mut acc = 0u32;
+        let mut pending = (10 - bits % 10) % 10;
+        for &byte in &self.value {
+            acc = (acc << 8) | byte as u32;
+            pending += 8;
+            while pending >= 10 {
+                pending -= 10;
+                words.push(((acc >> pending) & 0x3ff) as u16);
+            }
+        }
+        acc.zeroize();
+
+        words.extend(rs1024_checksum(self.customization(), &words));
+        words
+    }
+}
+
+impl Zeroize for Slip39Share {
+    fn zeroize(&mut self) {
+        self.value.zeroize();
+    }
+}
+
+impl Drop for Slip39Share {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl ZeroizeOnDrop for Slip39Share {}
+
+impl fmt::Display for Slip39Share {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut words = self.words();
+        let result = words.iter().enumerate().try_for_each(|(i, &word)| {
+            if i > 0 {
+                f.write_str(" ")?;
+            }
+            f.write_str(WORDS[word as usize])
+        });
+        words.zeroize();
+        result
+    }
+}
+
+impl FromStr for Slip39Share {
+    type Err = ShamirError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let malformed = ShamirError::MalformedShare;
+        let mut words = s
+            .split_whitespace()
+            .map(|word| {
+                WORDS
+                    .binary_search(&word.to_ascii_lowercase().as_str())
+                    .map(|index| index as u16)
+                    .map_err(|_| malformed("word is not in the SLIP-0039 wordlist"))
+            })
+            .collect::<Result<Vec<u16>, _>>()?;
+        let result = parse_words(&words);
+        words.zeroize();
+        result
+    }
+}
+
+fn parse_words(words: &[u16]) -> Result<Slip39Share, ShamirError> {
+    let malformed = ShamirError::MalformedShare;
+    if words.len() < MIN_WORDS {
+        return Err(malformed("mnemonic is too short"));
+    }
+    let header = words[..4]
+        .iter()
+        .fold(0u64, |acc, &word| (acc << 10) | word as u64);
+    let extendable = (header >> 24) & 1 == 1;
+    let customization: &[u8] = if extendable {
+        b"shamir_extendable"
+    } else {
+        b"shamir"
+    };
+    if rs1024_polymod(customization, words) != 1 {
+        return Err(malformed("invalid mnemonic checksum"));
+    }
+
+    let value_words = &words[4..words.len() - 3];
+    let padding = 10 * value_words.len() % 16;
+    if padding > 8 {
+        return Err(malformed("invalid mnemonic length"));
+    }
+    let mut value = Vec::with_capacity(10 * value_words.len() / 8);
+    let mut acc = 0u32;
+    let mut pending = 0usize;
+    let mut skip = padding;
+    for &word in value_words {
+        acc = (acc << 10) | word as u32;
+        pending += 10;
+        if skip > 0 {
+            if (acc >> (pending - skip)) & ((1 << skip) - 1) != 0 {
+                value.zeroize();
+                return Err(malformed("invalid mnemonic padding"));
+            }
+            pending -= skip;
+            skip = 0;
+        }
+        while pending >= 8 {
+            pending -= 8;
+            value.push((acc >> pending) as u8);
+        }
+        acc &= (1 << pending) - 1;
+    }
+    acc.zeroize();
+    if value.len() < MIN_SECRET_LEN || !value.len().is_multiple_of(2) {
+        value.zeroize();
+        return Err(malformed("invalid master secret length"));
+    }
+
+    let field = |shift: u32| ((header >> shift) & 0xf) as u8;
+    let share = Slip39Share {
+        identifier: (header >> 25) as u16,
+        extendable,
+        iteration_exponent: field(20),
+        group_index: field(16),
+        group_threshold: field(12) + 1,
+        group_count: field(8) + 1,
+        member_index: field(4),
+        member_threshold: field(0) + 1,
+        value,
+    };
+    if share.group_threshold > share.group_count {
+        return Err(malformed("group threshold exceeds the group count"));
+    }
+    Ok(share)
+}
+
+fn rs1024_polymod(customization: &[u8], words: &[u16]) -> u32 {
+    const GENERATOR: [u32; 10] = [
+        0x00e0_e040,
+        0x01c1_c080,
+        0x0383_8100,
+        0x0707_0200,
+        0x0e0e_0009,
+        0x1c0c_2412,
+        0x3808_6c24,
+        0x3090_fc48,
+        0x21b1_f890,
+        0x03f3_f120,
+    ];
+    customization
+        .iter()
+        .map(|&byte| byte as u16)
+        .chain(words.iter().copied())
+        .fold(1u32, |checksum, value| {
+            let top = checksum >> 20;
+            let checksum = ((checksum & 0x000f_ffff) << 10) ^ value as u32;
+            GENERATOR
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| (top >> i) & 1 == 1)
+                .fold(checksum, |checksum, (_, g)| checksum ^ g)
+        })
+}
+
+fn rs1024_checksum(customization: &[u8], words: &[u16]) -> [u16; 3] {
+    let mut padded = words.to_vec();
+    padded.extend_from_slice(&[0; 3]);
+    let residue = rs1024_polymod(customization, &padded) ^ 1;
+    padded.zeroize();
+    [2, 1, 0].map(|i| ((residue >> (10 * i)) & 0x3ff) as u16)
+}
+
+fn check_passphrase(passphrase: &[u8]) -> Result<(), ShamirError> {
+    if passphrase.iter().all(|&byte| (32..=126).contains(&byte)) {
+        Ok(())
+    } else {
+        Err(ShamirError::InvalidKey(
+            "passphrase must be printable ASCII",
+        ))
+    }
+}
+
+/// Runs the Feistel network forwards (`encrypt`) or backwards.
+fn feistel(
+    input: &[u8],
+    passphrase: &[u8],
+    iteration_exponent: u8,
+    identifier: u16,
+    extendable: bool,
+    encrypt: bool,
+) -> Vec<u8> {
+    let half = input.len() / 2;
+    let mut left = input[..half].to_vec();
+    let mut right = input[half..].to_vec();
+    let mut salt = Vec::new();
+    if !extendable {
+        salt.extend_from_slice(b"shamir");
+        salt.extend_from_slice(&identifier.to_be_bytes());
+    }
+    let iterations = (BASE_ITERATIONS << iteration_exponent) / ROUNDS as u32;
+    let mut password = Vec::with_capacity(passphrase.len() + 1);
+    let mut round_salt = Vec::with_capacity(salt.len() + half);
+    let mut round_key = vec![0u8; half];
+    for step in 0..ROUNDS {
+        let round = if encrypt { step } else { ROUNDS - 1 - step };
+        password.clear();
+        password.push(round);
+        password.extend_from_slice(passphrase);
+        round_salt.clear();
+        round_salt.extend_from_slice(&salt);
+        round_salt.extend_from_slice(&right);
+        pbkdf2::pbkdf2::<Hmac<Sha256>>(&password, &round_salt, iterations, &mut round_key);
+        for (l, k) in left.iter_mut().zip(&round_key) {
+            *l ^= k;
+        }
+        core::mem::swap(&mut left, &mut right);
+    }
+    password.zeroize();
+    round_salt.zeroize();
+    round_key.zeroize();
+    let mut output = right;
+    output.extend_from_slice(&left);
+    left.zeroize();
+    output
+}
+
+/// A SLIP-0039 split configuration: a group threshold and, per group, a
+/// member threshold and member count.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Slip39Scheme {
+    group_threshold: u8,
+    groups: Vec<(u8, u8)>,
+    extendable: bool,
+    iteration_exponent: u8,
+}
+
+impl Slip39Scheme {
+    /// `groups` lists `(member_threshold, member_count)` pairs.
+    ///
+    /// Up to 16 groups of up to 16 members are allowed, and a member
+    /// threshold of 1 requires a single member, as SLIP-0039 mandates.
+    pub fn new(group_threshold: usize, groups: &[(usize, usize)]) -> Result<Self, ShamirError> {
+        let invalid = ShamirError::InvalidAccessStructure;
+        if groups.is_empty() || groups.len() > 16 {
+            return Err(invalid("SLIP-0039 needs between 1 and 16 groups"));
+        }
+        if group_threshold == 0 || group_threshold > groups.len() {
+            return Err(ShamirError::InvalidThreshold {
+                threshold: group_threshold,
+                shares: groups.len(),
+            });
+        }
+        let mut checked = Vec::with_capacity(groups.len());
+        for &(threshold, count) in groups {
+            if threshold == 0 || threshold > count || count > 16 {
+                return Err(ShamirError::InvalidThreshold {
+                    threshold,
+                    shares: count,
+                });
+            }
+            if threshold == 1 && count > 1 {
+                return Err(invalid("a 1-of-n group must have a single member"));
+            }
+            checked.push((threshold as u8, count as u8));
+        }
+        Ok(Slip39Scheme {
+            group_threshold: group_threshold as u8,
+            groups: checked,
+            extendable: true,
+            iteration_exponent: 1,
+        })
+    }
+
+    /// A single group of `count` members, any `threshold` of which recover
+    /// the secret.
+    pub fn single(threshold: usize, count: usize) -> Result<Self, ShamirError> {
+        Slip39Scheme::new(1, &[(threshold, count)])
+    }
+
+    /// Whether the identifier is left out of the encryption salt, so the
+    /// same master secret and passphrase can be re-split into compatible
+    /// sets. Defaults to `true`.
+    pub fn extendable(mut self, extendable: bool) -> Self {
+        self.extendable = extendable;
+        self
+    }
+
+    /// PBKDF2 runs `10000 << exponent` iterations in total; the exponent
+    /// must be below 16 and defaults to 1.
+    pub fn iteration_exponent(mut self, exponent: u8) -> Self {
+        self.iteration_exponent = exponent;
+        self
+    }
+
+    /// Encrypts `master_secret` under `passphrase` and splits it, returning
+    /// one list of member shares per group.
+    ///
+    /// The master secret must be at least 16 bytes and of even length; the
+    /// passphrase may be empty and must be printable ASCII.
+    pub fn split_with_rng<R: RngCore + CryptoRng + ?Sized>(
+        &self,
+        master_secret: &[u8],
+        passphrase: &[u8],
+        rng: &mut R,
+    ) -> Result<Vec<Vec<Slip39Share>>, ShamirError> {
+        if master_secret.len() < MIN_SECRET_LEN || !master_secret.len().is_multiple_of(2) {
+            return Err(ShamirError::InvalidKey(
+                "master secret must be at least 16 bytes and of even length",
+            ));
+        }
+        if self.iteration_exponent > 15 {
+            return Err(ShamirError::InvalidAccessStructure(
+                "iteration exponent must be below 16",
+            ));
+        }
+        check_passphrase(passphrase)?;
+
+        let identifier = (rng.next_u32() & 0x7fff) as u16;
+        let mut encrypted = feistel(
+            master_secret,
+            passphrase,
+            self.iteration_exponent,
+            identifier,
+            self.extendable,
+            true,
+        );
+        let group_count = self.groups.len() as u8;
+        let groups = split_groups(self.group_threshold, &self.groups, &encrypted, rng);
+        encrypted.zeroize();
+
+        let shares = groups
+            .into_iter()
+            .zip(&self.groups)
+            .enumerate()
+            .map(|(group_index, (members, &(threshold, _)))| {
+                members
+                    .into_iter()
+                    .map(|(member_index, value)| Slip39Share {
+                        identifier,
+                        extendable: self.extendable,
+                        iteration_exponent: self.iteration_exponent,
+                        group_index: group_index as u8,
+                        group_threshold: self.group_threshold,
+                        group_count,
+                        member_index,
+                        member_threshold: threshold,
+                        value,
+                    })
+                    .collect()
+            })
+            .collect();
+        Ok(shares)
+    }
+}
+
+/// Recovers the master secret from SLIP-0039 shares and `passphrase`.
+///
+/// Any group with fewer than its member threshold of shares is ignored; a
+/// group threshold of complete groups is required. A wrong passphrase
+/// yields a different secret rather than an error, as SLIP-0039 intends.
+pub fn slip39_combine(shares: &[Slip39Share], passphrase: &[u8]) -> Result<Vec<u8>, ShamirError> {
+    let first = shares.first().ok_or(ShamirError::NotEnoughShares {
+        required: 1,
+        provided: 0,
+    })?;
+    check_passphrase(passphrase)?;
+    if shares.iter().any(|share| !first.same_set(share)) {
+        return Err(ShamirError::SetMismatch);
+    }
+
+    let members: Vec<GroupMember<'_>> = shares
+        .iter()
+        .map(|share| GroupMember {
+            group_index: share.group_index,
+            member_index: share.member_index,
+            member_threshold: share.member_threshold,
+            value: &share.value,
+        })
+        .collect();
+    let mut encrypted = combine_groups(first.group_threshold, &members)?;
+    let secret = feistel(
+        &encrypted,
+        passphrase,
+        first.iteration_exponent,
+        first.identifier,
+        first.extendable,
+        false,
+    );
+    encrypted.zeroize();
+    Ok(secret)
+}
+
+/// Parses `mnemonics` and combines them with [`slip39_combine`].
+pub fn slip39_combine_mnemonics(
+    mnemonics: &[&str],
+    passphrase: &[u8],
+) -> Result<Vec<u8>, ShamirError> {
+    let shares = mnemonics
+        .iter()
+        .map(|mnemonic| mnemonic.parse())
+        .collect::<Result<Vec<Slip39Share>, _>>()?;
+    slip39_combine(&shares, passphrase)
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha20Rng;
+
+    use super::*;
+
+    const PASSPHRASE: &[u8] = b"TREZOR";
+
+    /// Valid cases from the SLIP-0039 reference test vectors.
+    #[test]
+    fn reference_vectors() {
+        let vectors: [(&[&str], &str); 5] = [
+            (
+                &[
+                    "duckling enlarge academic academic agency result length solution fridge \
+                   kidney coal piece deal husband erode duke ajar critical decision keyboard",
+                ],
+                "bb54aac4b89dc868ba37d9cc21b2cece",
+            ),
+            (
+                &[
+                    "shadow pistol academic always adequate wildlife fancy gross oasis cylinder \
+                     mustang wrist rescue view short owner flip making coding armed",
+                    "shadow pistol academic acid actress prayer class unknown daughter sweater \
+                     depict flip twice unkind craft early superior advocate guest smoking",
+                ],
+                "b43ceb7e57a0ea8766221624d01b0864",
+            ),
+            (
+                &[
+                    "eraser senior decision smug corner ruin rescue cubic angel tackle skin skunk \
+                     program roster trash rumor slush angel flea amazing",
+                    "eraser senior beard romp adorn nuclear spill corner cradle style ancient \
+                     family general leader ambition exchange unusual garlic promise voice",
+                    "eraser senior decision scared cargo theory device idea deliver modify curly \
+                     include pancake both news skin realize vitamins away join",
+                ],
+                "7c3397a292a5941682d7a4ae2d898d11",
+            ),
+            (
+                &[
+                    "theory painting academic academic armed sweater year military elder discuss \
+                   acne wildlife boring employer fused large satoshi bundle carbon diagnose \
+                   anatomy hamster leaves tracks paces beyond phantom capital marvel lips brave \
+                   detect luck",
+                ],
+                "989baf9dcaad5b10ca33dfd8cc75e42477025dce88ae83e75a230086a0e00e92",
+            ),
+            (
+                &[
+                    "humidity disease academic always aluminum jewelry energy woman receiver \
+                     strategy amuse duckling lying evidence network walnut tactics forget hairy \
+                     rebound impulse brother survive clothes stadium mailman rival ocean reward \
+                     venture always armed unwrap",
+                    "humidity disease academic agency actress jacket gross physics cylinder \
+                     solution fake mortgage benefit public busy prepare sharp friar change work \
+                     slow purchase ruler again tricycle involve viral wireless mixture anatomy \
+                     desert cargo upgrade",
+                ],
+                "c938b319067687e990e05e0da0ecce1278f75ff58d9853f19dcaeed5de104aae",
+            ),
+        ];
+        for (mnemonics, secret) in vectors {
+            for mnemonic in mnemonics {
+                let share: Slip39Share = mnemonic.parse().unwrap();
+                assert_eq!(share.to_string(), *mnemonic);
+            }
+            let combined = slip39_combine_mnemonics(mnemonics, PASSPHRASE).unwrap();
+            assert_eq!(hex::encode(combined), secret);
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_reference_vectors() {
+        let bad_checksum = "duckling enlarge academic academic agency result length solution \
+                            fridge kidney coal piece deal husband erode duke ajar critical \
+                            decision kidney";
+        assert!(bad_checksum.parse::<Slip39Share>().is_err());
+
+        let too_few = [
+            "shadow pistol academic always adequate wildlife fancy gross oasis \
+                        cylinder mustang wrist rescue view short owner flip making coding armed",
+        ];
+        assert!(slip39_combine_mnemonics(&too_few, PASSPHRASE).is_err());
+
+        let bad_digest = [
+            "guilt walnut academic acid deliver remove equip listen vampire tactics nylon rhythm \
+             failure husband fatigue alive blind enemy teaspoon rebound",
+            "guilt walnut academic agency brave hamster hobo declare herd taste alpha slim \
+             criminal mild arcade formal romp branch pink ambition",
+        ];
+        assert!(matches!(
+            slip39_combine_mnemonics(&bad_digest, PASSPHRASE),
+            Err(ShamirError::IntegrityFailure)
+        ));
+    }
+
+    #[test]
+    fn round_trips_a_two_level_split() {
+        let mut rng = ChaCha20Rng::seed_from_u64(0);
+        let secret: Vec<u8> = (0..32).collect();
+        let groups = Slip39Scheme::new(2, &[(2, 3), (1, 1), (3, 5)])
+            .unwrap()
+            .split_with_rng(&secret, PASSPHRASE, &mut rng)
+            .unwrap();
+        let mnemonics: Vec<String> = [&groups[0][..2], &groups[2][1..4]]
+            .concat()
+            .iter()
+            .map(|share| share.to_string())
+            .collect();
+        let mnemonics: Vec<&str> = mnemonics.iter().map(String::as_str).collect();
+        assert_eq!(
+            slip39_combine_mnemonics(&mnemonics, PASSPHRASE).unwrap(),
+            secret
+        );
+        assert!(slip39_combine_mnemonics(&mnemonics[..4], PASSPHRASE).is_err());
+    }
+}
@@ -0,0 +1,415 @@
+//! Sharded Secret Key Reconstruction (SSKR, BCR-2020-011) and its
+//! `ur:sskr` Uniform Resource encoding.
+//!
+//! SSKR splits a 16- to 32-byte secret with the same two-level GF(2^8)
+//! scheme as SLIP-0039 but without encryption, and serializes each member
+//! share as five metadata bytes followed by the share value. For exchange
+//! with SSKR-aware wallets a share is wrapped in a CBOR byte string and
+//! written as a single-part UR: `ur:sskr/` followed by the minimal
+//! Bytewords of the CBOR and its CRC-32. Multi-part (fountain-coded) URs
+//! are not supported; SSKR shares are short enough for a single QR code.
+
+mod bytewords;
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+use core::str::FromStr;
+
+use rand::{CryptoRng, RngCore};
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+use crate::cbor::{bytes_from_cbor, bytes_to_cbor};
+use crate::crc32::crc32;
+use crate::grouped::{combine_groups, split_groups, GroupMember};
+use crate::ShamirError;
+use bytewords::WORDS;
+
+const METADATA_LEN: usize = 5;
+const MIN_SECRET_LEN: usize = 16;
+const MAX_SECRET_LEN: usize = 32;
+const UR_TYPE: &str = "sskr";
+const LEGACY_UR_TYPE: &str = "crypto-sskr";
+const CBOR_TAGS: [u64; 2] = [40309, 309];
+
+/// One member share of an SSKR split.
+///
+/// `Display` writes the `ur:sskr` form and `FromStr` parses it. The share
+/// value is wiped when the share is dropped.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SskrShare {
+    identifier: u16,
+    group_index: u8,
+    group_threshold: u8,
+    group_count: u8,
+    member_index: u8,
+    member_threshold: u8,
+    value: Vec<u8>,
+}
+
+impl SskrShare {
+    /// Random 16-bit identifier shared by all shares of one split.
+    pub fn identifier(&self) -> u16 {
+        self.identifier
+    }
+
+    pub fn group_index(&self) -> u8 {
+        self.group_index
+    }
+
+    pub fn group_threshold(&self) -> u8 {
+        self.group_threshold
+    }
+
+    pub fn group_count(&self) -> u8 {
+        self.group_count
+    }
+
+    pub fn member_index(&self) -> u8 {
+        self.member_index
+    }
+
+    pub fn member_threshold(&self) -> u8 {
+        self.member_threshold
+    }
+
+    /// The BCR-2020-011 serialization: identifier, packed thresholds and
+    /// indices, then the share value.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(METADATA_LEN + self.value.len());
+        out.extend_from_slice(&self.identifier.to_be_bytes());
+        out.push((self.group_threshold - 1) << 4 | (self.group_count - 1));
+        out.push(self.group_index << 4 | (self.member_threshold - 1));
+        out.push(self.member_index);
+        out.extend_from_slice(&self.value);
+        out
+    }
+
+    /// Parses a share written by [`SskrShare::to_bytes`].
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ShamirError> {
+        let malformed = ShamirError::MalformedShare;
+        let (metadata, value) = bytes
+            .split_at_checked(METADATA_LEN)
+            .ok_or(malformed("truncated SSKR share"))?;
+        if !(MIN_SECRET_LEN..=MAX_SECRET_LEN).contains(&value.len())
+            || !value.len().is_multiple_of(2)
+        {
+            return Err(malformed("invalid SSKR share length"));
+        }
+        if metadata[4] >> 4 != 0 {
+            return Err(malformed("reserved SSKR bits are set"));
+        }
+        let share = SskrShare {
+            identifier: u16::from_be_bytes([metadata[0], metadata[1]]),
+            group_threshold: (metadata[2] >> 4) + 1,
+            group_count: (metadata[2] & 0xf) + 1,
+            group_index: metadata[3] >> 4,
+            member_threshold: (metadata[3] & 0xf) + 1,
+            member_index: metadata[4] & 0xf,
+            value: value.to_vec(),
+        };
+        if share.group_threshold > share.group_count {
+            return Err(malformed("group threshold exceeds the group count"));
+        }
+        Ok(share)
+    }
+
+    /// The share as a single-part `ur:sskr` Uniform Resource.
+    pub fn to_ur(&self) -> String {
+        let mut bytes = self.to_bytes();
+        let mut cbor = bytes_to_cbor(&bytes);
+        bytes.zeroize();
+        cbor.extend_from_slice(&crc32(&cbor).to_be_bytes());
+        let mut ur = String::with_capacity(UR_TYPE.len() + 4 + 2 * cbor.len());
+        ur.push_str("ur:");
+        ur.push_str(UR_TYPE);
+        ur.push('/');
+        for &byte in &cbor {
+            let word = WORDS[byte as usize].as_bytes();
+            ur.push(word[0] as char);
+            ur.push(word[3] as char);
+        }
+        cbor.zeroize();
+        ur
+    }
+
+    /// Parses a `ur:sskr` (or legacy `ur:crypto-sskr`) Uniform Resource,
+    /// in either case.
+    pub fn from_ur(ur: &str) -> Result<Self, ShamirError> {
+        let malformed = ShamirError::MalformedShare;
+        let ur = ur.trim().to_ascii_lowercase();
+        let (ur_type, body) = ur
+            .strip_prefix("ur:")
+            .and_then(|rest| rest.split_once('/'))
+            .ok_or(malformed("not a UR"))?;
+        if ur_type != UR_TYPE && ur_type != LEGACY_UR_TYPE {
+            return Err(malformed("not an SSKR UR"));
+        }
+        if body.contains('/') {
+            return Err(malformed("multi-part URs are not supported"));
+        }
+        if !body.len().is_multiple_of(2) {
+            return Err(malformed("invalid Bytewords length"));
+        }
+        let mut data = body
+            .as_bytes()
+            .chunks(2)
+            .map(|pair| {
+                WORDS
+                    .iter()
+                    .position(|word| {
+                        let word = word.as_bytes();
+                        word[0] == pair[0] && word[3] == pair[1]
+                    })
+                    .map(|index| index as u8)
+                    .ok_or(malformed("invalid Bytewords"))
+            })
+            .collect::<Result<Vec<u8>, _>>()?;
+        let share = match data.split_last_chunk::<4>() {
+            Some((cbor, checksum)) if crc32(cbor).to_be_bytes() == *checksum => {
+                bytes_from_cbor(cbor, &CBOR_TAGS).and_then(SskrShare::from_bytes)
+            }
+            Some(_) => Err(malformed("UR checksum mismatch")),
+            None => Err(malformed("truncated UR")),
+        };
+        data.zeroize();
+        share
+    }
+}
+
+impl Zeroize for SskrShare {
+    fn zeroize(&mut self) {
+        self.value.zeroize();
+    }
+}
+
+impl Drop for SskrShare {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl ZeroizeOnDrop for SskrShare {}
+
+impl fmt::Display for SskrShare {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_ur())
+    }
+}
+
+impl FromStr for SskrShare {
+    type Err = ShamirError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        SskrShare::from_ur(s)
+    }
+}
+
+/// An SSKR split configuration: a group threshold and, per group, a member
+/// threshold and member count.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SskrScheme {
+    group_threshold: u8,
+    groups: Vec<(u8, u8)>,
+}
+
+impl SskrScheme {
+    /// `groups` lists `(member_threshold, member_count)` pairs; up to 16
+    /// groups of up to 16 members are allowed.
+    pub fn new(group_threshold: usize, groups: &[(usize, usize)]) -> Result<Self, ShamirError> {
+        if groups.is_empty() || groups.len() > 16 {
+            return Err(ShamirError::InvalidAccessStructure(
+                "SSKR needs between 1 and 16 groups",
+            ));
+        }
+        if group_threshold == 0 || group_threshold > groups.len() {
+            return Err(ShamirError::InvalidThreshold {
+                threshold: group_threshold,
+                shares: groups.len(),
+            });
+        }
+        let mut checked = Vec::with_capacity(groups.len());
+        for &(threshold, count) in groups {
+            if threshold == 0 || threshold > count || count > 16 {
+                return Err(ShamirError::InvalidThreshold {
+                    threshold,
+                    shares: count,
+                });
+            }
+            checked.push((threshold as u8, count as u8));
+        }
+        Ok(SskrScheme {
+            group_threshold: group_threshold as u8,
+            groups: checked,
+        })
+    }
+
+    /// A single group of `count` members, any `threshold` of which recover
+    /// the secret.
+    pub fn single(threshold: usize, count: usize) -> Result<Self, ShamirError> {
+        SskrScheme::new(1, &[(threshold, count)])
+    }
+
+    /// Splits `secret`, which must be 16 to 32 bytes of even length,
+    /// returning one list of member shares per group.
+    pub fn split_with_rng<R: RngCore + CryptoRng + ?Sized>(
+        &self,
+        secret: &[u8],
+        rng: &mut R,
+    ) -> Result<Vec<Vec<SskrShare>>, ShamirError> {
+        if !(MIN_SECRET_LEN..=MAX_SECRET_LEN).contains(&secret.len())
+            || !secret.len().is_multiple_of(2)
+        {
+            return Err(ShamirError::InvalidKey(
+                "SSKR secrets must be 16 to 32 bytes of even length",
+            ));
+        }
+        let mut identifier = [0u8; 2];
+        rng.fill_bytes(&mut identifier);
+        let identifier = u16::from_be_bytes(identifier);
+        let group_count = self.groups.len() as u8;
+        Ok(
+            split_groups(self.group_threshold, &self.groups, secret, rng)
+                .into_iter()
+                .zip(&self.groups)
+                .enumerate()
+                .map(|(group_index, (members, &(threshold, _)))| {
+                    members
+                        .into_iter()
+                        .map(|(member_index, value)| SskrShare {
+                            identifier,
+                            group_index: group_index as u8,
+                            group_threshold: self.group_threshold,
+                            group_count,
+                            member_index,
+                            member_threshold: threshold,
+                            value,
+                        })
+                        .collect()
+                })
+                .collect(),
+        )
+    }
+}
+
+/// Recovers the secret from SSKR shares.
+///
+/// Any group with fewer than its member threshold of shares is ignored; a
+/// group threshold of complete groups is required.
+pub fn sskr_combine(shares: &[SskrShare]) -> Result<Vec<u8>, ShamirError> {
+    let first = shares.first().ok_or(ShamirError::NotEnoughShares {
+        required: 1,
+        provided: 0,
+    })?;
+    let same_set = |share: &SskrShare| {
+        share.identifier == first.identifier
+            && share.group_threshold == first.group_threshold
+            && share.group_count == first.group_count
+            && share.value.len() == first.value.len()
+    };
+    if !shares.iter().all(same_set) {
+        return Err(ShamirError::SetMismatch);
+    }
+    let members: Vec<GroupMember<'_>> = shares
+        .iter()
+        .map(|share| GroupMember {
+            group_index: share.group_index,
+            member_index: share.member_index,
+            member_threshold: share.member_threshold,
+            value: &share.value,
+        })
+        .collect();
+    combine_groups(first.group_threshold, &members)
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha20Rng;
+
+    use super::*;
+
+    /// A 2-of-3 split of `0ff784df000c4380a5ed683f7e6e3dcf` made by the
+    /// Blockchain Commons `sskr` crate with its fake RNG, and the URs of
+    /// the shares as the `ur` crate's minimal Bytewords write them.
+    const REFERENCE: [(&str, &str); 3] = [
+        (
+            "7eb50001003173ad9f90194ae06b94e25e89a1f2cc",
+            "ur:sskr/gokbreaeadaeehjkpmnemhcfgevtjemwvohyldoywzsfbekktaid",
+        ),
+        (
+            "7eb5000101ef81fcdec9aeb60c005fb8ae68d685e8",
+            "ur:sskr/gokbreaeadadwslyztuesoplrpbnaeheroplistblpvsutbyjpbb",
+        ),
+        (
+            "7eb5000102968c0f1d226ca923bd1956a5504f1c84",
+            "ur:sskr/gokbreaeadaomtlkbscacpjzptcnrycfhfongdgwcelrnsluihtd",
+        ),
+    ];
+
+    #[test]
+    fn decodes_reference_shares() {
+        let shares: Vec<SskrShare> = REFERENCE
+            .iter()
+            .map(|(bytes, ur)| {
+                let share = SskrShare::from_bytes(&hex::decode(bytes).unwrap()).unwrap();
+                assert_eq!(share.to_ur(), *ur);
+                assert_eq!(ur.to_ascii_uppercase().parse::<SskrShare>().unwrap(), share);
+                share
+            })
+            .collect();
+        for pair in [[0, 1], [0, 2], [1, 2]] {
+            let chosen = [shares[pair[0]].clone(), shares[pair[1]].clone()];
+            let secret = sskr_combine(&chosen).unwrap();
+            assert_eq!(hex::encode(secret), "0ff784df000c4380a5ed683f7e6e3dcf");
+        }
+        assert!(sskr_combine(&shares[..1]).is_err());
+
+        // The same first share with the CBOR tag that URs leave out.
+        let tagged = "ur:sskr/tantkpgokbreaeadaeehjkpmnemhcfgevtjemwvohyldoywzsfaxlykioe";
+        assert_eq!(SskrShare::from_ur(tagged).unwrap(), shares[0]);
+    }
+
+    /// The minimal Bytewords example from BCR-2020-012, whose last four
+    /// words are the CRC-32 of the payload.
+    #[test]
+    fn minimal_bytewords_reference() {
+        let mut payload = vec![0, 1, 2, 128, 255];
+        payload.extend_from_slice(&crc32(&payload).to_be_bytes());
+        let encoded: String = payload
+            .iter()
+            .flat_map(|&byte| {
+                let word = WORDS[byte as usize].as_bytes();
+                [word[0] as char, word[3] as char]
+            })
+            .collect();
+        assert_eq!(encoded, "aeadaolazmjendeoti");
+        assert_eq!(crc32(b"Hello, world!"), 0xebe6_c6e6);
+    }
+
+    #[test]
+    fn rejects_a_changed_ur() {
+        let (_, ur) = REFERENCE[0];
+        let mut changed = String::from(ur);
+        let last = changed.pop().unwrap();
+        changed.push(if last == 'e' { 'd' } else { 'e' });
+        assert!(SskrShare::from_ur(&changed).is_err());
+    }
+
+    #[test]
+    fn round_trips_through_urs() {
+        let mut rng = ChaCha20Rng::seed_from_u64(0);
+        let secret: Vec<u8> = (0..32).collect();
+        let groups = SskrScheme::new(2, &[(2, 3), (3, 5)])
+            .unwrap()
+            .split_with_rng(&secret, &mut rng)
+            .unwrap();
+        let shares: Vec<SskrShare> = [&groups[0][1..], &groups[1][..3]]
+            .concat()
+            .iter()
+            .map(|share| SskrShare::from_ur(&share.to_ur()).unwrap())
+            .collect();
+        assert_eq!(sskr_combine(&shares).unwrap(), secret);
+        assert!(sskr_combine(&shares[..4]).is_err());
+    }
+}
@@ -0,0 +1,526 @@
+//! Shares in the format of B. Poettering's `ssss-split` / `ssss-combine`.
+//!
+//! `ssss` shares a secret of up to 1024 bits as a single element of
+//! GF(2^m), where the security level `m` is a multiple of 8 and defaults to
+//! the secret's length in bits. The field is defined by the pentanomial
+//! `x^m + x^a + x^b + x^c + 1` whose exponents `(a, b, c)` are the
+//! lexicographically smallest that give an irreducible polynomial; this is
+//! the table `ssss` ships. Share `i` is the polynomial evaluated at the
+//! field element with bit pattern `i`, written as `i-hex` with exactly
+//! `m / 4` lowercase hex digits, the index zero-padded to the width of the
+//! share count, and optionally prefixed by a `token-`.
+//!
+//! For security levels of 64 bits and up `ssss` also scrambles the secret
+//! with an unkeyed diffusion layer before splitting: 40 passes of XTEA with
+//! an all-zero key over overlapping 8-byte windows, so that a share set
+//! just short of the threshold leaks nothing even about a structured
+//! secret. It is on by default in both tools and has to match on both
+//! sides.
+
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+use core::str::FromStr;
+
+use num_bigint::BigUint;
+use rand::{CryptoRng, RngCore};
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+use crate::ShamirError;
+
+const MAX_DEGREE: usize = 1024;
+const MAX_TOKEN_LEN: usize = 128;
+const DIFFUSION_MIN_DEGREE: usize = 64;
+
+/// Exponents `(a, b, c)` of the irreducible `x^m + x^a + x^b + x^c + 1` for
+/// `m = 8, 16, ..., 1024`.
+#[rustfmt::skip]
+const IRREDUCIBLE: [[u8; 3]; MAX_DEGREE / 8] = [
+    [4, 3, 1], [5, 3, 1], [4, 3, 1], [7, 3, 2], [5, 4, 3], [5, 3, 2], [7, 4, 2], [4, 3, 1],
+    [10, 9, 3], [9, 4, 2], [7, 6, 2], [10, 9, 6], [4, 3, 1], [5, 4, 3], [4, 3, 1], [7, 2, 1],
+    [5, 3, 2], [7, 4, 2], [6, 3, 2], [5, 3, 2], [15, 3, 2], [11, 3, 2], [9, 8, 7], [7, 2, 1],
+    [5, 3, 2], [9, 3, 1], [7, 3, 1], [9, 8, 3], [9, 4, 2], [8, 5, 3], [15, 14, 10], [10, 5, 2],
+    [9, 6, 2], [9, 3, 2], [9, 5, 2], [11, 10, 1], [7, 3, 2], [11, 2, 1], [9, 7, 4], [4, 3, 1],
+    [8, 3, 1], [7, 4, 1], [7, 2, 1], [13, 11, 6], [5, 3, 2], [7, 3, 2], [8, 7, 5], [12, 3, 2],
+    [13, 10, 6], [5, 3, 2], [5, 3, 2], [9, 5, 2], [9, 7, 2], [13, 4, 3], [4, 3, 1], [11, 6, 4],
+    [18, 9, 6], [19, 18, 13], [11, 3, 2], [15, 9, 6], [4, 3, 1], [16, 5, 2], [15, 14, 6], [8, 5, 2],
+    [15, 11, 2], [11, 6, 2], [7, 5, 3], [8, 3, 1], [19, 16, 9], [11, 9, 6], [15, 7, 6], [13, 4, 3],
+    [14, 13, 3], [13, 6, 3], [9, 5, 2], [19, 13, 6], [19, 10, 3], [11, 6, 5], [9, 2, 1], [14, 3, 2],
+    [13, 3, 1], [7, 5, 4], [11, 9, 8], [11, 6, 5], [23, 16, 9], [19, 14, 6], [23, 10, 2], [8, 3, 2],
+    [5, 4, 3], [9, 6, 4], [4, 3, 2], [13, 8, 6], [13, 11, 1], [13, 10, 3], [11, 6, 5], [19, 17, 4],
+    [15, 14, 7], [13, 9, 6], [9, 7, 3], [9, 7, 1], [14, 3, 2], [11, 8, 2], [11, 6, 4], [13, 5, 2],
+    [11, 5, 1], [11, 4, 1], [19, 10, 3], [21, 10, 6], [13, 3, 1], [15, 7, 5], [19, 18, 10],
+    [7, 5, 3], [12, 7, 2], [7, 5, 1], [14, 9, 6], [10, 3, 2], [15, 13, 12], [12, 11, 9], [16, 9, 7],
+    [12, 9, 3], [9, 5, 2], [17, 10, 6], [24, 9, 3], [17, 15, 13], [5, 4, 3], [19, 17, 8],
+    [15, 6, 3], [19, 6, 1],
+];
+
+fn degree_valid(degree: usize) -> bool {
+    (8..=MAX_DEGREE).contains(&degree) && degree.is_multiple_of(8)
+}
+
+/// GF(2^degree) with elements as little-endian `u64` limbs.
+struct BinaryField {
+    degree: usize,
+    taps: [u8; 3],
+}
+
+impl BinaryField {
+    fn new(degree: usize) -> Self {
+        BinaryField {
+            degree,
+            taps: IRREDUCIBLE[degree / 8 - 1],
+        }
+    }
+
+    fn limbs(&self) -> usize {
+        self.degree.div_ceil(64)
+    }
+
+    fn zero(&self) -> Vec<u64> {
+        vec![0; self.limbs()]
+    }
+
+    fn element(&self, index: usize) -> Vec<u64> {
+        let mut element = self.zero();
+        element[0] = index as u64;
+        element
+    }
+
+    /// Reads `degree / 8` big-endian bytes.
+    fn read(&self, bytes: &[u8]) -> Vec<u64> {
+        let mut element = self.zero();
+        for (i, &byte) in bytes.iter().rev().enumerate() {
+            element[i / 8] |= (byte as u64) << (8 * (i % 8));
+        }
+        element
+    }
+
+    fn write(&self, element: &[u64]) -> Vec<u8> {
+        let len = self.degree / 8;
+        (0..len)
+            .rev()
+            .map(|i| (element[i / 8] >> (8 * (i % 8))) as u8)
+            .collect()
+    }
+
+    /// Multiplies by `x`, reducing modulo the field polynomial.
+    fn shift(&self, element: &mut [u64]) {
+        let top = self.degree - 1;
+        let carry = (element[top / 64] >> (top % 64)) & 1;
+        for i in (0..element.len()).rev() {
+            let low = if i == 0 { 0 } else { element[i - 1] >> 63 };
+            element[i] = (element[i] << 1) | low;
+        }
+        if !self.degree.is_multiple_of(64) {
+            element[self.degree / 64] &= (1 << (self.degree % 64)) - 1;
+        }
+        let mask = 0u64.wrapping_sub(carry);
+        element[0] ^= mask & 1;
+        for &tap in &self.taps {
+            element[tap as usize / 64] ^= mask & (1 << (tap % 64));
+        }
+    }
+
+    fn mul(&self, a: &[u64], b: &[u64]) -> Vec<u64> {
+        let mut product = self.zero();
+        for i in (0..self.degree).rev() {
+            self.shift(&mut product);
+            let mask = 0u64.wrapping_sub((b[i / 64] >> (i % 64)) & 1);
+            for (p, &a) in product.iter_mut().zip(a) {
+                *p ^= mask & a;
+            }
+        }
+        product
+    }
+
+    fn add(a: &mut [u64], b: &[u64]) {
+        for (a, &b) in a.iter_mut().zip(b) {
+            *a ^= b;
+        }
+    }
+
+    /// Inverse of a nonzero `a` by the binary extended Euclidean algorithm.
+    /// Only used on denominators built from public share indices, so it
+    /// need not run in constant time.
+    fn inverse(&self, a: &[u64]) -> Vec<u64> {
+        let width = self.degree / 64 + 1;
+        let mut modulus = vec![0u64; width];
+        modulus[0] = 1;
+        modulus[self.degree / 64] |= 1 << (self.degree % 64);
+        for &tap in &self.taps {
+            modulus[tap as usize / 64] |= 1 << (tap % 64);
+        }
+        let mut u = a.to_vec();
+        u.resize(width, 0);
+        let mut v = modulus.clone();
+        let mut g1 = vec![0u64; width];
+        g1[0] = 1;
+        let mut g2 = vec![0u64; width];
+
+        let is_one = |x: &[u64]| x[0] == 1 && x[1..].iter().all(|&limb| limb == 0);
+        let halve = |x: &mut [u64], g: &mut [u64]| {
+            while x[0] & 1 == 0 {
+                shift_right(x);
+                if g[0] & 1 == 1 {
+                    BinaryField::add(g, &modulus);
+                }
+                shift_right(g);
+            }
+        };
+        while !is_one(&u) && !is_one(&v) {
+            halve(&mut u, &mut g1);
+            halve(&mut v, &mut g2);
+            if bit_length(&u) > bit_length(&v) {
+                BinaryField::add(&mut u, &v);
+                BinaryField::add(&mut g1, &g2);
+            } else {
+                BinaryField::add(&mut v, &u);
+                BinaryField::add(&mut g2, &g1);
+            }
+        }
+        let mut result = if is_one(&u) { g1 } else { g2 };
+        result.truncate(self.limbs());
+        result
+    }
+}
+
+fn shift_right(x: &mut [u64]) {
+    for i in 0..x.len() {
+        let high = x.get(i + 1).map_or(0, |next| next << 63);
+        x[i] = (x[i] >> 1) | high;
+    }
+}
+
+fn bit_length(x: &[u64]) -> usize {
+    x.iter()
+        .rposition(|&limb| limb != 0)
+        .map_or(0, |i| 64 * i + 64 - x[i].leading_zeros() as usize)
+}
+
+/// XTEA with an all-zero key, as in `ssss`.
+fn encipher_block(v: &mut [u32; 2]) {
+    let mut sum = 0u32;
+    for _ in 0..32 {
+        v[0] = v[0].wrapping_add(((v[1] << 4) ^ (v[1] >> 5)).wrapping_add(v[1]) ^ sum);
+        sum = sum.wrapping_add(0x9e37_79b9);
+        v[1] = v[1].wrapping_add(((v[0] << 4) ^ (v[0] >> 5)).wrapping_add(v[0]) ^ sum);
+    }
+}
+
+fn decipher_block(v: &mut [u32; 2]) {
+    let mut sum = 0xc6ef_3720u32;
+    for _ in 0..32 {
+        v[1] = v[1].wrapping_sub(((v[0] << 4) ^ (v[0] >> 5)).wrapping_add(v[0]) ^ sum);
+        sum = sum.wrapping_sub(0x9e37_79b9);
+        v[0] = v[0].wrapping_sub(((v[1] << 4) ^ (v[1] >> 5)).wrapping_add(v[1]) ^ sum);
+    }
+}
+
+/// Runs `process` over the 8-byte window of `data` starting at `index`,
+/// wrapping around the end.
+fn process_slice(data: &mut [u8], index: usize, process: fn(&mut [u32; 2])) {
+    let len = data.len();
+    let mut block = [0u32; 2];
+    for (i, word) in block.iter_mut().enumerate() {
+        *word = (0..4).fold(0, |acc, j| {
+            (acc << 8) | data[(index + 4 * i + j) % len] as u32
+        });
+    }
+    process(&mut block);
+    for (i, word) in block.iter().enumerate() {
+        for j in 0..4 {
+            data[(index + 4 * i + j) % len] = (word >> (24 - 8 * j)) as u8;
+        }
+    }
+    block.zeroize();
+}
+
+/// Applies or removes the diffusion layer on a big-endian secret.
+///
+/// `ssss` exports the secret as little-endian 16-bit words with big-endian
+/// bytes, so bytes are processed in pairs from the least significant end;
+/// an odd top byte stays in place.
+fn diffuse(secret: &mut [u8], encode: bool) {
+    let len = secret.len();
+    let position = |i: usize| {
+        let from_low = if i ^ 1 < len { i ^ 1 } else { i };
+        len - 1 - from_low
+    };
+    let mut data: Vec<u8> = (0..len).map(|i| secret[position(i)]).collect();
+    if encode {
+        for i in (0..40 * len).step_by(2) {
+            process_slice(&mut data, i, encipher_block);
+        }
+    } else {
+        for i in (0..40 * len).step_by(2).rev() {
+            process_slice(&mut data, i, decipher_block);
+        }
+    }
+    for (i, &byte) in data.iter().enumerate() {
+        secret[position(i)] = byte;
+    }
+    data.zeroize();
+}
+
+/// One share in `ssss` format.
+///
+/// `Display` writes `[token-]index-hex` and `FromStr` parses it; the index
+/// keeps its zero padding. The share value is wiped when it is dropped.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SsssShare {
+    token: Option<String>,
+    index: usize,
+    width: usize,
+    value: Vec<u8>,
+}
+
+impl SsssShare {
+    pub fn token(&self) -> Option<&str> {
+        self.token.as_deref()
+    }
+
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Security level in bits, four per hex digit of the share.
+    pub fn security(&self) -> usize {
+        8 * self.value.len()
+    }
+}
+
+impl Zeroize for SsssShare {
+    fn zeroize(&mut self) {
+        self.value.zeroize();
+    }
+}
+
+impl Drop for SsssShare {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl ZeroizeOnDrop for SsssShare {}
+
+impl fmt::Display for SsssShare {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(token) = &self.token {
+            write!(f, "{token}-")?;
+        }
+        write!(f, "{:0width$}-", self.index, width = self.width)?;
+        let mut hex = hex::encode(&self.value);
+        let result = f.write_str(&hex);
+        hex.zeroize();
+        result
+    }
+}
+
+impl FromStr for SsssShare {
+    type Err = ShamirError;
+
+    /// Parses `index-hex` or `token-index-hex`, as `ssss-combine` does.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let malformed = ShamirError::MalformedShare;
+        let s = s.trim();
+        let (first, rest) = s.split_once('-').ok_or(malformed("invalid ssss share"))?;
+        let (token, index, data) = match rest.split_once('-') {
+            Some((index, data)) => (Some(first), index, data),
+            None => (None, first, rest),
+        };
+        if index.is_empty() || !index.bytes().all(|byte| byte.is_ascii_digit()) {
+            return Err(malformed("invalid ssss share index"));
+        }
+        let parsed: usize = index
+            .parse()
+            .map_err(|_| malformed("invalid ssss share index"))?;
+        if parsed == 0 {
+            return Err(malformed("invalid ssss share index"));
+        }
+        if !degree_valid(4 * data.len()) {
+            return Err(malformed("ssss share has an illegal length"));
+        }
+        let value = hex::decode(data).map_err(|_| malformed("invalid ssss share hex"))?;
+        if value.len() < 8 && parsed >> (8 * value.len()) != 0 {
+            return Err(malformed("ssss share index exceeds the field"));
+        }
+        Ok(SsssShare {
+            token: token.map(String::from),
+            index: parsed,
+            width: index.len(),
+            value,
+        })
+    }
+}
+
+/// Parameters of an `ssss-split` / `ssss-combine` run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SsssScheme {
+    threshold: usize,
+    shares: usize,
+    security: Option<usize>,
+    diffusion: bool,
+    token: Option<String>,
+}
+
+impl SsssScheme {
+    /// `threshold` of `shares` recover the secret, as `-t` and `-n`.
+    pub fn new(threshold: usize, shares: usize) -> Result<Self, ShamirError> {
+        if threshold < 2 || shares < threshold {
+            return Err(ShamirError::InvalidThreshold { threshold, shares });
+        }
+        Ok(SsssScheme {
+            threshold,
+            shares,
+            security: None,
+            diffusion: true,
+            token: None,
+        })
+    }
+
+    /// Fixed security level in bits, as `-s`; a multiple of 8 up to 1024.
+    /// By default it is the secret's length in bits.
+    pub fn security(mut self, bits: usize) -> Self {
+        self.security = Some(bits);
+        self
+    }
+
+    /// Whether the diffusion layer is applied, as the absence of `-D`.
+    /// Defaults to `true`; it is skipped below 64 bits, as in `ssss`.
+    pub fn diffusion(mut self, diffusion: bool) -> Self {
+        self.diffusion = diffusion;
+        self
+    }
+
+    /// Prefixes every share with `token-`, as `-w`.
+    pub fn token(mut self, token: &str) -> Self {
+        self.token = Some(token.into());
+        self
+    }
+
+    /// Splits `secret`, read as a big-endian number the way `ssss-split`
+    /// reads ASCII input.
+    pub fn split_with_rng<R: RngCore + CryptoRng + ?Sized>(
+        &self,
+        secret: &[u8],
+        rng: &mut R,
+    ) -> Result<Vec<SsssShare>, ShamirError> {
+        let degree = self.security.unwrap_or(8 * secret.len());
+        if !degree_valid(degree) || 8 * secret.len() > degree {
+            return Err(ShamirError::InvalidKey(
+                "ssss security level must be a multiple of 8 up to 1024 bits and fit the secret",
+            ));
+        }
+        if let Some(token) = &self.token {
+            if token.is_empty()
+                || token.len() > MAX_TOKEN_LEN
+                || token.contains(|c: char| c == '-' || c.is_whitespace())
+            {
+                return Err(ShamirError::InvalidKey(
+                    "ssss token must be 1 to 128 characters without dashes or spaces",
+                ));
+            }
+        }
+        if degree < 64 && self.shares >> degree != 0 {
+            return Err(ShamirError::InvalidThreshold {
+                threshold: self.threshold,
+                shares: self.shares,
+            });
+        }
+
+        let field = BinaryField::new(degree);
+        let mut padded = vec![0u8; degree / 8];
+        padded[degree / 8 - secret.len()..].copy_from_slice(secret);
+        if self.diffusion && degree >= DIFFUSION_MIN_DEGREE {
+            diffuse(&mut padded, true);
+        }
+        let mut coefficients = vec![field.read(&padded)];
+        padded.zeroize();
+        let mut random = vec![0u8; degree / 8];
+        for _ in 1..self.threshold {
+            rng.fill_bytes(&mut random);
+            coefficients.push(field.read(&random));
+        }
+        random.zeroize();
+
+        let width = self.shares.to_string().len();
+        let shares = (1..=self.shares)
+            .map(|index| {
+                let x = field.element(index);
+                let (last, rest) = coefficients.split_last().expect("threshold >= 2");
+                let mut y = last.clone();
+                for coefficient in rest.iter().rev() {
+                    let mut product = field.mul(&y, &x);
+                    BinaryField::add(&mut product, coefficient);
+                    y.zeroize();
+                    y = product;
+                }
+                let value = field.write(&y);
+                y.zeroize();
+                SsssShare {
+                    token: self.token.clone(),
+                    index,
+                    width,
+                    value,
+                }
+            })
+            .collect();
+        coefficients.iter_mut().for_each(|c| c.zeroize());
+        Ok(shares)
+    }
+
+    /// Recovers the secret from the first `threshold` of `shares`, as
+    /// `ssss-combine -t`. The result is as long as the security level, so a
+    /// secret split at a fixed level comes back left-padded with zero bytes.
+    pub fn combine(&self, shares: &[SsssShare]) -> Result<Vec<u8>, ShamirError> {
+        if shares.len() < self.threshold {
+            return Err(ShamirError::NotEnoughShares {
+                required: self.threshold,
+                provided: shares.len(),
+            });
+        }
+        let shares = &shares[..self.threshold];
+        let degree = shares[0].security();
+        if shares.iter().any(|share| share.security() != degree) {
+            return Err(ShamirError::SetMismatch);
+        }
+        let field = BinaryField::new(degree);
+        let xs: Vec<Vec<u64>> = shares
+            .iter()
+            .map(|share| field.element(share.index))
+            .collect();
+
+        let mut secret = field.zero();
+        for (i, share) in shares.iter().enumerate() {
+            // Lagrange basis at zero: prod x_j / (x_i + x_j) over j != i.
+            let mut numerator = field.element(1);
+            let mut denominator = field.element(1);
+            for (j, xj) in xs.iter().enumerate() {
+                if i == j {
+                    continue;
+                }
+                if shares[j].index == share.index {
+                    return Err(ShamirError::DuplicateShareX(BigUint::from(share.index)));
+                }
+                numerator = field.mul(&numerator, xj);
+                let mut difference = xs[i].clone();
+                BinaryField::add(&mut difference, xj);
+                denominator = field.mul(&denominator, &difference);
+            }
+            let basis = field.mul(&numerator, &field.inverse(&denominator));
+            let mut y = field.read(&share.value);
+            let mut term = field.mul(&basis, &y);
+            BinaryField::add(&mut secret, &term);
+            y.zeroize();
+            term.zeroize();
+        }
+        let mut bytes = field.write(&secret);
+        secret.zeroize();
+        if self.diffusion && degree >= DIFFUSION_MIN_DEGREE {
+            diffuse(&mut bytes, false);
+        }
+        Ok(bytes)
+    }
+}
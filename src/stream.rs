@@ -0,0 +1,197 @@
+//! Streaming split and reconstruction over GF(2^8).
+//!
+//! Large inputs are shared without being held in memory: every byte is
+//! shared independently over GF(2^8), as in [`gf256_split`](crate::gf256_split),
+//! so a [`ShareWriter`] can process the input in fixed-size chunks and a
+//! [`ShareReader`] can recombine the share streams chunk by chunk. Each
+//! share stream starts with a 6-byte header: the magic `PSS`, a format
+//! version, the share's x-coordinate and the threshold.
+
+use std::io::{self, Read, Write};
+
+use num_bigint::BigUint;
+use rand::{CryptoRng, RngCore};
+use zeroize::Zeroize;
+
+use crate::field::{field_reconstruct, field_split, FieldShare};
+use crate::{Gf256, ShamirError};
+
+/// Bytes processed per chunk.
+pub const STREAM_CHUNK_SIZE: usize = 64 * 1024;
+
+const MAGIC: &[u8; 3] = b"PSS";
+const VERSION: u8 = 1;
+
+fn invalid(err: ShamirError) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, err)
+}
+
+/// Splits everything written to it into one share stream per sink.
+pub struct ShareWriter<W: Write, R: RngCore + CryptoRng> {
+    sinks: Vec<W>,
+    threshold: usize,
+    rng: R,
+    started: bool,
+}
+
+impl<W: Write, R: RngCore + CryptoRng> ShareWriter<W, R> {
+    /// One share stream is written to each of `sinks`, with x-coordinates
+    /// `1..=sinks.len()`. Requires `2 <= threshold <= sinks.len() <= 255`.
+    pub fn new(sinks: Vec<W>, threshold: usize, rng: R) -> Result<Self, ShamirError> {
+        if threshold < 2 || sinks.len() < threshold || sinks.len() > 255 {
+            return Err(ShamirError::InvalidThreshold {
+                threshold,
+                shares: sinks.len(),
+            });
+        }
+        Ok(ShareWriter {
+            sinks,
+            threshold,
+            rng,
+            started: false,
+        })
+    }
+
+    fn start(&mut self) -> io::Result<()> {
+        if !self.started {
+            for (i, sink) in self.sinks.iter_mut().enumerate() {
+                sink.write_all(MAGIC)?;
+                sink.write_all(&[VERSION, i as u8 + 1, self.threshold as u8])?;
+            }
+            self.started = true;
+        }
+        Ok(())
+    }
+
+    /// Writes the headers if nothing was written yet, flushes, and returns
+    /// the sinks.
+    pub fn finish(mut self) -> io::Result<Vec<W>> {
+        self.start()?;
+        self.flush()?;
+        Ok(self.sinks)
+    }
+}
+
+impl<W: Write, R: RngCore + CryptoRng> Write for ShareWriter<W, R> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.start()?;
+        let chunk = &buf[..buf.len().min(STREAM_CHUNK_SIZE)];
+        let mut values: Vec<Gf256> = chunk.iter().map(|&byte| Gf256(byte)).collect();
+        let shares = field_split(&values, self.threshold, self.sinks.len(), &mut self.rng)
+            .map_err(invalid)?;
+        values.zeroize();
+        let mut bytes = Vec::with_capacity(chunk.len());
+        for (sink, share) in self.sinks.iter_mut().zip(&shares) {
+            bytes.clear();
+            bytes.extend(share.values().iter().map(|value| value.0));
+            sink.write_all(&bytes)?;
+        }
+        bytes.zeroize();
+        Ok(chunk.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.sinks.iter_mut().try_for_each(Write::flush)
+    }
+}
+
+/// Recombines share streams written by [`ShareWriter`] into the original
+/// byte stream.
+pub struct ShareReader<R: Read> {
+    sources: Vec<(u8, R)>,
+    threshold: usize,
+}
+
+impl<R: Read> ShareReader<R> {
+    /// Reads the stream headers; at least `threshold` distinct streams are
+    /// needed and extra ones are ignored.
+    pub fn new(sources: Vec<R>) -> io::Result<Self> {
+        let mut threshold = None;
+        let mut headed = Vec::with_capacity(sources.len());
+        for mut source in sources {
+            let mut header = [0u8; 6];
+            source.read_exact(&mut header)?;
+            if &header[..3] != MAGIC || header[3] != VERSION {
+                return Err(invalid(ShamirError::MalformedShare("not a share stream")));
+            }
+            let (x, t) = (header[4], header[5] as usize);
+            if x == 0 || t < 2 {
+                return Err(invalid(ShamirError::MalformedShare(
+                    "invalid stream header",
+                )));
+            }
+            if *threshold.get_or_insert(t) != t {
+                return Err(invalid(ShamirError::ThresholdMismatch));
+            }
+            if headed.iter().any(|(other, _)| *other == x) {
+                return Err(invalid(ShamirError::DuplicateShareX(BigUint::from(x))));
+            }
+            headed.push((x, source));
+        }
+        let threshold = threshold.unwrap_or(2);
+        if headed.len() < threshold {
+            return Err(invalid(ShamirError::NotEnoughShares {
+                required: threshold,
+                provided: headed.len(),
+            }));
+        }
+        headed.truncate(threshold);
+        Ok(ShareReader {
+            sources: headed,
+            threshold,
+        })
+    }
+
+    pub fn threshold(&self) -> usize {
+        self.threshold
+    }
+}
+
+impl<R: Read> Read for ShareReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let want = buf.len().min(STREAM_CHUNK_SIZE);
+        let mut chunk = vec![0u8; want];
+        let (first, rest) = self.sources.split_first_mut().expect("threshold >= 2");
+        let got = first.1.read(&mut chunk)?;
+        chunk.truncate(got);
+        if got == 0 && want != 0 {
+            for (_, source) in rest.iter_mut() {
+                if source.read(&mut [0u8; 1])? != 0 {
+                    return Err(invalid(ShamirError::MalformedShare(
+                        "share streams differ in length",
+                    )));
+                }
+            }
+            return Ok(0);
+        }
+        let mut shares = Vec::with_capacity(self.threshold);
+        shares.push(FieldShare::new(
+            first.0 as u64,
+            self.threshold,
+            chunk.iter().map(|&byte| Gf256(byte)).collect(),
+        ));
+        for (x, source) in rest {
+            chunk.resize(got, 0);
+            source
+                .read_exact(&mut chunk)
+                .map_err(|err| match err.kind() {
+                    io::ErrorKind::UnexpectedEof => invalid(ShamirError::MalformedShare(
+                        "share streams differ in length",
+                    )),
+                    _ => err,
+                })?;
+            shares.push(FieldShare::new(
+                *x as u64,
+                self.threshold,
+                chunk.iter().map(|&byte| Gf256(byte)).collect(),
+            ));
+        }
+        chunk.zeroize();
+        let mut values = field_reconstruct(&shares).map_err(invalid)?;
+        for (out, value) in buf.iter_mut().zip(&values) {
+            *out = value.0;
+        }
+        values.zeroize();
+        Ok(got)
+    }
+}
@@ -0,0 +1,89 @@
+//! Weighted threshold sharing.
+//!
+//! A participant of weight `w` holds `w` ordinary shares, and any group
+//! whose weights sum to the threshold weight can reconstruct.
+
+use alloc::vec::Vec;
+
+use num_bigint::BigUint;
+use rand::{CryptoRng, RngCore};
+
+use crate::{shamir_reconstruct, shamir_split_shares, ShamirError, Share};
+
+/// The shares held by one weighted participant.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WeightedShare {
+    participant: usize,
+    shares: Vec<Share>,
+}
+
+impl WeightedShare {
+    pub fn new(participant: usize, shares: Vec<Share>) -> Self {
+        WeightedShare {
+            participant,
+            shares,
+        }
+    }
+
+    /// Index of the participant in the `weights` passed to [`split_weighted`].
+    pub fn participant(&self) -> usize {
+        self.participant
+    }
+
+    pub fn weight(&self) -> usize {
+        self.shares.len()
+    }
+
+    pub fn shares(&self) -> &[Share] {
+        &self.shares
+    }
+}
+
+/// Splits `secret` so that participant `i` receives `weights[i]` shares and
+/// any participants with combined weight `threshold_weight` can reconstruct.
+pub fn split_weighted<R: RngCore + CryptoRng + ?Sized>(
+    secret: &BigUint,
+    weights: &[usize],
+    threshold_weight: usize,
+    modulus: &BigUint,
+    rng: &mut R,
+) -> Result<Vec<WeightedShare>, ShamirError> {
+    if weights.contains(&0) {
+        return Err(ShamirError::InvalidAccessStructure(
+            "every participant needs a positive weight",
+        ));
+    }
+    let total: usize = weights.iter().sum();
+    let mut shares =
+        shamir_split_shares(secret, threshold_weight, total, modulus, rng)?.into_iter();
+    Ok(weights
+        .iter()
+        .enumerate()
+        .map(|(participant, &weight)| {
+            WeightedShare::new(participant, shares.by_ref().take(weight).collect())
+        })
+        .collect())
+}
+
+/// Recovers the secret once the supplied participants' weights reach the
+/// threshold weight. A participant supplied twice fails with
+/// [`ShamirError::DuplicateParticipant`].
+pub fn reconstruct_weighted(
+    shares: &[WeightedShare],
+    modulus: &BigUint,
+) -> Result<BigUint, ShamirError> {
+    for (i, share) in shares.iter().enumerate() {
+        if shares[..i]
+            .iter()
+            .any(|other| other.participant == share.participant)
+        {
+            return Err(ShamirError::DuplicateParticipant(share.participant));
+        }
+    }
+    let flat: Vec<Share> = shares
+        .iter()
+        .flat_map(|share| share.shares.iter().cloned())
+        .collect();
+    shamir_reconstruct(&flat, modulus)
+}
@@ -0,0 +1,13 @@
+use alloc::vec;
+
+use num_bigint::BigUint;
+
+/// Overwrites the limbs of `value` with zeros and leaves it equal to zero.
+///
+/// `BigUint` does not implement `Zeroize`, so this reuses its allocation for
+/// a zero-filled assignment before normalizing. Copies made earlier by
+/// arithmetic or reallocation are not reachable and cannot be wiped.
+pub fn zeroize_biguint(value: &mut BigUint) {
+    let limbs = value.bits().div_ceil(32) as usize;
+    value.assign_from_slice(&vec![0u32; limbs]);
+}